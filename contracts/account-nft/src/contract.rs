@@ -4,6 +4,7 @@ use cosmwasm_std::{
     to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
 };
 use cw2::set_contract_version;
+use cw721::Cw721Execute;
 use cw721_base::Cw721Contract;
 use mars_account_nft_types::{
     msg::{ExecuteMsg, InstantiateMsg, MigrateV1ToV2, QueryMsg},
@@ -12,9 +13,9 @@ use mars_account_nft_types::{
 
 use crate::{
     error::ContractError,
-    execute::{burn, mint, update_config},
+    execute::{burn, increment_transfer_nonce, mint, update_config},
     migrations::{self},
-    query::{query_config, query_next_id},
+    query::{query_config, query_next_id, query_transfer_nonce},
     state::{CONFIG, NEXT_ID},
 };
 
@@ -71,6 +72,21 @@ pub fn execute(
         ExecuteMsg::Burn {
             token_id,
         } => burn(deps, env, info, token_id),
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => {
+            increment_transfer_nonce(deps.storage, &token_id)?;
+            Parent::default().transfer_nft(deps, env, info, recipient, token_id).map_err(Into::into)
+        }
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => {
+            increment_transfer_nonce(deps.storage, &token_id)?;
+            Parent::default().send_nft(deps, env, info, contract, token_id, msg).map_err(Into::into)
+        }
         ExecuteMsg::Migrate(msg) => match msg {
             MigrateV1ToV2::BurnEmptyAccounts {
                 limit,
@@ -85,6 +101,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::NextId {} => to_binary(&query_next_id(deps)?),
+        QueryMsg::TransferNonce {
+            token_id,
+        } => to_binary(&query_transfer_nonce(deps, &token_id)?),
         _ => Parent::default().query(deps, env, msg.try_into()?),
     }
 }
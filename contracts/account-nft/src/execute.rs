@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    to_binary, DepsMut, Empty, Env, MessageInfo, QueryRequest, Response, WasmQuery,
+    to_binary, DepsMut, Empty, Env, MessageInfo, QueryRequest, Response, StdResult, Storage,
+    WasmQuery,
 };
 use cw721::Cw721Execute;
 use cw721_base::{
@@ -16,9 +17,21 @@ use crate::{
     error::ContractError::{
         self, BaseError, BurnNotAllowed, CreditManagerContractNotSet, HealthContractNotSet,
     },
-    state::{CONFIG, NEXT_ID},
+    state::{CONFIG, NEXT_ID, TRANSFER_NONCES},
 };
 
+/// Counts transfers of the token, so that the credit manager can drop trigger orders and
+/// delegations made before the account last changed hands, even if it has returned to the
+/// same owner since
+pub fn increment_transfer_nonce(
+    storage: &mut dyn Storage,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    TRANSFER_NONCES
+        .update(storage, token_id, |nonce| -> StdResult<_> { Ok(nonce.unwrap_or_default() + 1) })?;
+    Ok(())
+}
+
 pub fn mint(deps: DepsMut, info: MessageInfo, user: &str) -> Result<Response, ContractError> {
     let next_id = NEXT_ID.load(deps.storage)?;
     NEXT_ID.save(deps.storage, &(next_id + 1))?;
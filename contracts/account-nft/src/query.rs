@@ -1,7 +1,7 @@
 use cosmwasm_std::{Deps, StdResult};
 use mars_account_nft_types::nft_config::UncheckedNftConfig;
 
-use crate::state::{CONFIG, NEXT_ID};
+use crate::state::{CONFIG, NEXT_ID, TRANSFER_NONCES};
 
 pub fn query_config(deps: Deps) -> StdResult<UncheckedNftConfig> {
     Ok(CONFIG.load(deps.storage)?.into())
@@ -10,3 +10,7 @@ pub fn query_config(deps: Deps) -> StdResult<UncheckedNftConfig> {
 pub fn query_next_id(deps: Deps) -> StdResult<String> {
     Ok(NEXT_ID.load(deps.storage)?.to_string())
 }
+
+pub fn query_transfer_nonce(deps: Deps, token_id: &str) -> StdResult<u64> {
+    Ok(TRANSFER_NONCES.may_load(deps.storage, token_id)?.unwrap_or_default())
+}
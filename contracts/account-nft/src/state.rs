@@ -1,9 +1,10 @@
 use cosmwasm_schema::cw_serde;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use mars_account_nft_types::nft_config::NftConfig;
 
 pub const CONFIG: Item<NftConfig> = Item::new("config");
 pub const NEXT_ID: Item<u64> = Item::new("next_id");
+pub const TRANSFER_NONCES: Map<&str, u64> = Map::new("transfer_nonces"); // Map<TokenId, Nonce>
 
 /// Helper marker used during burning empty accounts. Used only for v1 -> v2 migration.
 #[cw_serde]
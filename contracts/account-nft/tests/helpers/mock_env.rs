@@ -54,6 +54,18 @@ impl MockEnv {
         self.app.wrap().query_wasm_smart(self.nft_contract.clone(), &QueryMsg::NextId {}).unwrap()
     }
 
+    pub fn query_transfer_nonce(&mut self, token_id: &str) -> u64 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.nft_contract.clone(),
+                &QueryMsg::TransferNonce {
+                    token_id: token_id.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
     // Double checking ownership by querying NFT account-nft for correct owner
     pub fn assert_owner_is_correct(&mut self, user: &Addr, token_id: &str) {
         let owner_res: OwnerOfResponse = self
@@ -141,6 +153,23 @@ impl MockEnv {
         Ok(attr.first().unwrap().to_string())
     }
 
+    pub fn transfer(
+        &mut self,
+        sender: &Addr,
+        recipient: &Addr,
+        token_id: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.nft_contract.clone(),
+            &ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.to_string(),
+            },
+            &[],
+        )
+    }
+
     pub fn burn(&mut self, sender: &Addr, token_id: &str) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             sender.clone(),
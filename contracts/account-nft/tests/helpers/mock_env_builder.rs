@@ -152,6 +152,11 @@ impl MockEnvBuilder {
                         zapper: "n/a".to_string(),
                         health_contract: "n/a".to_string(),
                         rewards_collector: None,
+                        keeper_fee: None,
+                        flash_loan_fee: Decimal::zero(),
+                        liquidation_auction: None,
+                        bad_debt_dust_threshold: Default::default(),
+                        config_update_delay: 0,
                     },
                 },
                 &[],
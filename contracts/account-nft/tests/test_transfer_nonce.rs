@@ -0,0 +1,41 @@
+use cosmwasm_std::Addr;
+
+use crate::helpers::MockEnv;
+
+pub mod helpers;
+
+#[test]
+fn transfers_increment_nonce() {
+    let mut mock = MockEnv::new().build().unwrap();
+
+    let user_1 = Addr::unchecked("user_1");
+    let user_2 = Addr::unchecked("user_2");
+    let token_id = mock.mint(&user_1).unwrap();
+    assert_eq!(mock.query_transfer_nonce(&token_id), 0);
+
+    mock.transfer(&user_1, &user_2, &token_id).unwrap();
+    assert_eq!(mock.query_transfer_nonce(&token_id), 1);
+
+    // Returning the token to its previous owner does not restore the nonce
+    mock.transfer(&user_2, &user_1, &token_id).unwrap();
+    assert_eq!(mock.query_transfer_nonce(&token_id), 2);
+    mock.assert_owner_is_correct(&user_1, &token_id);
+
+    // A failed transfer leaves the nonce as is
+    mock.transfer(&user_2, &user_1, &token_id).unwrap_err();
+    assert_eq!(mock.query_transfer_nonce(&token_id), 2);
+}
+
+#[test]
+fn nonce_is_kept_per_token() {
+    let mut mock = MockEnv::new().build().unwrap();
+
+    let user_1 = Addr::unchecked("user_1");
+    let user_2 = Addr::unchecked("user_2");
+    let token_1 = mock.mint(&user_1).unwrap();
+    let token_2 = mock.mint(&user_1).unwrap();
+
+    mock.transfer(&user_1, &user_2, &token_1).unwrap();
+    assert_eq!(mock.query_transfer_nonce(&token_1), 1);
+    assert_eq!(mock.query_transfer_nonce(&token_2), 0);
+}
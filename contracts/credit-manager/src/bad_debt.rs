@@ -0,0 +1,119 @@
+use std::cmp::min;
+
+use cosmwasm_std::{Coin, CosmosMsg, Deps, DepsMut, Env, Response, StdResult, Storage, Uint128};
+use cw_paginate::paginate_map;
+use cw_storage_plus::Bound;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    error::{ContractError, ContractResult},
+    msg::{execute::CallbackMsg, query::BadDebtResponseItem},
+    reentrancy_guard::GuardScope,
+};
+
+use crate::{
+    health::query_health_values,
+    query::{query_coin_balances, query_debt_amounts},
+    state::{
+        BAD_DEBT, BAD_DEBT_DUST_THRESHOLD, COIN_BALANCES, REENTRANCY_GUARD, REWARDS_COLLECTOR,
+    },
+    utils::{decrement_coin_balance, increment_coin_balance},
+};
+
+/// Once a credit account's collateral is worth no more than the dust threshold, its outstanding
+/// debt is not worth liquidating. The debt is repaid from the coin balances of the
+/// rewards-collector account, which act as an insurance fund. Debt denoms the rewards-collector
+/// does not hold enough of are only partially written off and the rest can be written off once
+/// it does.
+///
+/// Anyone can call this, so it locks globally: a contract called in the middle of a dispatch
+/// could otherwise empty the account and have its debt written off before health is asserted.
+pub fn write_off_bad_debt(deps: DepsMut, env: Env, account_id: &str) -> ContractResult<Response> {
+    REENTRANCY_GUARD.try_lock(deps.storage, &GuardScope::Global)?;
+
+    let health = query_health_values(deps.as_ref(), account_id, ActionKind::Liquidation)?;
+    let dust_threshold = BAD_DEBT_DUST_THRESHOLD.may_load(deps.storage)?.unwrap_or_default();
+    if health.total_collateral_value > dust_threshold || health.total_debt_value.is_zero() {
+        return Err(ContractError::NoBadDebt {
+            account_id: account_id.to_string(),
+        });
+    }
+
+    let rewards_collector_account = REWARDS_COLLECTOR.load(deps.storage)?.account_id;
+
+    let mut msgs = vec![];
+    let mut response = Response::new()
+        .add_attribute("action", "write_off_bad_debt")
+        .add_attribute("account_id", account_id)
+        .add_attribute("rewards_collector_account_id", rewards_collector_account.clone());
+
+    // Dust coin balances go to the insurance fund rather than staying withdrawable by the owner
+    for coin in query_coin_balances(deps.as_ref(), account_id)? {
+        decrement_coin_balance(deps.storage, account_id, &coin)?;
+        increment_coin_balance(deps.storage, &rewards_collector_account, &coin)?;
+        response = response.add_attribute("dust_swept", coin.to_string());
+    }
+
+    for debt in query_debt_amounts(deps.as_ref(), account_id)? {
+        let insurance_balance = COIN_BALANCES
+            .may_load(deps.storage, (&rewards_collector_account, &debt.denom))?
+            .unwrap_or_default();
+        let coin_written_off = Coin {
+            denom: debt.denom,
+            amount: min(debt.amount, insurance_balance),
+        };
+        if coin_written_off.amount.is_zero() {
+            continue;
+        }
+
+        record_bad_debt(deps.storage, account_id, &coin_written_off)?;
+
+        msgs.push(CallbackMsg::RepayForRecipient {
+            benefactor_account_id: rewards_collector_account.clone(),
+            recipient_account_id: account_id.to_string(),
+            coin: (&coin_written_off).into(),
+        });
+
+        response = response.add_attribute("coin_written_off", coin_written_off.to_string());
+    }
+
+    if msgs.is_empty() {
+        return Err(ContractError::NoInsuranceBalance {
+            account_id: account_id.to_string(),
+        });
+    }
+
+    msgs.push(CallbackMsg::RemoveReentrancyGuard {
+        scope: GuardScope::Global,
+    });
+
+    let msgs = msgs
+        .iter()
+        .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(response.add_messages(msgs))
+}
+
+fn record_bad_debt(storage: &mut dyn Storage, account_id: &str, coin: &Coin) -> ContractResult<()> {
+    BAD_DEBT.update(storage, (account_id, &coin.denom), |total| -> ContractResult<_> {
+        Ok(total.unwrap_or_default().checked_add(coin.amount)?)
+    })?;
+    Ok(())
+}
+
+pub fn query_bad_debt(
+    deps: Deps,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BadDebtResponseItem>> {
+    let start = start_after
+        .as_ref()
+        .map(|(account_id, denom)| Bound::exclusive((account_id.as_str(), denom.as_str())));
+    paginate_map(&BAD_DEBT, deps.storage, start, limit, |(account_id, denom), amount: Uint128| {
+        Ok(BadDebtResponseItem {
+            account_id,
+            denom,
+            amount,
+        })
+    })
+}
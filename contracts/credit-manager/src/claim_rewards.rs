@@ -1,10 +1,12 @@
 use cosmwasm_std::{
-    to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, QuerierWrapper, Response, StdResult,
-    WasmMsg,
+    to_binary, Addr, Coin, CosmosMsg, DepsMut, Env, QuerierWrapper, Response, StdResult, WasmMsg,
 };
 use mars_rover::{
     error::{ContractError, ContractResult},
-    msg::{execute::CallbackMsg, ExecuteMsg},
+    msg::{
+        execute::{CallbackMsg, Recipient},
+        ExecuteMsg,
+    },
     traits::Denoms,
 };
 
@@ -14,7 +16,7 @@ pub fn claim_rewards(
     deps: DepsMut,
     env: Env,
     account_id: &str,
-    recipient: Addr,
+    recipient: Recipient,
 ) -> ContractResult<Response> {
     let incentives = INCENTIVES.load(deps.storage)?;
 
@@ -41,14 +43,14 @@ pub fn claim_rewards(
         .add_message(msg)
         .add_attribute("action", "claim_rewards")
         .add_attribute("account_id", account_id)
-        .add_attribute("recipient", recipient.to_string()))
+        .add_attribute("recipient", recipient.address().to_string()))
 }
 
 fn send_rewards_msg(
     querier: &QuerierWrapper,
     credit_manager_addr: &Addr,
     account_id: &str,
-    recipient: Addr,
+    recipient: Recipient,
     denoms: Vec<&str>,
 ) -> StdResult<CosmosMsg> {
     let coins = denoms
@@ -70,7 +72,7 @@ pub fn send_rewards(
     deps: DepsMut,
     credit_manager_addr: &Addr,
     account_id: &str,
-    recipient: Addr,
+    recipient: Recipient,
     previous_balances: Vec<Coin>,
 ) -> ContractResult<Response> {
     let coins = previous_balances
@@ -86,14 +88,11 @@ pub fn send_rewards(
         .collect::<StdResult<Vec<_>>>()?;
 
     // send coin to recipient
-    let transfer_msg = CosmosMsg::Bank(BankMsg::Send {
-        to_address: recipient.to_string(),
-        amount: coins,
-    });
+    let transfer_msg = recipient.send_msg(coins);
 
     Ok(Response::new()
         .add_message(transfer_msg)
         .add_attribute("action", "callback/send_rewards")
         .add_attribute("account_id", account_id)
-        .add_attribute("recipient", recipient.to_string()))
+        .add_attribute("recipient", recipient.address().to_string()))
 }
@@ -0,0 +1,276 @@
+use std::cmp::min;
+
+use cosmwasm_std::{Coin, CosmosMsg, Decimal, DepsMut, Env, Response, StdResult, Uint128};
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    error::ContractResult,
+    msg::execute::{ActionAmount, ActionCoin, CallbackMsg, PausableAction},
+};
+
+use crate::{
+    pause::assert_not_paused,
+    query::query_positions,
+    state::{ORACLE, ZAPPER},
+    utils::{assert_coin_is_whitelisted, assert_slippage},
+};
+
+/// The first step of closing the account's positions. Assets are made available as coin balances
+/// (lends reclaimed, unlocked vault positions exited, LP tokens withdrawn from their pools) before
+/// the debts are repaid and the remainder is swapped into `target_denom`. Each step needs the
+/// coin balances resulting from the previous one, so they are dispatched as separate callbacks.
+pub fn close_position(
+    mut deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    target_denom: &str,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    assert_slippage(deps.storage, slippage)?;
+    assert_coin_is_whitelisted(&mut deps, target_denom)?;
+
+    let positions = query_positions(deps.as_ref(), account_id)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "close_position")
+        .add_attribute("account_id", account_id)
+        .add_attribute("target_denom", target_denom);
+
+    let mut callbacks = vec![];
+
+    for lend in positions.lends {
+        assert_not_paused(deps.storage, PausableAction::Reclaim, &[lend.denom.clone()])?;
+        callbacks.push(CallbackMsg::Reclaim {
+            account_id: account_id.to_string(),
+            coin: ActionCoin {
+                denom: lend.denom,
+                amount: ActionAmount::AccountBalance,
+            },
+        });
+    }
+
+    for v in positions.vaults {
+        let unlocked = v.amount.unlocked();
+        if !unlocked.is_zero() {
+            assert_not_paused(
+                deps.storage,
+                PausableAction::ExitVault,
+                &[v.vault.address.to_string()],
+            )?;
+            callbacks.push(CallbackMsg::ExitVault {
+                account_id: account_id.to_string(),
+                vault: v.vault.clone(),
+                amount: unlocked,
+            });
+        }
+
+        // Locked and unlocking positions can not be exited right away, they are left in place
+        let locked = v.amount.locked();
+        if !locked.is_zero() {
+            response = response
+                .add_attribute("locked_vault_position", format!("{}:{}", v.vault.address, locked));
+        }
+        for u in v.amount.unlocking().positions() {
+            response = response.add_attribute(
+                "unlocking_vault_position",
+                format!("{}:{}:{}", v.vault.address, u.id, u.coin),
+            );
+        }
+    }
+
+    callbacks.extend([
+        CallbackMsg::WithdrawLiquidityBalances {
+            account_id: account_id.to_string(),
+            slippage,
+        },
+        CallbackMsg::RepayDebtsFromCollateral {
+            account_id: account_id.to_string(),
+            slippage,
+        },
+        CallbackMsg::SwapBalancesInto {
+            account_id: account_id.to_string(),
+            target_denom: target_denom.to_string(),
+            slippage,
+        },
+    ]);
+
+    Ok(response.add_messages(into_cosmos_msgs(&env, &callbacks)?))
+}
+
+/// Withdraws the underlying coins of every LP token balance, including those just exited from
+/// vaults, so the debts can be covered with them rather than by swapping the LP tokens. A denom is
+/// taken as an LP token if the zapper can estimate the coins withdrawn for it.
+pub fn withdraw_liquidity_balances(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    let positions = query_positions(deps.as_ref(), account_id)?;
+    let zapper = ZAPPER.load(deps.storage)?;
+
+    let mut callbacks = vec![];
+    for c in positions.deposits {
+        if positions.debts.iter().any(|d| d.denom == c.denom) {
+            continue;
+        }
+        let is_lp_token = zapper
+            .estimate_withdraw_liquidity(&deps.querier, &c)
+            .map_or(false, |coins_out| !coins_out.is_empty());
+        if !is_lp_token {
+            continue;
+        }
+        assert_not_paused(deps.storage, PausableAction::WithdrawLiquidity, &[c.denom.clone()])?;
+        callbacks.push(CallbackMsg::WithdrawLiquidity {
+            account_id: account_id.to_string(),
+            lp_token: ActionCoin {
+                denom: c.denom,
+                amount: ActionAmount::AccountBalance,
+            },
+            slippage,
+        });
+    }
+
+    Ok(Response::new()
+        .add_messages(into_cosmos_msgs(&env, &callbacks)?)
+        .add_attribute("action", "withdraw_liquidity_balances")
+        .add_attribute("account_id", account_id))
+}
+
+/// Covers the part of each debt the account's balance of the debt denom falls short of by
+/// swapping coins that are not a debt denom for exactly that amount. Then repays all debts with
+/// whatever the account holds of the debt denom. The coins left, including any input the swapper
+/// did not spend, are swapped straight into the target denom by the next step.
+pub fn repay_debts_from_collateral(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    let positions = query_positions(deps.as_ref(), account_id)?;
+    let oracle = ORACLE.load(deps.storage)?;
+
+    let mut collaterals = vec![];
+    for coin in positions.deposits.iter() {
+        if !positions.debts.iter().any(|d| d.denom == coin.denom) {
+            let price = oracle.query_price(&deps.querier, &coin.denom, ActionKind::Default)?.price;
+            collaterals.push((coin.clone(), price));
+        }
+    }
+
+    let mut callbacks = vec![];
+    let mut swapped_into = vec![];
+
+    for debt in positions.debts.iter() {
+        let mut shortfall = debt.amount.saturating_sub(amount_of(&positions.deposits, &debt.denom));
+        if shortfall.is_zero() {
+            continue;
+        }
+
+        // Value of a unit of debt, with the slippage allowance added, in terms of the collateral
+        let debt_price = oracle.query_price(&deps.querier, &debt.denom, ActionKind::Default)?.price;
+        let max_debt_price = debt_price.checked_mul(Decimal::one().checked_add(slippage)?)?;
+
+        for (collateral, price) in collaterals.iter_mut() {
+            if shortfall.is_zero() {
+                break;
+            }
+            if collateral.amount.is_zero() {
+                continue;
+            }
+
+            let coverable =
+                collateral.amount.checked_mul_floor(*price)?.checked_div_floor(max_debt_price)?;
+            let amount_out = min(shortfall, coverable);
+            if amount_out.is_zero() {
+                continue;
+            }
+            let max_amount_in = if amount_out == coverable {
+                collateral.amount
+            } else {
+                min(
+                    amount_out.checked_mul_ceil(max_debt_price)?.checked_div_ceil(*price)?,
+                    collateral.amount,
+                )
+            };
+
+            assert_not_paused(
+                deps.storage,
+                PausableAction::SwapExactOut,
+                &[collateral.denom.clone(), debt.denom.clone()],
+            )?;
+            callbacks.push(CallbackMsg::SwapExactOut {
+                account_id: account_id.to_string(),
+                denom_in: collateral.denom.clone(),
+                coin_out: Coin {
+                    denom: debt.denom.clone(),
+                    amount: amount_out,
+                },
+                max_amount_in: ActionAmount::Exact(max_amount_in),
+            });
+            swapped_into.push(debt.denom.clone());
+
+            collateral.amount = collateral.amount.checked_sub(max_amount_in)?;
+            shortfall = shortfall.checked_sub(amount_out)?;
+        }
+    }
+
+    for debt in positions.debts {
+        let has_balance = !amount_of(&positions.deposits, &debt.denom).is_zero();
+        if has_balance || swapped_into.contains(&debt.denom) {
+            // With no previous balance, the entire balance of the denom is used to repay
+            callbacks.push(CallbackMsg::RepaySwapOutput {
+                account_id: account_id.to_string(),
+                denom: debt.denom,
+                previous_balance: Uint128::zero(),
+                min_amount_out: Uint128::zero(),
+            });
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(into_cosmos_msgs(&env, &callbacks)?)
+        .add_attribute("action", "repay_debts_from_collateral")
+        .add_attribute("account_id", account_id))
+}
+
+pub fn swap_balances_into(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    target_denom: &str,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    let positions = query_positions(deps.as_ref(), account_id)?;
+
+    let mut callbacks = vec![];
+    for c in positions.deposits {
+        if c.denom == target_denom || c.amount.is_zero() {
+            continue;
+        }
+        assert_not_paused(
+            deps.storage,
+            PausableAction::SwapExactIn,
+            &[c.denom.clone(), target_denom.to_string()],
+        )?;
+        callbacks.push(CallbackMsg::SwapExactIn {
+            account_id: account_id.to_string(),
+            coin_in: ActionCoin::from(&c),
+            denom_out: target_denom.to_string(),
+            slippage,
+        });
+    }
+
+    Ok(Response::new()
+        .add_messages(into_cosmos_msgs(&env, &callbacks)?)
+        .add_attribute("action", "swap_balances_into")
+        .add_attribute("account_id", account_id)
+        .add_attribute("target_denom", target_denom))
+}
+
+fn into_cosmos_msgs(env: &Env, callbacks: &[CallbackMsg]) -> StdResult<Vec<CosmosMsg>> {
+    callbacks.iter().map(|callback| callback.into_cosmos_msg(&env.contract.address)).collect()
+}
+
+fn amount_of(coins: &[Coin], denom: &str) -> Uint128 {
+    coins.iter().find(|c| c.denom == denom).map(|c| c.amount).unwrap_or_default()
+}
@@ -9,15 +9,34 @@ use mars_rover::{
 };
 
 use crate::{
-    execute::{create_credit_account, dispatch_actions, execute_callback},
+    bad_debt::{query_bad_debt, write_off_bad_debt},
+    execute::{
+        change_account_kind, create_credit_account, dispatch_actions, dispatch_batch_actions,
+        execute_callback,
+    },
     instantiate::store_config,
+    liquidate::{
+        liquidate_from_wallet, query_liquidatable_since, query_liquidation_preview,
+        update_liquidatable_since,
+    },
+    liquidate_basket::query_basket_liquidation_preview,
+    merge::merge_accounts,
     migrations,
+    pause::{pause_action, query_paused_actions, unpause_action},
+    permission::{grant_permission, revoke_permission},
     query::{
-        query_accounts, query_all_coin_balances, query_all_debt_shares,
-        query_all_total_debt_shares, query_all_vault_positions, query_config, query_positions,
-        query_total_debt_shares, query_vault_position_value, query_vault_utilization,
+        query_accounts, query_accounts_with_debt, query_all_coin_balances, query_all_debt_shares,
+        query_all_total_debt_shares, query_all_trigger_orders, query_all_vault_positions,
+        query_config, query_delegates, query_positions, query_total_debt_shares,
+        query_trigger_orders, query_vault_position_value, query_vault_utilization,
     },
     repay::repay_from_wallet,
+    roles::{grant_role, query_roles, revoke_role},
+    simulate::query_simulate_actions,
+    timelock::{
+        cancel_config_update, execute_config_update, propose_config_update, query_config_proposals,
+    },
+    trigger::{cancel_trigger_order, create_trigger_order, execute_trigger_order},
     update_config::{update_config, update_nft_config, update_owner},
     utils::get_account_kind,
     vault::handle_unlock_request_reply,
@@ -51,19 +70,81 @@ pub fn execute(
         ExecuteMsg::UpdateConfig {
             updates,
         } => update_config(deps, env, info, updates),
+        ExecuteMsg::ProposeConfigUpdate(updates) => propose_config_update(deps, env, info, updates),
+        ExecuteMsg::ExecuteConfigUpdate {
+            proposal_id,
+        } => execute_config_update(deps, env, info, proposal_id),
+        ExecuteMsg::CancelConfigUpdate {
+            proposal_id,
+        } => cancel_config_update(deps, info, proposal_id),
         ExecuteMsg::UpdateNftConfig {
             config,
             ownership,
         } => update_nft_config(deps, info, config, ownership),
         ExecuteMsg::UpdateOwner(update) => update_owner(deps, info, update),
+        ExecuteMsg::PauseAction(pause) => pause_action(deps, info, pause),
+        ExecuteMsg::UnpauseAction(pause) => unpause_action(deps, info, pause),
+        ExecuteMsg::GrantRole {
+            address,
+            role,
+        } => grant_role(deps, info, &address, role),
+        ExecuteMsg::RevokeRole {
+            address,
+            role,
+        } => revoke_role(deps, info, &address, role),
         ExecuteMsg::Callback(callback) => execute_callback(deps, info, env, callback),
         ExecuteMsg::UpdateCreditAccount {
             account_id,
             actions,
         } => dispatch_actions(deps, env, info, &account_id, actions),
+        ExecuteMsg::UpdateCreditAccounts(updates) => {
+            dispatch_batch_actions(deps, env, info, updates)
+        }
+        ExecuteMsg::ChangeAccountKind {
+            account_id,
+            kind,
+        } => change_account_kind(deps, env, info, &account_id, kind),
+        ExecuteMsg::MergeAccounts {
+            from_account_id,
+            into_account_id,
+            burn_from_account,
+        } => merge_accounts(deps, env, info, &from_account_id, &into_account_id, burn_from_account),
         ExecuteMsg::RepayFromWallet {
             account_id,
         } => repay_from_wallet(deps, env, info, account_id),
+        ExecuteMsg::LiquidateFromWallet {
+            liquidatee_account_id,
+            request,
+        } => liquidate_from_wallet(deps, env, info, liquidatee_account_id, request),
+        ExecuteMsg::UpdateLiquidatableSince {
+            account_id,
+        } => update_liquidatable_since(deps, env, &account_id),
+        ExecuteMsg::WriteOffBadDebt {
+            account_id,
+        } => write_off_bad_debt(deps, env, &account_id),
+        ExecuteMsg::CreateTriggerOrder {
+            account_id,
+            actions,
+            condition,
+        } => create_trigger_order(deps, info, &account_id, actions, condition),
+        ExecuteMsg::CancelTriggerOrder {
+            account_id,
+            trigger_order_id,
+        } => cancel_trigger_order(deps, info, &account_id, trigger_order_id),
+        ExecuteMsg::ExecuteTriggerOrder {
+            account_id,
+            trigger_order_id,
+        } => execute_trigger_order(deps, env, info, &account_id, trigger_order_id),
+        ExecuteMsg::GrantPermission {
+            account_id,
+            delegate,
+            permissions,
+            expires,
+        } => grant_permission(deps, info, &account_id, &delegate, permissions, expires),
+        ExecuteMsg::RevokePermission {
+            account_id,
+            delegate,
+        } => revoke_permission(deps, info, &account_id, &delegate),
     }
 }
 
@@ -86,6 +167,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
             start_after,
             limit,
         } => to_binary(&query_accounts(deps, owner, start_after, limit)?),
+        QueryMsg::AccountsWithDebt {
+            start_after,
+            limit,
+        } => to_binary(&query_accounts_with_debt(deps, start_after, limit)?),
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::VaultUtilization {
             vault,
@@ -120,6 +205,63 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
         QueryMsg::VaultPositionValue {
             vault_position,
         } => to_binary(&query_vault_position_value(deps, vault_position)?),
+        QueryMsg::SimulateActions {
+            sender,
+            account_id,
+            actions,
+        } => to_binary(&query_simulate_actions(deps, env, &sender, &account_id, actions)?),
+        QueryMsg::LiquidationPreview {
+            liquidatee_account_id,
+            debt_coin,
+            request,
+        } => to_binary(&query_liquidation_preview(
+            deps,
+            env,
+            &liquidatee_account_id,
+            debt_coin,
+            request,
+        )?),
+        QueryMsg::BasketLiquidationPreview {
+            liquidatee_account_id,
+            debt_coin,
+            requests,
+        } => to_binary(&query_basket_liquidation_preview(
+            deps,
+            env,
+            &liquidatee_account_id,
+            debt_coin,
+            requests,
+        )?),
+        QueryMsg::LiquidatableSince {
+            account_id,
+        } => to_binary(&query_liquidatable_since(deps, &account_id)?),
+        QueryMsg::Roles {
+            start_after,
+            limit,
+        } => to_binary(&query_roles(deps, start_after, limit)?),
+        QueryMsg::ConfigProposals {
+            start_after,
+            limit,
+        } => to_binary(&query_config_proposals(deps, start_after, limit)?),
+        QueryMsg::PausedActions {} => to_binary(&query_paused_actions(deps.storage)?),
+        QueryMsg::BadDebt {
+            start_after,
+            limit,
+        } => to_binary(&query_bad_debt(deps, start_after, limit)?),
+        QueryMsg::TriggerOrders {
+            account_id,
+            start_after,
+            limit,
+        } => to_binary(&query_trigger_orders(deps, &account_id, start_after, limit)?),
+        QueryMsg::AllTriggerOrders {
+            start_after,
+            limit,
+        } => to_binary(&query_all_trigger_orders(deps, start_after, limit)?),
+        QueryMsg::Delegates {
+            account_id,
+            start_after,
+            limit,
+        } => to_binary(&query_delegates(deps, &account_id, start_after, limit)?),
     };
     res.map_err(Into::into)
 }
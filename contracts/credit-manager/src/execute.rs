@@ -1,36 +1,47 @@
 use std::collections::BTreeSet;
 
 use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg,
+    to_binary, Addr, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg,
 };
 use mars_account_nft_types::msg::ExecuteMsg as NftExecuteMsg;
 use mars_red_bank_types::oracle::ActionKind;
 use mars_rover::{
     coins::Coins,
     error::{ContractError, ContractResult},
-    msg::execute::{Action, CallbackMsg, LiquidateRequest},
+    msg::execute::{AccountUpdate, Action, CallbackMsg, Recipient},
+    reentrancy_guard::GuardScope,
 };
-use mars_rover_health_types::AccountKind;
+use mars_rover_health_types::{AccountKind, HealthState};
 
 use crate::{
     borrow::borrow,
     claim_rewards::{claim_rewards, send_rewards},
+    close_position::{
+        close_position, repay_debts_from_collateral, swap_balances_into,
+        withdraw_liquidity_balances,
+    },
     deposit::{assert_deposit_caps, deposit},
+    flash_loan::{assert_flash_loans_repaid, flash_borrow, flash_repay},
     health::{assert_max_ltv, query_health_state},
     hls::assert_hls_rules,
     lend::lend,
-    liquidate::assert_not_self_liquidation,
-    liquidate_deposit::liquidate_deposit,
-    liquidate_lend::liquidate_lend,
+    leverage::leverage,
+    liquidate::{assert_not_self_liquidation, liquidate, update_liquidatable_since, Liquidator},
+    pause::assert_actions_not_paused,
+    permission::assert_is_owner_or_delegate,
     reclaim::reclaim,
     refund::refund_coin_balances,
-    repay::{repay, repay_for_recipient},
-    state::{ACCOUNT_KINDS, ACCOUNT_NFT, REENTRANCY_GUARD},
-    swap::swap_exact_in,
-    update_coin_balances::{update_coin_balance, update_coin_balance_after_vault_liquidation},
+    repay::{repay, repay_for_recipient, repay_from_collateral, repay_swap_output},
+    state::{ACCOUNT_KINDS, ACCOUNT_NFT, HEALTH_CONTRACT, REENTRANCY_GUARD},
+    swap::{assert_min_received, swap_exact_in, swap_exact_out},
+    transfer::transfer_to_account,
+    update_coin_balances::{
+        send_coin_after_vault_liquidation, update_coin_balance,
+        update_coin_balance_after_vault_liquidation,
+    },
     utils::{assert_is_token_owner, get_account_kind},
     vault::{
-        enter_vault, exit_vault, exit_vault_unlocked, liquidate_vault, request_vault_unlock,
+        enter_vault, exit_vault, exit_vault_unlocked, request_vault_unlock,
         update_vault_coin_balance,
     },
     withdraw::withdraw,
@@ -61,23 +72,65 @@ pub fn create_credit_account(
         .add_attribute("kind", kind.to_string()))
 }
 
-pub fn dispatch_actions(
-    mut deps: DepsMut,
+/// Dispatches read the account kind upfront to decide which rules to assert, so the kind can not
+/// change while the account is being acted upon.
+pub fn change_account_kind(
+    deps: DepsMut,
     env: Env,
     info: MessageInfo,
     account_id: &str,
-    actions: Vec<Action>,
+    kind: AccountKind,
 ) -> ContractResult<Response> {
     assert_is_token_owner(&deps, &info.sender, account_id)?;
-    REENTRANCY_GUARD.try_lock(deps.storage)?;
 
-    let mut response = Response::new();
-    let mut callbacks: Vec<CallbackMsg> = vec![];
-    let mut received_coins = Coins::try_from(info.funds)?;
+    let guard_scope = GuardScope::Account(account_id.to_string());
+    REENTRANCY_GUARD.try_lock(deps.storage, &guard_scope)?;
+
+    let prev_kind = get_account_kind(deps.storage, account_id)?;
+    if prev_kind == kind {
+        return Err(ContractError::AccountKindUnchanged {
+            account_id: account_id.to_string(),
+            kind: kind.to_string(),
+        });
+    }
+
+    if kind == AccountKind::HighLeveredStrategy {
+        assert_hls_rules(deps.as_ref(), account_id)?;
+    }
+
+    // Positions are unchanged, only the LTVs they are weighted by differ under the new kind
+    let hc = HEALTH_CONTRACT.load(deps.storage)?;
+    let health =
+        hc.query_health_state(&deps.querier, account_id, kind.clone(), ActionKind::Default)?;
+    if let HealthState::Unhealthy {
+        max_ltv_health_factor,
+    } = health
+    {
+        return Err(ContractError::AboveMaxLTV {
+            account_id: account_id.to_string(),
+            max_ltv_health_factor: max_ltv_health_factor.to_string(),
+        });
+    }
+
+    ACCOUNT_KINDS.save(deps.storage, account_id, &kind)?;
+
+    let remove_guard_msg = CallbackMsg::RemoveReentrancyGuard {
+        scope: guard_scope,
+    }
+    .into_cosmos_msg(&env.contract.address)?;
 
-    // deposit / repay actions don't require health check.
-    // It allows users to save some positions in cases of extreme volatility.
-    let no_health_check = actions.iter().all(|action| {
+    Ok(Response::new()
+        .add_message(remove_guard_msg)
+        .add_attribute("action", "change_account_kind")
+        .add_attribute("account_id", account_id)
+        .add_attribute("prev_kind", prev_kind.to_string())
+        .add_attribute("kind", kind.to_string()))
+}
+
+/// Deposit / repay actions don't require health check.
+/// It allows users to save some positions in cases of extreme volatility.
+pub fn no_health_check(actions: &[Action]) -> bool {
+    actions.iter().all(|action| {
         matches!(
             action,
             Action::Deposit(..)
@@ -85,8 +138,177 @@ pub fn dispatch_actions(
                     recipient_account_id: None,
                     ..
                 }
+                | Action::RepayFromCollateral { .. }
         )
-    });
+    })
+}
+
+pub fn dispatch_actions(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: &str,
+    actions: Vec<Action>,
+) -> ContractResult<Response> {
+    let owner =
+        assert_is_owner_or_delegate(deps.as_ref(), &env, &info.sender, account_id, &actions)?;
+    process_actions(deps, env, account_id, &owner, info.funds, actions, vec![])
+}
+
+/// Updates several accounts in one go. Each account's actions are processed, and its health and
+/// deposit caps asserted, before the next account's. The funds sent are allocated to the accounts
+/// in order of their `Deposit` actions.
+pub fn dispatch_batch_actions(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    updates: Vec<AccountUpdate>,
+) -> ContractResult<Response> {
+    let mut account_ids = BTreeSet::new();
+    for update in &updates {
+        if !account_ids.insert(update.account_id.clone()) {
+            return Err(ContractError::DuplicateAccountUpdate {
+                account_id: update.account_id.clone(),
+            });
+        }
+    }
+
+    let mut remaining_funds = Coins::try_from(info.funds)?;
+    let mut processed = vec![];
+    for update in updates {
+        let owner = assert_is_owner_or_delegate(
+            deps.as_ref(),
+            &env,
+            &info.sender,
+            &update.account_id,
+            &update.actions,
+        )?;
+        let funds = allocate_deposit_funds(&mut remaining_funds, &update.actions)?;
+        processed.push((update, owner, funds));
+    }
+    if !remaining_funds.is_empty() {
+        return Err(ContractError::ExtraFundsReceived(remaining_funds));
+    }
+
+    // A global lock covers every account, so it is taken once for the whole batch
+    let guard_scopes = if processed
+        .iter()
+        .any(|(update, ..)| update.actions.iter().any(Action::requires_global_guard))
+    {
+        vec![GuardScope::Global]
+    } else {
+        account_ids.into_iter().map(GuardScope::Account).collect()
+    };
+    for scope in &guard_scopes {
+        REENTRANCY_GUARD.try_lock(deps.storage, scope)?;
+    }
+
+    let mut response =
+        Response::new().add_attribute("action", "rover/execute/update_credit_accounts");
+    for (update, owner, funds) in processed {
+        let res = process_actions_unguarded(
+            deps.branch(),
+            env.clone(),
+            &update.account_id,
+            &owner,
+            funds,
+            update.actions,
+            vec![],
+        )?;
+        response = response.add_submessages(res.messages).add_attributes(res.attributes);
+    }
+
+    for scope in guard_scopes {
+        let callback = CallbackMsg::RemoveReentrancyGuard {
+            scope,
+        };
+        response = response.add_message(callback.into_cosmos_msg(&env.contract.address)?);
+    }
+
+    Ok(response)
+}
+
+/// Takes the coins deposited by the actions out of the funds sent along with a batch
+fn allocate_deposit_funds(
+    remaining_funds: &mut Coins,
+    actions: &[Action],
+) -> ContractResult<Vec<Coin>> {
+    let mut funds = Coins::default();
+    for action in actions {
+        if let Action::Deposit(coin) = action {
+            funds.add(coin)?;
+        }
+    }
+
+    for coin in funds.to_vec() {
+        let available = remaining_funds.amount(&coin.denom).unwrap_or_default();
+        if available < coin.amount {
+            return Err(ContractError::FundsMismatch {
+                expected: coin.amount,
+                received: available,
+            });
+        }
+        remaining_funds.deduct(&coin)?;
+    }
+
+    Ok(funds.into_vec())
+}
+
+/// Converts the actions into callbacks, followed by the account health and deposit cap assertions.
+/// Callers are responsible for asserting the account can be acted upon.
+/// `recipient` receives withdrawn coins and claimed rewards. `extra_callbacks` are executed after
+/// the actions, but before any of the assertions.
+pub fn process_actions(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    recipient: &Addr,
+    funds: Vec<Coin>,
+    actions: Vec<Action>,
+    extra_callbacks: Vec<CallbackMsg>,
+) -> ContractResult<Response> {
+    let guard_scope = if actions.iter().any(Action::requires_global_guard) {
+        GuardScope::Global
+    } else {
+        GuardScope::Account(account_id.to_string())
+    };
+    REENTRANCY_GUARD.try_lock(deps.storage, &guard_scope)?;
+
+    let rover_addr = env.contract.address.clone();
+    let response = process_actions_unguarded(
+        deps,
+        env,
+        account_id,
+        recipient,
+        funds,
+        actions,
+        extra_callbacks,
+    )?;
+
+    // Removes guard so that subsequent action dispatches can be made
+    let remove_guard = CallbackMsg::RemoveReentrancyGuard {
+        scope: guard_scope,
+    };
+    Ok(response.add_message(remove_guard.into_cosmos_msg(&rover_addr)?))
+}
+
+/// Same as `process_actions`, for callers already holding the reentrancy guard
+fn process_actions_unguarded(
+    mut deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    recipient: &Addr,
+    funds: Vec<Coin>,
+    actions: Vec<Action>,
+    extra_callbacks: Vec<CallbackMsg>,
+) -> ContractResult<Response> {
+    assert_actions_not_paused(deps.storage, &actions)?;
+
+    let mut response = Response::new();
+    let mut callbacks: Vec<CallbackMsg> = vec![];
+    let mut received_coins = Coins::try_from(funds)?;
+
+    let no_health_check = no_health_check(&actions) && extra_callbacks.is_empty();
 
     // If needed (i.e. if health check is required), we query the health state
     let prev_health_state = if !no_health_check {
@@ -106,18 +328,27 @@ pub fn dispatch_actions(
     // There are a few actions that may result in an asset's deposit amount
     // going up:
     // - Deposit: we check the deposited denom
-    // - SwapExactIn: we check the output denom
+    // - SwapExactIn/SwapExactOut: we check the output denom
     // - ClaimRewards: we don't check here; the reward amount is likely small so
     //   won't have much impact; this is also difficult to handle given that now
     //   we have multi-rewards
     // - ExitVault/ExitVaultUnlocked: we don't check here; it isn't reasonable
     //   to not allow a user to exit a vault because deposit cap will be exceeded
+    // - RepayFromCollateral: we check the debt denom; any swap output in excess
+    //   of the debt is deposited
+    // - ClosePosition: we check the target denom; swaps into debt denoms are
+    //   used to repay debt
     //
     // Note that Borrow/Lend/Reclaim does not impact total deposit amount,
     // because they simply move assets between Red Bank and Rover. We don't
     // check these actions.
     let mut denoms_for_cap_check = BTreeSet::new();
 
+    let mut has_flash_loan = false;
+
+    // Accounts receiving positions via `TransferToAccount`, with their health prior to the actions
+    let mut transfer_recipients: Vec<(String, HealthState)> = vec![];
+
     for action in actions {
         match action {
             Action::Deposit(coin) => {
@@ -128,7 +359,15 @@ pub fn dispatch_actions(
             Action::Withdraw(coin) => callbacks.push(CallbackMsg::Withdraw {
                 account_id: account_id.to_string(),
                 coin,
-                recipient: info.sender.clone(),
+                recipient: Recipient::Wallet(recipient.clone()),
+            }),
+            Action::WithdrawToRecipient {
+                coin,
+                recipient: withdraw_recipient,
+            } => callbacks.push(CallbackMsg::Withdraw {
+                account_id: account_id.to_string(),
+                coin,
+                recipient: withdraw_recipient.check(deps.api)?,
             }),
             Action::Borrow(coin) => callbacks.push(CallbackMsg::Borrow {
                 account_id: account_id.to_string(),
@@ -159,9 +398,14 @@ pub fn dispatch_actions(
                 account_id: account_id.to_string(),
                 coin,
             }),
-            Action::ClaimRewards {} => callbacks.push(CallbackMsg::ClaimRewards {
+            Action::ClaimRewards {
+                recipient: rewards_recipient,
+            } => callbacks.push(CallbackMsg::ClaimRewards {
                 account_id: account_id.to_string(),
-                recipient: info.sender.clone(),
+                recipient: match rewards_recipient {
+                    Some(unchecked) => unchecked.check(deps.api)?,
+                    None => Recipient::Wallet(recipient.clone()),
+                },
             }),
             Action::EnterVault {
                 vault,
@@ -175,32 +419,12 @@ pub fn dispatch_actions(
                 liquidatee_account_id,
                 debt_coin,
                 request,
-            } => match request {
-                LiquidateRequest::Deposit(denom) => callbacks.push(CallbackMsg::Liquidate {
-                    liquidator_account_id: account_id.to_string(),
-                    liquidatee_account_id: liquidatee_account_id.to_string(),
-                    debt_coin,
-                    request: LiquidateRequest::Deposit(denom),
-                }),
-                LiquidateRequest::Lend(denom) => callbacks.push(CallbackMsg::Liquidate {
-                    liquidator_account_id: account_id.to_string(),
-                    liquidatee_account_id: liquidatee_account_id.to_string(),
-                    debt_coin,
-                    request: LiquidateRequest::Lend(denom),
-                }),
-                LiquidateRequest::Vault {
-                    request_vault,
-                    position_type,
-                } => callbacks.push(CallbackMsg::Liquidate {
-                    liquidator_account_id: account_id.to_string(),
-                    liquidatee_account_id: liquidatee_account_id.to_string(),
-                    debt_coin,
-                    request: LiquidateRequest::Vault {
-                        request_vault: request_vault.check(deps.api)?,
-                        position_type,
-                    },
-                }),
-            },
+            } => callbacks.push(CallbackMsg::Liquidate {
+                liquidator_account_id: account_id.to_string(),
+                liquidatee_account_id: liquidatee_account_id.to_string(),
+                debt_coin,
+                request: request.check(deps.api)?,
+            }),
             Action::SwapExactIn {
                 coin_in,
                 denom_out,
@@ -215,6 +439,20 @@ pub fn dispatch_actions(
                 // check the deposit cap of the swap output denom
                 denoms_for_cap_check.insert(denom_out);
             }
+            Action::SwapExactOut {
+                denom_in,
+                coin_out,
+                max_amount_in,
+            } => {
+                // check the deposit cap of the swap output denom
+                denoms_for_cap_check.insert(coin_out.denom.clone());
+                callbacks.push(CallbackMsg::SwapExactOut {
+                    account_id: account_id.to_string(),
+                    denom_in,
+                    coin_out,
+                    max_amount_in,
+                });
+            }
             Action::ExitVault {
                 vault,
                 amount,
@@ -257,20 +495,107 @@ pub fn dispatch_actions(
                 lp_token,
                 slippage,
             }),
-            Action::RefundAllCoinBalances {} => {
-                callbacks.push(CallbackMsg::RefundAllCoinBalances {
+            Action::RefundAllCoinBalances {
+                recipient,
+            } => callbacks.push(CallbackMsg::RefundAllCoinBalances {
+                account_id: account_id.to_string(),
+                recipient: recipient.map(|unchecked| unchecked.check(deps.api)).transpose()?,
+            }),
+            Action::FlashBorrow(coin) => {
+                has_flash_loan = true;
+                callbacks.push(CallbackMsg::FlashBorrow {
                     account_id: account_id.to_string(),
+                    coin,
                 })
             }
+            Action::FlashRepay {} => callbacks.push(CallbackMsg::FlashRepay {
+                account_id: account_id.to_string(),
+            }),
+            Action::Leverage {
+                collateral_denom,
+                debt_denom,
+                target_ltv,
+                slippage,
+            } => {
+                // check the deposit cap of the swap output denom
+                denoms_for_cap_check.insert(collateral_denom.clone());
+                callbacks.push(CallbackMsg::Leverage {
+                    account_id: account_id.to_string(),
+                    collateral_denom,
+                    debt_denom,
+                    target_ltv,
+                    slippage,
+                });
+            }
+            Action::RepayFromCollateral {
+                collateral,
+                debt_denom,
+                slippage,
+            } => {
+                // check the deposit cap of the debt denom the surplus is deposited in
+                denoms_for_cap_check.insert(debt_denom.clone());
+                callbacks.push(CallbackMsg::RepayFromCollateral {
+                    account_id: account_id.to_string(),
+                    collateral,
+                    debt_denom,
+                    slippage,
+                });
+            }
+            Action::ClosePosition {
+                target_denom,
+                slippage,
+            } => {
+                // check the deposit cap of the denom everything is swapped into
+                denoms_for_cap_check.insert(target_denom.clone());
+                callbacks.push(CallbackMsg::ClosePosition {
+                    account_id: account_id.to_string(),
+                    target_denom,
+                    slippage,
+                });
+            }
+            Action::TransferToAccount {
+                recipient_account_id,
+                coins,
+                debt,
+                vault_positions,
+            } => {
+                if !transfer_recipients.iter().any(|(id, _)| id == &recipient_account_id) {
+                    let health_state = query_health_state(
+                        deps.as_ref(),
+                        &recipient_account_id,
+                        ActionKind::Default,
+                    )?;
+                    transfer_recipients.push((recipient_account_id.clone(), health_state));
+                }
+                callbacks.push(CallbackMsg::TransferToAccount {
+                    account_id: account_id.to_string(),
+                    recipient_account_id,
+                    coins,
+                    debt,
+                    vault_positions: vault_positions
+                        .iter()
+                        .map(|v| v.check(deps.api))
+                        .collect::<StdResult<_>>()?,
+                });
+            }
         }
     }
 
+    callbacks.extend(extra_callbacks);
+
     // after all deposits have been handled, we assert that the `received_natives` list is empty
     // this way, we ensure that the user does not send any extra fund which will get lost in the contract
     if !received_coins.is_empty() {
         return Err(ContractError::ExtraFundsReceived(received_coins));
     }
 
+    // Flash loans must be repaid (plus fee) within the bundle, before health is asserted
+    if has_flash_loan {
+        callbacks.push(CallbackMsg::AssertFlashLoansRepaid {
+            account_id: account_id.to_string(),
+        });
+    }
+
     // Ensures the account state abides by the rules of the HLS account kind
     let kind = get_account_kind(deps.storage, account_id)?;
     if kind == AccountKind::HighLeveredStrategy {
@@ -290,15 +615,26 @@ pub fn dispatch_actions(
         });
     }
 
-    callbacks.extend([
-        // After user selected actions, we assert that the relevant deposit caps
-        // are not exceeded.
-        CallbackMsg::AssertDepositCaps {
-            denoms: denoms_for_cap_check,
-        },
-        // Removes guard so that subsequent action dispatches can be made
-        CallbackMsg::RemoveReentrancyGuard {},
-    ]);
+    // Recipients of transferred positions are held to the same rules as the account itself
+    for (recipient_account_id, prev_health_state) in transfer_recipients {
+        if get_account_kind(deps.storage, &recipient_account_id)?
+            == AccountKind::HighLeveredStrategy
+        {
+            callbacks.push(CallbackMsg::AssertHlsRules {
+                account_id: recipient_account_id.clone(),
+            });
+        }
+        callbacks.push(CallbackMsg::AssertMaxLTV {
+            account_id: recipient_account_id,
+            prev_health_state,
+        });
+    }
+
+    // After user selected actions, we assert that the relevant deposit caps
+    // are not exceeded.
+    callbacks.push(CallbackMsg::AssertDepositCaps {
+        denoms: denoms_for_cap_check,
+    });
 
     let callback_msgs = callbacks
         .iter()
@@ -354,7 +690,7 @@ pub fn execute_callback(
         CallbackMsg::AssertMaxLTV {
             account_id,
             prev_health_state,
-        } => assert_max_ltv(deps.as_ref(), &account_id, prev_health_state),
+        } => assert_max_ltv(deps, &account_id, prev_health_state),
         CallbackMsg::AssertDepositCaps {
             denoms,
         } => assert_deposit_caps(deps.as_ref(), denoms),
@@ -381,36 +717,14 @@ pub fn execute_callback(
             request,
         } => {
             assert_not_self_liquidation(&liquidator_account_id, &liquidatee_account_id)?;
-            match request {
-                LiquidateRequest::Deposit(request_coin_denom) => liquidate_deposit(
-                    deps,
-                    env,
-                    &liquidator_account_id,
-                    &liquidatee_account_id,
-                    debt_coin,
-                    &request_coin_denom,
-                ),
-                LiquidateRequest::Lend(request_coin_denom) => liquidate_lend(
-                    deps,
-                    env,
-                    &liquidator_account_id,
-                    &liquidatee_account_id,
-                    debt_coin,
-                    &request_coin_denom,
-                ),
-                LiquidateRequest::Vault {
-                    request_vault,
-                    position_type,
-                } => liquidate_vault(
-                    deps,
-                    env,
-                    &liquidator_account_id,
-                    &liquidatee_account_id,
-                    debt_coin,
-                    request_vault,
-                    position_type,
-                ),
-            }
+            liquidate(
+                deps,
+                env,
+                &Liquidator::Account(liquidator_account_id),
+                &liquidatee_account_id,
+                debt_coin,
+                request,
+            )
         }
         CallbackMsg::SwapExactIn {
             account_id,
@@ -418,6 +732,16 @@ pub fn execute_callback(
             denom_out,
             slippage,
         } => swap_exact_in(deps, env, &account_id, &coin_in, &denom_out, slippage),
+        CallbackMsg::SwapExactOut {
+            account_id,
+            denom_in,
+            coin_out,
+            max_amount_in,
+        } => swap_exact_out(deps, env, &account_id, &denom_in, &coin_out, &max_amount_in),
+        CallbackMsg::AssertMinReceived {
+            previous_balance,
+            minimum,
+        } => assert_min_received(deps.as_ref(), env, previous_balance, minimum),
         CallbackMsg::UpdateCoinBalance {
             account_id,
             previous_balance,
@@ -434,6 +758,17 @@ pub fn execute_callback(
             &previous_balance,
             protocol_fee,
         ),
+        CallbackMsg::SendCoinAfterVaultLiquidation {
+            recipient,
+            previous_balance,
+            protocol_fee,
+        } => send_coin_after_vault_liquidation(
+            deps,
+            env,
+            &recipient,
+            &previous_balance,
+            protocol_fee,
+        ),
         CallbackMsg::ExitVault {
             account_id,
             vault,
@@ -462,11 +797,78 @@ pub fn execute_callback(
         } => withdraw_liquidity(deps, env, &account_id, &lp_token, slippage),
         CallbackMsg::RefundAllCoinBalances {
             account_id,
-        } => refund_coin_balances(deps, env, &account_id),
+            recipient,
+        } => refund_coin_balances(deps, env, &account_id, recipient),
         CallbackMsg::AssertHlsRules {
             account_id,
         } => assert_hls_rules(deps.as_ref(), &account_id),
-        CallbackMsg::RemoveReentrancyGuard {} => REENTRANCY_GUARD.try_unlock(deps.storage),
+        CallbackMsg::FlashBorrow {
+            account_id,
+            coin,
+        } => flash_borrow(deps, env, &account_id, coin),
+        CallbackMsg::FlashRepay {
+            account_id,
+        } => flash_repay(deps, &account_id),
+        CallbackMsg::AssertFlashLoansRepaid {
+            account_id,
+        } => assert_flash_loans_repaid(deps.as_ref(), &account_id),
+        CallbackMsg::UpdateLiquidatableSince {
+            account_id,
+        } => update_liquidatable_since(deps, env, &account_id),
+        CallbackMsg::Leverage {
+            account_id,
+            collateral_denom,
+            debt_denom,
+            target_ltv,
+            slippage,
+        } => leverage(deps, env, &account_id, &collateral_denom, &debt_denom, target_ltv, slippage),
+        CallbackMsg::RepayFromCollateral {
+            account_id,
+            collateral,
+            debt_denom,
+            slippage,
+        } => repay_from_collateral(deps, env, &account_id, collateral, &debt_denom, slippage),
+        CallbackMsg::RepaySwapOutput {
+            account_id,
+            denom,
+            previous_balance,
+            min_amount_out,
+        } => repay_swap_output(deps, &account_id, &denom, previous_balance, min_amount_out),
+        CallbackMsg::ClosePosition {
+            account_id,
+            target_denom,
+            slippage,
+        } => close_position(deps, env, &account_id, &target_denom, slippage),
+        CallbackMsg::WithdrawLiquidityBalances {
+            account_id,
+            slippage,
+        } => withdraw_liquidity_balances(deps, env, &account_id, slippage),
+        CallbackMsg::RepayDebtsFromCollateral {
+            account_id,
+            slippage,
+        } => repay_debts_from_collateral(deps, env, &account_id, slippage),
+        CallbackMsg::SwapBalancesInto {
+            account_id,
+            target_denom,
+            slippage,
+        } => swap_balances_into(deps, env, &account_id, &target_denom, slippage),
+        CallbackMsg::TransferToAccount {
+            account_id,
+            recipient_account_id,
+            coins,
+            debt,
+            vault_positions,
+        } => transfer_to_account(
+            deps,
+            &account_id,
+            &recipient_account_id,
+            &coins,
+            &debt,
+            &vault_positions,
+        ),
+        CallbackMsg::RemoveReentrancyGuard {
+            scope,
+        } => REENTRANCY_GUARD.try_unlock(deps.storage, &scope),
         CallbackMsg::SendRewardsToAddr {
             account_id,
             previous_balances,
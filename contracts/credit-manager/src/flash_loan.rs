@@ -0,0 +1,114 @@
+use cosmwasm_std::{Coin, Deps, DepsMut, Env, Order, Response, StdResult};
+use mars_rover::error::{ContractError, ContractResult};
+
+use crate::{
+    state::{FLASH_LOANS, FLASH_LOAN_FEE, REWARDS_COLLECTOR},
+    utils::{assert_coin_is_whitelisted, decrement_coin_balance, increment_coin_balance},
+};
+
+/// A same-tx advance with fee, funded by the coins held by the credit manager. No Red Bank debt
+/// is taken: the coin is credited to the account and recorded as a flash loan, which `FlashRepay`
+/// must return, along with the fee, before the bundle ends. The advance is capped by the credit
+/// manager's balance of the denom, so the coins exist to be sent out by the following actions.
+pub fn flash_borrow(
+    mut deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    coin: Coin,
+) -> ContractResult<Response> {
+    if coin.amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    assert_coin_is_whitelisted(&mut deps, &coin.denom)?;
+
+    let outstanding = FLASH_LOANS
+        .may_load(deps.storage, (account_id, &coin.denom))?
+        .unwrap_or_default()
+        .checked_add(coin.amount)?;
+
+    let available = deps.querier.query_balance(&env.contract.address, &coin.denom)?.amount;
+    if outstanding > available {
+        return Err(ContractError::FlashLoanExceedsLiquidity {
+            requested: Coin {
+                denom: coin.denom,
+                amount: outstanding,
+            }
+            .to_string(),
+            available: available.to_string(),
+        });
+    }
+
+    FLASH_LOANS.save(deps.storage, (account_id, &coin.denom), &outstanding)?;
+    increment_coin_balance(deps.storage, account_id, &coin)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "flash_borrow")
+        .add_attribute("account_id", account_id)
+        .add_attribute("coin_flash_borrowed", coin.to_string()))
+}
+
+/// Takes the flash borrowed coins back from the account's balance and transfers the fee,
+/// calculated on the borrowed amount, to the rewards-collector account
+pub fn flash_repay(deps: DepsMut, account_id: &str) -> ContractResult<Response> {
+    let loans = FLASH_LOANS
+        .prefix(account_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    if loans.is_empty() {
+        return Err(ContractError::NoFlashLoan);
+    }
+
+    let fee_rate = FLASH_LOAN_FEE.may_load(deps.storage)?.unwrap_or_default();
+
+    let mut response = Response::new()
+        .add_attribute("action", "flash_repay")
+        .add_attribute("account_id", account_id);
+
+    for (denom, amount) in loans {
+        FLASH_LOANS.remove(deps.storage, (account_id, &denom));
+
+        let loan = Coin {
+            denom: denom.clone(),
+            amount,
+        };
+        decrement_coin_balance(deps.storage, account_id, &loan)?;
+        response = response.add_attribute("coin_flash_repaid", loan.to_string());
+
+        let fee = Coin {
+            denom,
+            amount: amount.checked_mul_ceil(fee_rate)?,
+        };
+        if !fee.amount.is_zero() {
+            let rewards_collector_account = REWARDS_COLLECTOR.load(deps.storage)?.account_id;
+            decrement_coin_balance(deps.storage, account_id, &fee)?;
+            increment_coin_balance(deps.storage, &rewards_collector_account, &fee)?;
+            response = response.add_attribute("flash_loan_fee", fee.to_string());
+        }
+    }
+
+    Ok(response)
+}
+
+pub fn assert_flash_loans_repaid(deps: Deps, account_id: &str) -> ContractResult<Response> {
+    let outstanding = FLASH_LOANS
+        .prefix(account_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?;
+
+    if let Some((denom, amount)) = outstanding {
+        return Err(ContractError::FlashLoanNotRepaid(
+            Coin {
+                denom,
+                amount,
+            }
+            .to_string(),
+        ));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "callback/assert_flash_loans_repaid")
+        .add_attribute("account_id", account_id))
+}
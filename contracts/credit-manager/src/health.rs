@@ -1,9 +1,16 @@
-use cosmwasm_std::{Deps, Response};
+use cosmwasm_std::{Deps, DepsMut, Response, StdResult};
 use mars_red_bank_types::oracle::ActionKind;
-use mars_rover::error::{ContractError, ContractResult};
-use mars_rover_health_types::{HealthState, HealthValuesResponse};
+use mars_rover::{
+    error::{ContractError, ContractResult},
+    msg::query::Positions,
+};
+use mars_rover_health_computer::{DenomsData, HealthComputer, VaultsData};
+use mars_rover_health_types::{AccountKind, HealthState, HealthValuesResponse};
 
-use crate::{state::HEALTH_CONTRACT, utils::get_account_kind};
+use crate::{
+    state::{HEALTH_CONTRACT, LIQUIDATABLE_SINCE, ORACLE, PARAMS},
+    utils::get_account_kind,
+};
 
 pub fn query_health_state(
     deps: Deps,
@@ -26,13 +33,34 @@ pub fn query_health_values(
 }
 
 pub fn assert_max_ltv(
-    deps: Deps,
+    deps: DepsMut,
     account_id: &str,
     prev_health: HealthState,
 ) -> ContractResult<Response> {
-    let new_health = query_health_state(deps, account_id, ActionKind::Default)?;
+    let new_health = query_health_state(deps.as_ref(), account_id, ActionKind::Default)?;
+
+    assert_health_not_weakened(account_id, &prev_health, &new_health)?;
+
+    // The account has recovered, a later liquidation starts a new bonus auction
+    if new_health == HealthState::Healthy {
+        LIQUIDATABLE_SINCE.remove(deps.storage, account_id);
+    }
 
-    match (&prev_health, &new_health) {
+    Ok(Response::new()
+        .add_attribute("action", "callback/assert_health")
+        .add_attribute("account_id", account_id)
+        .add_attribute("prev_health_state", prev_health.to_string())
+        .add_attribute("new_health_state", new_health.to_string()))
+}
+
+/// Asserts the account is either healthy after the actions, or that its
+/// Max LTV health factor did not further weaken
+pub fn assert_health_not_weakened(
+    account_id: &str,
+    prev_health: &HealthState,
+    new_health: &HealthState,
+) -> ContractResult<()> {
+    match (prev_health, new_health) {
         // If account ends in a healthy state, all good! ✅
         (_, HealthState::Healthy) => {}
         // If previous health was in an unhealthy state, assert it did not further weaken ⚠️
@@ -68,9 +96,68 @@ pub fn assert_max_ltv(
         }
     }
 
-    Ok(Response::new()
-        .add_attribute("action", "callback/assert_health")
-        .add_attribute("account_id", account_id)
-        .add_attribute("prev_health_state", prev_health.to_string())
-        .add_attribute("new_health_state", new_health.to_string()))
+    Ok(())
+}
+
+/// Health values of positions that may not have been persisted, e.g. simulated ones
+pub fn compute_health_values(
+    deps: Deps,
+    kind: &AccountKind,
+    positions: &Positions,
+) -> ContractResult<HealthValuesResponse> {
+    Ok(health_computer(deps, kind, positions, &[])?.compute_health()?.into())
+}
+
+/// Mirrors the health contract's computation, but for positions that may not have been persisted.
+/// Prices and params of `extra_denoms` are loaded as well, even if not in the positions.
+pub fn health_computer(
+    deps: Deps,
+    kind: &AccountKind,
+    positions: &Positions,
+    extra_denoms: &[&str],
+) -> ContractResult<HealthComputer> {
+    let oracle = ORACLE.load(deps.storage)?;
+    let params = PARAMS.load(deps.storage)?;
+
+    let vault_base_token_denoms = positions
+        .vaults
+        .iter()
+        .map(|v| Ok(v.vault.query_info(&deps.querier)?.base_token))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut denoms_data: DenomsData = Default::default();
+    positions
+        .deposits
+        .iter()
+        .map(|d| d.denom.as_str())
+        .chain(positions.debts.iter().map(|d| d.denom.as_str()))
+        .chain(positions.lends.iter().map(|d| d.denom.as_str()))
+        .chain(vault_base_token_denoms.iter().map(|d| d.as_str()))
+        .chain(extra_denoms.iter().copied())
+        .try_for_each(|denom| -> StdResult<()> {
+            if denoms_data.prices.contains_key(denom) {
+                return Ok(());
+            }
+            let price = oracle.query_price(&deps.querier, denom, ActionKind::Default)?.price;
+            denoms_data.prices.insert(denom.to_string(), price);
+            let asset_params = params.query_asset_params(&deps.querier, denom)?;
+            denoms_data.params.insert(denom.to_string(), asset_params);
+            Ok(())
+        })?;
+
+    let mut vaults_data: VaultsData = Default::default();
+    positions.vaults.iter().try_for_each(|v| -> StdResult<()> {
+        let vault_coin_value = v.query_values(&deps.querier, &oracle, ActionKind::Default)?;
+        vaults_data.vault_values.insert(v.vault.address.clone(), vault_coin_value);
+        let config = params.query_vault_config(&deps.querier, &v.vault.address)?;
+        vaults_data.vault_configs.insert(v.vault.address.clone(), config);
+        Ok(())
+    })?;
+
+    Ok(HealthComputer {
+        kind: kind.clone(),
+        positions: positions.clone(),
+        denoms_data,
+        vaults_data,
+    })
 }
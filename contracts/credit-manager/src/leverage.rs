@@ -0,0 +1,94 @@
+use cosmwasm_std::{Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, Response, StdResult, Uint128};
+use mars_rover::{
+    error::{ContractError, ContractResult},
+    msg::{
+        execute::{ActionCoin, CallbackMsg},
+        query::Positions,
+    },
+};
+use mars_rover_health_types::AccountKind;
+
+use crate::{
+    health::health_computer,
+    query::query_positions,
+    utils::{assert_slippage, get_account_kind},
+};
+
+pub fn leverage(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    collateral_denom: &str,
+    debt_denom: &str,
+    target_ltv: Decimal,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    assert_slippage(deps.storage, slippage)?;
+
+    let kind = get_account_kind(deps.storage, account_id)?;
+    let positions = query_positions(deps.as_ref(), account_id)?;
+    let borrow_amount = leverage_amount(
+        deps.as_ref(),
+        &kind,
+        &positions,
+        collateral_denom,
+        debt_denom,
+        target_ltv,
+        slippage,
+    )?;
+
+    let coin_to_borrow = Coin {
+        denom: debt_denom.to_string(),
+        amount: borrow_amount,
+    };
+
+    // Borrowed coins are swapped right away, before the next action of the bundle
+    let callback_msgs = [
+        CallbackMsg::Borrow {
+            account_id: account_id.to_string(),
+            coin: coin_to_borrow.clone(),
+        },
+        CallbackMsg::SwapExactIn {
+            account_id: account_id.to_string(),
+            coin_in: ActionCoin::from(&coin_to_borrow),
+            denom_out: collateral_denom.to_string(),
+            slippage,
+        },
+    ]
+    .iter()
+    .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+    .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new()
+        .add_messages(callback_msgs)
+        .add_attribute("action", "leverage")
+        .add_attribute("account_id", account_id)
+        .add_attribute("coin_borrowed", coin_to_borrow.to_string())
+        .add_attribute("denom_out", collateral_denom)
+        .add_attribute("target_ltv", target_ltv.to_string()))
+}
+
+/// The amount of `debt_denom` to borrow for the positions to reach `target_ltv`,
+/// using the max LTVs of the account kind
+pub fn leverage_amount(
+    deps: Deps,
+    kind: &AccountKind,
+    positions: &Positions,
+    collateral_denom: &str,
+    debt_denom: &str,
+    target_ltv: Decimal,
+    slippage: Decimal,
+) -> ContractResult<Uint128> {
+    if target_ltv.is_zero() || target_ltv >= Decimal::one() {
+        return Err(ContractError::InvalidTargetLtv(target_ltv));
+    }
+
+    let amount = health_computer(deps, kind, positions, &[collateral_denom, debt_denom])?
+        .leverage_amount_estimate(collateral_denom, debt_denom, target_ltv, slippage)?;
+
+    if amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    Ok(amount)
+}
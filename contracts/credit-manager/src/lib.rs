@@ -1,23 +1,36 @@
 pub mod contract;
 
+pub mod bad_debt;
 pub mod borrow;
 pub mod claim_rewards;
+pub mod close_position;
 pub mod deposit;
 pub mod execute;
+pub mod flash_loan;
 pub mod health;
 pub mod hls;
 pub mod instantiate;
 pub mod lend;
+pub mod leverage;
 pub mod liquidate;
+pub mod liquidate_basket;
 pub mod liquidate_deposit;
 pub mod liquidate_lend;
+pub mod merge;
 pub mod migrations;
+pub mod pause;
+pub mod permission;
 pub mod query;
 pub mod reclaim;
 pub mod refund;
 pub mod repay;
+pub mod roles;
+pub mod simulate;
 pub mod state;
 pub mod swap;
+pub mod timelock;
+pub mod transfer;
+pub mod trigger;
 pub mod update_coin_balances;
 pub mod update_config;
 pub mod utils;
@@ -1,18 +1,293 @@
-use cosmwasm_std::{Coin, DepsMut, QuerierWrapper, Uint128};
+use cosmwasm_std::{
+    attr, Addr, Attribute, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, Storage, Uint128,
+};
+use cw_utils::one_coin;
 use mars_liquidation::liquidation::calculate_liquidation_amounts;
+use mars_params::types::asset::LiquidationBonus;
 use mars_red_bank_types::oracle::ActionKind;
 use mars_rover::{
-    adapters::oracle::Oracle,
+    adapters::vault::{Vault, VaultUnchecked},
     error::{ContractError, ContractResult},
+    msg::{
+        execute::{CallbackMsg, LiquidateRequest},
+        query::LiquidationPreviewResponse,
+    },
+    reentrancy_guard::GuardScope,
     traits::Stringify,
 };
+use mars_rover_health_types::HealthValuesResponse;
 
 use crate::{
     health::query_health_values,
+    liquidate_basket::liquidate_basket,
+    liquidate_deposit::{deposit_balance, liquidate_deposit},
+    liquidate_lend::{lent_amount, liquidate_lend},
     repay::current_debt_for_denom,
-    state::{ORACLE, PARAMS},
+    state::{LIQUIDATABLE_SINCE, LIQUIDATION_AUCTION, ORACLE, PARAMS, REENTRANCY_GUARD},
+    utils::increment_coin_balance,
+    vault::{liquidate_vault, preview_vault_liquidation},
 };
 
+/// The party repaying the liquidatee's debt and receiving the liquidated coins
+pub enum Liquidator {
+    /// Repays from and receives into the coin balances of a credit account
+    Account(String),
+    /// Repays with the coin sent along with the message and receives into the wallet
+    Wallet {
+        addr: Addr,
+        coin_sent: Coin,
+    },
+}
+
+impl Liquidator {
+    pub fn attribute(&self) -> Attribute {
+        match self {
+            Liquidator::Account(account_id) => attr("account_id", account_id),
+            Liquidator::Wallet {
+                addr,
+                ..
+            } => attr("liquidator", addr),
+        }
+    }
+
+    /// Transfers the liquidated coin to the liquidator
+    pub fn receive(
+        &self,
+        storage: &mut dyn Storage,
+        coin: &Coin,
+    ) -> ContractResult<Vec<CosmosMsg>> {
+        match self {
+            Liquidator::Account(account_id) => {
+                increment_coin_balance(storage, account_id, coin)?;
+                Ok(vec![])
+            }
+            Liquidator::Wallet {
+                addr,
+                ..
+            } => Ok(vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: addr.to_string(),
+                amount: vec![coin.clone()],
+            })]),
+        }
+    }
+}
+
+/// Liquidate a credit account without owning one. The debt is repaid with the coin sent in the
+/// message funds, the liquidated coins are sent straight to the sender.
+/// Locks globally like `Action::Liquidate`, as vault liquidations call external contracts.
+pub fn liquidate_from_wallet(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    liquidatee_account_id: String,
+    request: LiquidateRequest<VaultUnchecked>,
+) -> ContractResult<Response> {
+    let coin_sent = one_coin(&info)?;
+    let request = request.check(deps.api)?;
+    let liquidator = Liquidator::Wallet {
+        addr: info.sender,
+        coin_sent: coin_sent.clone(),
+    };
+
+    REENTRANCY_GUARD.try_lock(deps.storage, &GuardScope::Global)?;
+
+    let remove_guard = CallbackMsg::RemoveReentrancyGuard {
+        scope: GuardScope::Global,
+    }
+    .into_cosmos_msg(&env.contract.address)?;
+
+    let response = liquidate(deps, env, &liquidator, &liquidatee_account_id, coin_sent, request)?;
+    Ok(response.add_message(remove_guard))
+}
+
+pub fn liquidate(
+    deps: DepsMut,
+    env: Env,
+    liquidator: &Liquidator,
+    liquidatee_account_id: &str,
+    debt_coin: Coin,
+    request: LiquidateRequest<Vault>,
+) -> ContractResult<Response> {
+    // Once the liquidation has settled, record the liquidatee as liquidatable, or clear the
+    // record if the liquidation restored its health
+    let update_liquidatable_since = CallbackMsg::UpdateLiquidatableSince {
+        account_id: liquidatee_account_id.to_string(),
+    }
+    .into_cosmos_msg(&env.contract.address)?;
+
+    let response = match request {
+        LiquidateRequest::Deposit(request_coin_denom) => liquidate_deposit(
+            deps,
+            env,
+            liquidator,
+            liquidatee_account_id,
+            debt_coin,
+            &request_coin_denom,
+        ),
+        LiquidateRequest::Lend(request_coin_denom) => liquidate_lend(
+            deps,
+            env,
+            liquidator,
+            liquidatee_account_id,
+            debt_coin,
+            &request_coin_denom,
+        ),
+        LiquidateRequest::Vault {
+            request_vault,
+            position_type,
+        } => liquidate_vault(
+            deps,
+            env,
+            liquidator,
+            liquidatee_account_id,
+            debt_coin,
+            request_vault,
+            position_type,
+        ),
+        LiquidateRequest::Basket(requests) => {
+            liquidate_basket(deps, env, liquidator, liquidatee_account_id, debt_coin, requests)
+        }
+    }?;
+
+    Ok(response.add_message(update_liquidatable_since))
+}
+
+/// Outcome of liquidating a single position. The liquidated coins have been transferred (or
+/// are transferred by `msgs`), the debt is yet to be repaid.
+pub struct LiquidatedBucket {
+    pub debt: Coin,
+    pub liquidatee_request: Coin,
+    pub protocol_fee: Coin,
+    pub msgs: Vec<CosmosMsg>,
+}
+
+impl LiquidatedBucket {
+    pub fn into_response(
+        self,
+        action: &str,
+        liquidator: &Liquidator,
+        liquidatee_account_id: &str,
+        repay_msgs: Vec<CosmosMsg>,
+    ) -> Response {
+        // The liquidated coins are credited by balance diffs, so they must settle before the
+        // repayment and any refund move coins of the same denom out of the contract
+        Response::new()
+            .add_messages(self.msgs)
+            .add_messages(repay_msgs)
+            .add_attribute("action", action)
+            .add_attributes([liquidator.attribute()])
+            .add_attribute("liquidatee_account_id", liquidatee_account_id)
+            .add_attribute("coin_debt_repaid", self.debt.to_string())
+            .add_attribute("coin_liquidated", self.liquidatee_request.to_string())
+            .add_attribute("protocol_fee_coin", self.protocol_fee.to_string())
+    }
+}
+
+/// Liquidatee values read once per liquidation
+pub struct LiquidationState {
+    pub liquidatee_account_id: String,
+    pub health: HealthValuesResponse,
+    /// Liquidatee's total debt for the debt denom
+    pub total_debt_amount: Uint128,
+    /// Blocks elapsed since the liquidatee was first recorded as liquidatable
+    pub liquidatable_blocks: u64,
+}
+
+/// Asserts the liquidatee's credit account is liquidatable and reads its total debt for denom
+pub fn liquidation_state(
+    deps: Deps,
+    env: &Env,
+    liquidatee_account_id: &str,
+    debt_denom: &str,
+) -> ContractResult<LiquidationState> {
+    let health = query_health_values(deps, liquidatee_account_id, ActionKind::Liquidation)?;
+    if !health.liquidatable {
+        return Err(ContractError::NotLiquidatable {
+            account_id: liquidatee_account_id.to_string(),
+            lqdt_health_factor: health.liquidation_health_factor.to_string(),
+        });
+    }
+
+    let (total_debt_amount, _) = current_debt_for_denom(deps, liquidatee_account_id, debt_denom)?;
+
+    let liquidatable_since = LIQUIDATABLE_SINCE
+        .may_load(deps.storage, liquidatee_account_id)?
+        .unwrap_or(env.block.height);
+
+    Ok(LiquidationState {
+        liquidatee_account_id: liquidatee_account_id.to_string(),
+        health,
+        total_debt_amount,
+        liquidatable_blocks: env.block.height.saturating_sub(liquidatable_since),
+    })
+}
+
+/// Saves the block the account became liquidatable at, unless already recorded.
+/// An account that is not liquidatable has its record cleared, so the next time it becomes
+/// liquidatable starts a new bonus auction.
+/// Returns the recorded block height, if any.
+pub fn record_liquidatable(
+    storage: &mut dyn Storage,
+    account_id: &str,
+    health: &HealthValuesResponse,
+    block_height: u64,
+) -> ContractResult<Option<u64>> {
+    if !health.liquidatable {
+        LIQUIDATABLE_SINCE.remove(storage, account_id);
+        return Ok(None);
+    }
+
+    let since = LIQUIDATABLE_SINCE.update(storage, account_id, |since| -> ContractResult<_> {
+        Ok(since.unwrap_or(block_height))
+    })?;
+    Ok(Some(since))
+}
+
+/// Records the account as liquidatable, or clears the record if the account has recovered
+pub fn update_liquidatable_since(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+) -> ContractResult<Response> {
+    let health = query_health_values(deps.as_ref(), account_id, ActionKind::Liquidation)?;
+
+    let liquidatable_since =
+        record_liquidatable(deps.storage, account_id, &health, env.block.height)?
+            .map(|since| since.to_string())
+            .unwrap_or_else(|| "none".to_string());
+
+    Ok(Response::new()
+        .add_attribute("action", "update_liquidatable_since")
+        .add_attribute("account_id", account_id)
+        .add_attribute("liquidatable_since", liquidatable_since))
+}
+
+pub fn query_liquidatable_since(deps: Deps, account_id: &str) -> ContractResult<Option<u64>> {
+    Ok(LIQUIDATABLE_SINCE.may_load(deps.storage, account_id)?)
+}
+
+/// In auction mode, the liquidation bonus is capped at `min_lb` when the account is first recorded
+/// as liquidatable. The cap rises linearly with elapsed blocks up to `max_lb`.
+/// The bonus still rises with how far health has fallen (`slope`), within the cap.
+pub fn auction_max_lb(
+    storage: &dyn Storage,
+    liquidatable_blocks: u64,
+    bonus: &LiquidationBonus,
+) -> ContractResult<Decimal> {
+    let Some(auction) = LIQUIDATION_AUCTION.may_load(storage)? else {
+        return Ok(bonus.max_lb);
+    };
+
+    if liquidatable_blocks >= auction.blocks_to_max_bonus || bonus.max_lb <= bonus.min_lb {
+        return Ok(bonus.max_lb);
+    }
+
+    let progress = Decimal::from_ratio(liquidatable_blocks, auction.blocks_to_max_bonus);
+    let max_lb = bonus.min_lb.checked_add((bonus.max_lb - bonus.min_lb).checked_mul(progress)?)?;
+    Ok(max_lb)
+}
+
 /// Calculates precise debt, request coin amounts to liquidate, request coin transfered to liquidator and rewards-collector.
 /// The debt amount will be adjusted down if:
 /// - Exceeds liquidatee's total debt for denom
@@ -21,29 +296,49 @@ use crate::{
 /// Returns -> (Debt Coin, Liquidator Request Coin, Liquidatee Request Coin)
 /// Difference between Liquidator Request Coin and Liquidatee Request Coin goes to rewards-collector account as protocol fee.
 pub fn calculate_liquidation(
-    deps: &DepsMut,
+    deps: Deps,
+    env: &Env,
     liquidatee_account_id: &str,
     debt_coin: &Coin,
     request_coin: &str,
     request_coin_balance: Uint128,
 ) -> ContractResult<(Coin, Coin, Coin)> {
-    // Assert the liquidatee's credit account is liquidatable
-    let health =
-        query_health_values(deps.as_ref(), liquidatee_account_id, ActionKind::Liquidation)?;
-    if !health.liquidatable {
-        return Err(ContractError::NotLiquidatable {
-            account_id: liquidatee_account_id.to_string(),
-            lqdt_health_factor: health.liquidation_health_factor.to_string(),
-        });
-    }
+    let state = liquidation_state(deps, env, liquidatee_account_id, &debt_coin.denom)?;
+    calculate_liquidation_for_state(deps, &state, debt_coin, request_coin, request_coin_balance)
+}
+
+/// Same as `calculate_liquidation`, using liquidatee values that have already been read
+pub fn calculate_liquidation_for_state(
+    deps: Deps,
+    state: &LiquidationState,
+    debt_coin: &Coin,
+    request_coin: &str,
+    request_coin_balance: Uint128,
+) -> ContractResult<(Coin, Coin, Coin)> {
+    let result =
+        liquidation_amounts_for_state(deps, state, debt_coin, request_coin, request_coin_balance)?;
+
+    assert_liquidation_profitable(deps, result.clone())?;
 
-    // Ensure debt repaid does not exceed liquidatee's total debt for denom
-    let (total_debt_amount, _) =
-        current_debt_for_denom(deps.as_ref(), liquidatee_account_id, &debt_coin.denom)?;
+    Ok(result)
+}
 
+/// Same as `calculate_liquidation_for_state`, without asserting the liquidation is profitable
+pub fn liquidation_amounts_for_state(
+    deps: Deps,
+    state: &LiquidationState,
+    debt_coin: &Coin,
+    request_coin: &str,
+    request_coin_balance: Uint128,
+) -> ContractResult<(Coin, Coin, Coin)> {
     let params = PARAMS.load(deps.storage)?;
     let target_health_factor = params.query_target_health_factor(&deps.querier)?;
-    let request_coin_params = params.query_asset_params(&deps.querier, request_coin)?;
+    let mut request_coin_params = params.query_asset_params(&deps.querier, request_coin)?;
+    request_coin_params.liquidation_bonus.max_lb = auction_max_lb(
+        deps.storage,
+        state.liquidatable_blocks,
+        &request_coin_params.liquidation_bonus,
+    )?;
 
     let oracle = ORACLE.load(deps.storage)?;
     let debt_coin_price =
@@ -56,15 +351,16 @@ pub fn calculate_liquidation(
             request_coin_balance,
             request_coin_price,
             &request_coin_params,
-            total_debt_amount,
+            // Ensure debt repaid does not exceed liquidatee's total debt for denom
+            state.total_debt_amount,
             debt_coin.amount,
             debt_coin_price,
             target_health_factor,
-            &health.into(),
+            &state.health.clone().into(),
         )?;
 
     // (Debt Coin, Liquidator Request Coin, Liquidatee Request Coin)
-    let result = (
+    Ok((
         Coin {
             denom: debt_coin.denom.clone(),
             amount: debt_amount_to_repay,
@@ -77,24 +373,16 @@ pub fn calculate_liquidation(
             denom: request_coin.to_string(),
             amount: request_amount_to_liquidate,
         },
-    );
-
-    assert_liquidation_profitable(&deps.querier, &oracle, result.clone())?;
-
-    Ok(result)
+    ))
 }
 
 /// In scenarios with small amounts or large gap between coin prices, there is a possibility
 /// that the liquidation will result in loss for the liquidator. This assertion prevents this.
 fn assert_liquidation_profitable(
-    querier: &QuerierWrapper,
-    oracle: &Oracle,
+    deps: Deps,
     (debt_coin, request_coin, ..): (Coin, Coin, Coin),
 ) -> ContractResult<()> {
-    let debt_value = oracle.query_value(querier, &debt_coin, ActionKind::Liquidation)?;
-    let request_value = oracle.query_value(querier, &request_coin, ActionKind::Liquidation)?;
-
-    if debt_value >= request_value {
+    if !is_liquidation_profitable(deps, &debt_coin, &request_coin)? {
         return Err(ContractError::LiquidationNotProfitable {
             debt_coin,
             request_coin,
@@ -104,6 +392,85 @@ fn assert_liquidation_profitable(
     Ok(())
 }
 
+pub fn is_liquidation_profitable(
+    deps: Deps,
+    debt_coin: &Coin,
+    request_coin: &Coin,
+) -> ContractResult<bool> {
+    let oracle = ORACLE.load(deps.storage)?;
+    let debt_value = oracle.query_value(&deps.querier, debt_coin, ActionKind::Liquidation)?;
+    let request_value = oracle.query_value(&deps.querier, request_coin, ActionKind::Liquidation)?;
+    Ok(debt_value < request_value)
+}
+
+pub fn query_liquidation_preview(
+    deps: Deps,
+    env: Env,
+    liquidatee_account_id: &str,
+    debt_coin: Coin,
+    request: LiquidateRequest<VaultUnchecked>,
+) -> ContractResult<LiquidationPreviewResponse> {
+    let request = request.check(deps.api)?;
+    let state = liquidation_state(deps, &env, liquidatee_account_id, &debt_coin.denom)?;
+    preview_request(deps, &state, &debt_coin, request)
+}
+
+/// Previews liquidating a single deposit, lend or vault position
+pub fn preview_request(
+    deps: Deps,
+    state: &LiquidationState,
+    debt_coin: &Coin,
+    request: LiquidateRequest<Vault>,
+) -> ContractResult<LiquidationPreviewResponse> {
+    let liquidatee_account_id = state.liquidatee_account_id.as_str();
+    let (debt, liquidator_request, liquidatee_request, profitable) = match request {
+        LiquidateRequest::Deposit(denom) => {
+            let request_coin_balance =
+                deposit_balance(deps.storage, liquidatee_account_id, &denom)?;
+            preview_liquidation(deps, state, debt_coin, &denom, request_coin_balance)?
+        }
+        LiquidateRequest::Lend(denom) => {
+            let total_lent_amount = lent_amount(deps, liquidatee_account_id, &denom)?;
+            preview_liquidation(deps, state, debt_coin, &denom, total_lent_amount)?
+        }
+        LiquidateRequest::Vault {
+            request_vault,
+            position_type,
+        } => preview_vault_liquidation(deps, state, debt_coin, &request_vault, position_type)?,
+        // Previewed per position with the `BasketLiquidationPreview` query
+        LiquidateRequest::Basket(_) => {
+            return Err(ContractError::SimulationNotSupported("Basket liquidation".to_string()))
+        }
+    };
+
+    let protocol_fee = Coin {
+        denom: liquidatee_request.denom.clone(),
+        amount: liquidatee_request.amount.checked_sub(liquidator_request.amount)?,
+    };
+
+    Ok(LiquidationPreviewResponse {
+        debt_repaid: debt,
+        liquidator_request,
+        liquidatee_request,
+        protocol_fee,
+        profitable,
+    })
+}
+
+/// Returns -> (Debt Coin, Liquidator Request Coin, Liquidatee Request Coin, Profitable)
+pub fn preview_liquidation(
+    deps: Deps,
+    state: &LiquidationState,
+    debt_coin: &Coin,
+    request_coin: &str,
+    request_coin_balance: Uint128,
+) -> ContractResult<(Coin, Coin, Coin, bool)> {
+    let (debt, liquidator_request, liquidatee_request) =
+        liquidation_amounts_for_state(deps, state, debt_coin, request_coin, request_coin_balance)?;
+    let profitable = is_liquidation_profitable(deps, &debt, &liquidator_request)?;
+    Ok((debt, liquidator_request, liquidatee_request, profitable))
+}
+
 /// Guards against the case an account is trying to liquidate itself
 pub fn assert_not_self_liquidation(
     liquidator_account_id: &str,
@@ -0,0 +1,330 @@
+use std::collections::HashSet;
+
+use cosmwasm_std::{Coin, Decimal, Deps, DepsMut, Env, Response, StdResult, Uint128};
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    adapters::vault::{Vault, VaultUnchecked},
+    error::{ContractError, ContractResult},
+    msg::{execute::LiquidateRequest, query::BasketLiquidationPreviewResponse},
+};
+use mars_rover_health_types::{is_below_one, AccountKind};
+
+use crate::{
+    liquidate::{liquidation_state, preview_request, LiquidationState, Liquidator},
+    liquidate_deposit::{deposit_balance, liquidate_deposit_bucket, repay_debt},
+    liquidate_lend::{lent_amount, liquidate_lend_bucket},
+    state::{ORACLE, PARAMS},
+    utils::get_account_kind,
+    vault::{liquidate_vault_bucket, load_vault_bucket},
+};
+
+/// Allocates the debt coin across the requested positions in the order given. Each position is
+/// liquidated as a single liquidation would be, by `calculate_liquidation_amounts`, from the
+/// liquidatee's values less what the previous positions took. The basket stops once the account
+/// is no longer liquidatable. The debt repaid for all positions is repaid in a single message.
+pub fn liquidate_basket(
+    mut deps: DepsMut,
+    env: Env,
+    liquidator: &Liquidator,
+    liquidatee_account_id: &str,
+    debt_coin: Coin,
+    requests: Vec<LiquidateRequest<Vault>>,
+) -> ContractResult<Response> {
+    assert_valid_basket(deps.as_ref(), &requests)?;
+
+    let mut state =
+        liquidation_state(deps.as_ref(), &env, liquidatee_account_id, &debt_coin.denom)?;
+    let kind = get_account_kind(deps.storage, liquidatee_account_id)?;
+
+    let mut remaining = debt_coin.amount;
+    let mut debt_repaid = Uint128::zero();
+    let mut msgs = vec![];
+    let mut attrs = vec![];
+
+    for request in requests {
+        if remaining.is_zero() || !state.health.liquidatable {
+            break;
+        }
+
+        let bucket_debt_coin = Coin {
+            denom: debt_coin.denom.clone(),
+            amount: remaining,
+        };
+
+        let liquidated = match request.clone() {
+            LiquidateRequest::Deposit(denom) => {
+                let balance = deposit_balance(deps.storage, liquidatee_account_id, &denom)?;
+                liquidate_deposit_bucket(
+                    deps.branch(),
+                    liquidator,
+                    &state,
+                    &bucket_debt_coin,
+                    &denom,
+                    balance,
+                )?
+            }
+            LiquidateRequest::Lend(denom) => {
+                let total_lent_amount = lent_amount(deps.as_ref(), liquidatee_account_id, &denom)?;
+                liquidate_lend_bucket(
+                    deps.branch(),
+                    liquidator,
+                    &state,
+                    &bucket_debt_coin,
+                    &denom,
+                    total_lent_amount,
+                )?
+            }
+            LiquidateRequest::Vault {
+                request_vault,
+                position_type,
+            } => {
+                let bucket = load_vault_bucket(
+                    deps.storage,
+                    liquidatee_account_id,
+                    &request_vault,
+                    position_type,
+                )?;
+                liquidate_vault_bucket(
+                    deps.branch(),
+                    &env,
+                    liquidator,
+                    &state,
+                    &bucket_debt_coin,
+                    &request_vault,
+                    bucket,
+                )?
+            }
+            LiquidateRequest::Basket(_) => return Err(nested_basket_error()),
+        };
+
+        take_liquidated(
+            deps.as_ref(),
+            &mut state,
+            &kind,
+            &request,
+            &liquidated.debt,
+            &liquidated.liquidatee_request,
+        )?;
+
+        remaining = remaining.checked_sub(liquidated.debt.amount)?;
+        debt_repaid = debt_repaid.checked_add(liquidated.debt.amount)?;
+        msgs.extend(liquidated.msgs);
+        attrs.push(("coin_liquidated", liquidated.liquidatee_request.to_string()));
+        attrs.push(("protocol_fee_coin", liquidated.protocol_fee.to_string()));
+    }
+
+    let debt = Coin {
+        denom: debt_coin.denom,
+        amount: debt_repaid,
+    };
+    let repay_msgs = repay_debt(deps.storage, &env, liquidator, liquidatee_account_id, &debt)?;
+
+    // Liquidated coins settle first, see `LiquidatedBucket::into_response`
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_messages(repay_msgs)
+        .add_attribute("action", "liquidate_basket")
+        .add_attributes([liquidator.attribute()])
+        .add_attribute("liquidatee_account_id", liquidatee_account_id)
+        .add_attribute("coin_debt_repaid", debt.to_string())
+        .add_attributes(attrs))
+}
+
+/// Previews each position of the basket, allocating the debt coin as `liquidate_basket` does
+pub fn query_basket_liquidation_preview(
+    deps: Deps,
+    env: Env,
+    liquidatee_account_id: &str,
+    debt_coin: Coin,
+    requests: Vec<LiquidateRequest<VaultUnchecked>>,
+) -> ContractResult<BasketLiquidationPreviewResponse> {
+    let requests = requests
+        .into_iter()
+        .map(|request| request.check(deps.api))
+        .collect::<StdResult<Vec<_>>>()?;
+    assert_valid_basket(deps, &requests)?;
+
+    let mut state = liquidation_state(deps, &env, liquidatee_account_id, &debt_coin.denom)?;
+    let kind = get_account_kind(deps.storage, liquidatee_account_id)?;
+
+    let mut remaining = debt_coin.amount;
+    let mut debt_repaid = Uint128::zero();
+    let mut positions = vec![];
+
+    for request in requests {
+        if remaining.is_zero() || !state.health.liquidatable {
+            break;
+        }
+
+        let bucket_debt_coin = Coin {
+            denom: debt_coin.denom.clone(),
+            amount: remaining,
+        };
+        let preview = preview_request(deps, &state, &bucket_debt_coin, request.clone())?;
+
+        take_liquidated(
+            deps,
+            &mut state,
+            &kind,
+            &request,
+            &preview.debt_repaid,
+            &preview.liquidatee_request,
+        )?;
+
+        remaining = remaining.checked_sub(preview.debt_repaid.amount)?;
+        debt_repaid = debt_repaid.checked_add(preview.debt_repaid.amount)?;
+        positions.push(preview);
+    }
+
+    Ok(BasketLiquidationPreviewResponse {
+        debt_repaid: Coin {
+            denom: debt_coin.denom,
+            amount: debt_repaid,
+        },
+        profitable: positions.iter().all(|preview| preview.profitable),
+        positions,
+    })
+}
+
+/// Coins withdrawn from Red Bank or a vault are credited by comparing the contract's balance
+/// before and after. For this to hold, each lend and vault request in the basket has to release
+/// a different denom, and no deposit request may take that denom either, as deposits liquidated
+/// to a wallet leave the contract's balance before the diff is taken.
+fn assert_valid_basket(deps: Deps, requests: &[LiquidateRequest<Vault>]) -> ContractResult<()> {
+    let mut deposit_denoms = HashSet::new();
+    let mut released_denoms = HashSet::new();
+
+    for request in requests {
+        let denom = match request {
+            LiquidateRequest::Deposit(denom) => {
+                if !deposit_denoms.insert(denom.clone()) {
+                    return Err(ContractError::InvalidLiquidationBasket {
+                        reason: format!("{denom} deposit requested more than once"),
+                    });
+                }
+                denom.clone()
+            }
+            LiquidateRequest::Lend(denom) => denom.clone(),
+            LiquidateRequest::Vault {
+                request_vault,
+                ..
+            } => request_vault.query_info(&deps.querier)?.base_token,
+            LiquidateRequest::Basket(_) => return Err(nested_basket_error()),
+        };
+
+        if !matches!(request, LiquidateRequest::Deposit(_))
+            && !released_denoms.insert(denom.clone())
+        {
+            return Err(ContractError::InvalidLiquidationBasket {
+                reason: format!("{denom} released by more than one lend or vault request"),
+            });
+        }
+    }
+
+    if let Some(denom) = deposit_denoms.intersection(&released_denoms).next() {
+        return Err(ContractError::InvalidLiquidationBasket {
+            reason: format!(
+                "{denom} requested as a deposit and released by a lend or vault request"
+            ),
+        });
+    }
+
+    if requests.is_empty() {
+        return Err(ContractError::InvalidLiquidationBasket {
+            reason: "no requests".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn nested_basket_error() -> ContractError {
+    ContractError::InvalidLiquidationBasket {
+        reason: "baskets can not be nested".to_string(),
+    }
+}
+
+/// Takes what a position's liquidation repaid and removed from the liquidatee's values, so the
+/// next position is sized from the account as it is after this one. Values are computed the way
+/// the health computer does. Max LTV values are left as read, liquidations do not use them.
+fn take_liquidated(
+    deps: Deps,
+    state: &mut LiquidationState,
+    kind: &AccountKind,
+    request: &LiquidateRequest<Vault>,
+    debt: &Coin,
+    liquidatee_request: &Coin,
+) -> ContractResult<()> {
+    let params = PARAMS.load(deps.storage)?;
+    let (collateral, liquidation_threshold) = match request {
+        LiquidateRequest::Deposit(denom) | LiquidateRequest::Lend(denom) => {
+            let asset_params = params.query_asset_params(&deps.querier, denom)?;
+            let liquidation_threshold = match kind {
+                AccountKind::Default => asset_params.liquidation_threshold,
+                AccountKind::HighLeveredStrategy => {
+                    asset_params
+                        .credit_manager
+                        .hls
+                        .ok_or_else(|| missing_hls_params(denom))?
+                        .liquidation_threshold
+                }
+            };
+            (liquidatee_request.clone(), liquidation_threshold)
+        }
+        LiquidateRequest::Vault {
+            request_vault,
+            ..
+        } => {
+            // Unlocking positions are liquidated in underlying coins already
+            let base_token = request_vault.query_info(&deps.querier)?.base_token;
+            let amount = if liquidatee_request.denom == base_token {
+                liquidatee_request.amount
+            } else {
+                request_vault.query_preview_redeem(&deps.querier, liquidatee_request.amount)?
+            };
+            let config = params.query_vault_config(&deps.querier, &request_vault.address)?;
+            let liquidation_threshold = match kind {
+                AccountKind::Default => config.liquidation_threshold,
+                AccountKind::HighLeveredStrategy => {
+                    config
+                        .hls
+                        .ok_or_else(|| missing_hls_params(request_vault.address.as_str()))?
+                        .liquidation_threshold
+                }
+            };
+            (Coin::new(amount.u128(), base_token), liquidation_threshold)
+        }
+        LiquidateRequest::Basket(_) => return Err(nested_basket_error()),
+    };
+
+    let oracle = ORACLE.load(deps.storage)?;
+    let debt_value = oracle.query_value(&deps.querier, debt, ActionKind::Liquidation)?;
+    let collateral_value =
+        oracle.query_value(&deps.querier, &collateral, ActionKind::Liquidation)?;
+
+    let health = &mut state.health;
+    health.total_debt_value = health.total_debt_value.saturating_sub(debt_value);
+    health.total_collateral_value = health.total_collateral_value.saturating_sub(collateral_value);
+    health.liquidation_threshold_adjusted_collateral = health
+        .liquidation_threshold_adjusted_collateral
+        .saturating_sub(collateral_value.checked_mul_floor(liquidation_threshold)?);
+    health.liquidation_health_factor = if health.total_debt_value.is_zero() {
+        None
+    } else {
+        Some(Decimal::checked_from_ratio(
+            health.liquidation_threshold_adjusted_collateral,
+            health.total_debt_value,
+        )?)
+    };
+    health.liquidatable = is_below_one(&health.liquidation_health_factor);
+
+    state.total_debt_amount = state.total_debt_amount.saturating_sub(debt.amount);
+
+    Ok(())
+}
+
+fn missing_hls_params(target: &str) -> ContractError {
+    ContractError::HLS {
+        reason: format!("{target} does not have HLS parameters"),
+    }
+}
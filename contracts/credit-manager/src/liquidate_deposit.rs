@@ -1,79 +1,132 @@
-use cosmwasm_std::{Coin, CosmosMsg, DepsMut, Env, Response, Storage};
+use cosmwasm_std::{BankMsg, Coin, CosmosMsg, DepsMut, Env, Response, Storage, Uint128};
 use mars_rover::{
     error::{ContractError, ContractResult},
     msg::execute::CallbackMsg,
 };
 
 use crate::{
-    liquidate::calculate_liquidation,
+    liquidate::{
+        calculate_liquidation_for_state, liquidation_state, LiquidatedBucket, LiquidationState,
+        Liquidator,
+    },
     state::{COIN_BALANCES, REWARDS_COLLECTOR},
     utils::{decrement_coin_balance, increment_coin_balance},
 };
 
 pub fn liquidate_deposit(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
-    liquidator_account_id: &str,
+    liquidator: &Liquidator,
     liquidatee_account_id: &str,
     debt_coin: Coin,
     request_coin_denom: &str,
 ) -> ContractResult<Response> {
-    let request_coin_balance = COIN_BALANCES
-        .load(deps.storage, (liquidatee_account_id, request_coin_denom))
-        .map_err(|_| ContractError::CoinNotAvailable(request_coin_denom.to_string()))?;
+    let request_coin_balance =
+        deposit_balance(deps.storage, liquidatee_account_id, request_coin_denom)?;
+    let state = liquidation_state(deps.as_ref(), &env, liquidatee_account_id, &debt_coin.denom)?;
 
-    let (debt, liquidator_request, liquidatee_request) = calculate_liquidation(
-        &deps,
-        liquidatee_account_id,
+    let liquidated = liquidate_deposit_bucket(
+        deps.branch(),
+        liquidator,
+        &state,
         &debt_coin,
         request_coin_denom,
         request_coin_balance,
     )?;
 
-    let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
+    let repay_msgs =
+        repay_debt(deps.storage, &env, liquidator, liquidatee_account_id, &liquidated.debt)?;
+
+    Ok(liquidated.into_response("liquidate_deposit", liquidator, liquidatee_account_id, repay_msgs))
+}
+
+pub fn deposit_balance(
+    storage: &dyn Storage,
+    liquidatee_account_id: &str,
+    request_coin_denom: &str,
+) -> ContractResult<Uint128> {
+    COIN_BALANCES
+        .load(storage, (liquidatee_account_id, request_coin_denom))
+        .map_err(|_| ContractError::CoinNotAvailable(request_coin_denom.to_string()))
+}
+
+/// Transfers the requested coin from the liquidatee to the liquidator and rewards-collector.
+/// The debt is not repaid here, see `repay_debt`.
+pub fn liquidate_deposit_bucket(
+    deps: DepsMut,
+    liquidator: &Liquidator,
+    state: &LiquidationState,
+    debt_coin: &Coin,
+    request_coin_denom: &str,
+    request_coin_balance: Uint128,
+) -> ContractResult<LiquidatedBucket> {
+    let liquidatee_account_id = state.liquidatee_account_id.as_str();
+    let (debt, liquidator_request, liquidatee_request) = calculate_liquidation_for_state(
+        deps.as_ref(),
+        state,
+        debt_coin,
+        request_coin_denom,
+        request_coin_balance,
+    )?;
 
     // Transfer requested coin from liquidatee to liquidator
     decrement_coin_balance(deps.storage, liquidatee_account_id, &liquidatee_request)?;
-    increment_coin_balance(deps.storage, liquidator_account_id, &liquidator_request)?;
+    let msgs = liquidator.receive(deps.storage, &liquidator_request)?;
 
     // Transfer protocol fee to rewards-collector account
     let rewards_collector_account = REWARDS_COLLECTOR.load(deps.storage)?.account_id;
-    let protocol_fee_amount = liquidatee_request.amount.checked_sub(liquidator_request.amount)?;
-    increment_coin_balance(
-        deps.storage,
-        &rewards_collector_account,
-        &Coin::new(protocol_fee_amount.u128(), liquidatee_request.denom.clone()),
-    )?;
+    let protocol_fee = Coin {
+        denom: request_coin_denom.to_string(),
+        amount: liquidatee_request.amount.checked_sub(liquidator_request.amount)?,
+    };
+    increment_coin_balance(deps.storage, &rewards_collector_account, &protocol_fee)?;
 
-    Ok(Response::new()
-        .add_message(repay_msg)
-        .add_attribute("action", "liquidate_deposit")
-        .add_attribute("account_id", liquidator_account_id)
-        .add_attribute("liquidatee_account_id", liquidatee_account_id)
-        .add_attribute("coin_debt_repaid", debt.to_string())
-        .add_attribute("coin_liquidated", liquidatee_request.to_string())
-        .add_attribute(
-            "protocol_fee_coin",
-            Coin::new(protocol_fee_amount.u128(), request_coin_denom).to_string(),
-        ))
+    Ok(LiquidatedBucket {
+        debt,
+        liquidatee_request,
+        protocol_fee,
+        msgs,
+    })
 }
 
+/// Returns the repay callback, followed by the refund of the coin sent by a wallet liquidator in
+/// excess of the debt. Both must run after the liquidated coins have been credited.
 pub fn repay_debt(
     storage: &mut dyn Storage,
     env: &Env,
-    liquidator_account_id: &str,
+    liquidator: &Liquidator,
     liquidatee_account_id: &str,
     debt: &Coin,
-) -> ContractResult<CosmosMsg> {
-    // Transfer debt coin from liquidator's coin balance to liquidatee
+) -> ContractResult<Vec<CosmosMsg>> {
+    // Transfer debt coin from liquidator to liquidatee
     // Will be used to pay off the debt via CallbackMsg::Repay {}
-    decrement_coin_balance(storage, liquidator_account_id, debt)?;
+    let mut refund_msgs = vec![];
+    match liquidator {
+        Liquidator::Account(liquidator_account_id) => {
+            decrement_coin_balance(storage, liquidator_account_id, debt)?;
+        }
+        Liquidator::Wallet {
+            addr,
+            coin_sent,
+        } => {
+            // If the debt repaid was adjusted down, refund back the extra
+            let refund_amount = coin_sent.amount.checked_sub(debt.amount)?;
+            if !refund_amount.is_zero() {
+                refund_msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: addr.to_string(),
+                    amount: vec![Coin {
+                        denom: coin_sent.denom.clone(),
+                        amount: refund_amount,
+                    }],
+                }));
+            }
+        }
+    }
     increment_coin_balance(storage, liquidatee_account_id, debt)?;
     let msg = (CallbackMsg::Repay {
         account_id: liquidatee_account_id.to_string(),
         coin: debt.into(),
     })
     .into_cosmos_msg(&env.contract.address)?;
-    Ok(msg)
+    Ok([vec![msg], refund_msgs].concat())
 }
@@ -1,22 +1,49 @@
-use cosmwasm_std::{Coin, DepsMut, Env, Response};
+use cosmwasm_std::{Coin, Deps, DepsMut, Env, Response, Uint128};
 use mars_rover::error::{ContractError::NoneLent, ContractResult};
 
 use crate::{
-    liquidate::calculate_liquidation,
+    liquidate::{
+        calculate_liquidation_for_state, liquidation_state, LiquidatedBucket, LiquidationState,
+        Liquidator,
+    },
     liquidate_deposit::repay_debt,
     state::{RED_BANK, REWARDS_COLLECTOR},
     utils::increment_coin_balance,
 };
 
 pub fn liquidate_lend(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
-    liquidator_account_id: &str,
+    liquidator: &Liquidator,
     liquidatee_account_id: &str,
     debt_coin: Coin,
     request_coin_denom: &str,
 ) -> ContractResult<Response> {
-    // Check how much lent coin is available for reclaim (can be withdrawn from Red Bank)
+    let total_lent_amount = lent_amount(deps.as_ref(), liquidatee_account_id, request_coin_denom)?;
+    let state = liquidation_state(deps.as_ref(), &env, liquidatee_account_id, &debt_coin.denom)?;
+
+    let liquidated = liquidate_lend_bucket(
+        deps.branch(),
+        liquidator,
+        &state,
+        &debt_coin,
+        request_coin_denom,
+        total_lent_amount,
+    )?;
+
+    // Liquidator pays down debt on behalf of liquidatee
+    let repay_msgs =
+        repay_debt(deps.storage, &env, liquidator, liquidatee_account_id, &liquidated.debt)?;
+
+    Ok(liquidated.into_response("liquidate_lend", liquidator, liquidatee_account_id, repay_msgs))
+}
+
+/// Check how much lent coin is available for reclaim (can be withdrawn from Red Bank)
+pub fn lent_amount(
+    deps: Deps,
+    liquidatee_account_id: &str,
+    request_coin_denom: &str,
+) -> ContractResult<Uint128> {
     let total_lent_amount = RED_BANK.load(deps.storage)?.query_lent(
         &deps.querier,
         liquidatee_account_id,
@@ -27,41 +54,48 @@ pub fn liquidate_lend(
         return Err(NoneLent);
     }
 
-    let (debt, liquidator_request, liquidatee_request) = calculate_liquidation(
-        &deps,
-        liquidatee_account_id,
-        &debt_coin,
+    Ok(total_lent_amount)
+}
+
+/// Reclaims the requested coin from the liquidatee's lent position and transfers it to the
+/// liquidator and rewards-collector. The debt is not repaid here, see `repay_debt`.
+pub fn liquidate_lend_bucket(
+    deps: DepsMut,
+    liquidator: &Liquidator,
+    state: &LiquidationState,
+    debt_coin: &Coin,
+    request_coin_denom: &str,
+    total_lent_amount: Uint128,
+) -> ContractResult<LiquidatedBucket> {
+    let liquidatee_account_id = state.liquidatee_account_id.as_str();
+    let (debt, liquidator_request, liquidatee_request) = calculate_liquidation_for_state(
+        deps.as_ref(),
+        state,
+        debt_coin,
         request_coin_denom,
         total_lent_amount,
     )?;
 
-    // Liquidator pays down debt on behalf of liquidatee
-    let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
-
     // Liquidatee's lent coin reclaimed from Red Bank
     let red_bank = RED_BANK.load(deps.storage)?;
     let reclaim_from_liquidatee_msg =
         red_bank.reclaim_msg(&liquidatee_request, liquidatee_account_id, true)?;
 
     // Liquidator gets portion of reclaimed lent coin
-    increment_coin_balance(deps.storage, liquidator_account_id, &liquidator_request)?;
+    let receive_msgs = liquidator.receive(deps.storage, &liquidator_request)?;
 
     // Transfer protocol fee to rewards-collector account
     let rewards_collector_account = REWARDS_COLLECTOR.load(deps.storage)?.account_id;
-    let protocol_fee_coin = Coin {
+    let protocol_fee = Coin {
         denom: request_coin_denom.to_string(),
         amount: liquidatee_request.amount.checked_sub(liquidator_request.amount)?,
     };
-    increment_coin_balance(deps.storage, &rewards_collector_account, &protocol_fee_coin)?;
+    increment_coin_balance(deps.storage, &rewards_collector_account, &protocol_fee)?;
 
-    Ok(Response::new()
-        .add_message(repay_msg)
-        .add_message(reclaim_from_liquidatee_msg)
-        .add_attribute("action", "liquidate_lend")
-        .add_attribute("account_id", liquidator_account_id)
-        .add_attribute("liquidatee_account_id", liquidatee_account_id)
-        .add_attribute("coin_debt_repaid", debt.to_string())
-        .add_attribute("coin_liquidated", liquidatee_request.to_string())
-        .add_attribute("protocol_fee_coin", protocol_fee_coin.to_string()))
+    Ok(LiquidatedBucket {
+        debt,
+        liquidatee_request,
+        protocol_fee,
+        msgs: [vec![reclaim_from_liquidatee_msg], receive_msgs].concat(),
+    })
 }
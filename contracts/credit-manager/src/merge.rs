@@ -0,0 +1,193 @@
+use cosmwasm_std::{
+    to_binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
+    Uint128, WasmMsg,
+};
+use mars_account_nft_types::msg::ExecuteMsg as NftExecuteMsg;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    adapters::vault::Vault,
+    error::{ContractError, ContractResult},
+    msg::execute::{CallbackMsg, PausableAction},
+    reentrancy_guard::GuardScope,
+};
+use mars_rover_health_types::AccountKind;
+
+use crate::{
+    health::query_health_state,
+    pause::assert_not_paused,
+    query::query_positions,
+    state::{
+        ACCOUNT_NFT, COIN_BALANCES, DEBT_SHARES, DELEGATES, FLASH_LOANS, LIQUIDATABLE_SINCE,
+        RED_BANK, REENTRANCY_GUARD, TRIGGER_ORDERS, VAULT_POSITIONS,
+    },
+    transfer::transfer_vault_position,
+    utils::{assert_is_token_owner, get_account_kind, increment_coin_balance},
+};
+
+/// Moves all positions of `from_account_id` into `into_account_id`. Coin balances, debt shares and
+/// vault positions are moved in storage, lent coins are reclaimed from the Red Bank and lent again
+/// on behalf of `into_account_id`. The merged account must pass the same health and HLS checks as
+/// after `UpdateCreditAccount`.
+///
+/// Burning the emptied account requires the owner to have approved the credit manager for the NFT.
+/// Its trigger orders, delegates and flash loan records are removed along with it.
+pub fn merge_accounts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from_account_id: &str,
+    into_account_id: &str,
+    burn_from_account: bool,
+) -> ContractResult<Response> {
+    if from_account_id == into_account_id {
+        return Err(ContractError::MergeIntoSelf {
+            account_id: from_account_id.to_string(),
+        });
+    }
+
+    assert_is_token_owner(&deps, &info.sender, from_account_id)?;
+    assert_is_token_owner(&deps, &info.sender, into_account_id)?;
+
+    REENTRANCY_GUARD.try_lock(deps.storage, &GuardScope::Global)?;
+
+    // Merging moves positions between accounts, the same as transferring them
+    let from_positions = query_positions(deps.as_ref(), from_account_id)?;
+    let targets = from_positions
+        .deposits
+        .iter()
+        .chain(from_positions.lends.iter())
+        .map(|c| c.denom.clone())
+        .chain(from_positions.debts.iter().map(|d| d.denom.clone()))
+        .chain(from_positions.vaults.iter().map(|v| v.vault.address.to_string()))
+        .collect::<Vec<_>>();
+    assert_not_paused(deps.storage, PausableAction::TransferToAccount, &targets)?;
+
+    let prev_health_state =
+        query_health_state(deps.as_ref(), into_account_id, ActionKind::Default)?;
+
+    move_coin_balances(deps.storage, from_account_id, into_account_id)?;
+    move_debt_shares(deps.storage, from_account_id, into_account_id)?;
+    move_vault_positions(deps.storage, from_account_id, into_account_id)?;
+    LIQUIDATABLE_SINCE.remove(deps.storage, from_account_id);
+
+    let red_bank = RED_BANK.load(deps.storage)?;
+    let mut msgs = vec![];
+    for coin in red_bank.query_all_lent(&deps.querier, from_account_id)? {
+        msgs.push(red_bank.reclaim_msg(&coin, from_account_id, false)?);
+        msgs.push(red_bank.lend_msg(&coin, into_account_id)?);
+    }
+
+    let mut callbacks = vec![];
+    if get_account_kind(deps.storage, into_account_id)? == AccountKind::HighLeveredStrategy {
+        callbacks.push(CallbackMsg::AssertHlsRules {
+            account_id: into_account_id.to_string(),
+        });
+    }
+    callbacks.extend([
+        CallbackMsg::AssertMaxLTV {
+            account_id: into_account_id.to_string(),
+            prev_health_state,
+        },
+        CallbackMsg::RemoveReentrancyGuard {
+            scope: GuardScope::Global,
+        },
+    ]);
+    for callback in callbacks {
+        msgs.push(callback.into_cosmos_msg(&env.contract.address)?);
+    }
+
+    if burn_from_account {
+        clear_account_records(deps.storage, from_account_id)?;
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: ACCOUNT_NFT.load(deps.storage)?.address().into(),
+            msg: to_binary(&NftExecuteMsg::Burn {
+                token_id: from_account_id.to_string(),
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "merge_accounts")
+        .add_attribute("from_account_id", from_account_id)
+        .add_attribute("into_account_id", into_account_id)
+        .add_attribute("burn_from_account", burn_from_account.to_string()))
+}
+
+fn move_coin_balances(storage: &mut dyn Storage, from: &str, into: &str) -> ContractResult<()> {
+    let balances = COIN_BALANCES
+        .prefix(from)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+
+    for (denom, amount) in balances {
+        COIN_BALANCES.remove(storage, (from, &denom));
+        increment_coin_balance(
+            storage,
+            into,
+            &Coin {
+                denom,
+                amount,
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// Shares are moved as is, total debt shares of each denom are unchanged
+fn move_debt_shares(storage: &mut dyn Storage, from: &str, into: &str) -> ContractResult<()> {
+    let debt_shares = DEBT_SHARES
+        .prefix(from)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+
+    for (denom, shares) in debt_shares {
+        DEBT_SHARES.remove(storage, (from, &denom));
+        DEBT_SHARES.update(storage, (into, &denom), |existing| -> ContractResult<_> {
+            Ok(existing.unwrap_or_default().checked_add(shares)?)
+        })?;
+    }
+    Ok(())
+}
+
+/// Removes the records kept for an account that is about to be burned
+fn clear_account_records(storage: &mut dyn Storage, account_id: &str) -> ContractResult<()> {
+    let trigger_order_ids = TRIGGER_ORDERS
+        .prefix(account_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for trigger_order_id in trigger_order_ids {
+        TRIGGER_ORDERS.remove(storage, (account_id, trigger_order_id));
+    }
+
+    let delegates = DELEGATES
+        .prefix(account_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for delegate in delegates {
+        DELEGATES.remove(storage, (account_id, &delegate));
+    }
+
+    let flash_loan_denoms = FLASH_LOANS
+        .prefix(account_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for denom in flash_loan_denoms {
+        FLASH_LOANS.remove(storage, (account_id, &denom));
+    }
+
+    Ok(())
+}
+
+fn move_vault_positions(storage: &mut dyn Storage, from: &str, into: &str) -> ContractResult<()> {
+    let positions = VAULT_POSITIONS
+        .prefix(from)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (vault_addr, amount) in positions {
+        transfer_vault_position(storage, from, into, &Vault::new(vault_addr), amount)?;
+    }
+    Ok(())
+}
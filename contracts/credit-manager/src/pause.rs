@@ -0,0 +1,95 @@
+use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult, Storage};
+use mars_rover::{
+    error::{ContractError, ContractResult},
+    msg::{
+        execute::{Action, ActionPause, PausableAction},
+        instantiate::Role,
+    },
+};
+
+use crate::{
+    roles::has_role,
+    state::{OWNER, PAUSED_ACTIONS},
+};
+
+pub fn pause_action(
+    deps: DepsMut,
+    info: MessageInfo,
+    pause: ActionPause,
+) -> ContractResult<Response> {
+    if !has_role(deps.storage, &info.sender, &Role::Pauser)? {
+        OWNER.assert_emergency_owner(deps.storage, &info.sender)?;
+    }
+
+    let mut paused = query_paused_actions(deps.storage)?;
+    if !paused.contains(&pause) {
+        paused.push(pause.clone());
+        PAUSED_ACTIONS.save(deps.storage, &paused)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "pause_action")
+        .add_attribute("paused_action", format!("{:?}", pause.action))
+        .add_attribute("target", pause.target.unwrap_or_else(|| "all".to_string())))
+}
+
+pub fn unpause_action(
+    deps: DepsMut,
+    info: MessageInfo,
+    pause: ActionPause,
+) -> ContractResult<Response> {
+    OWNER.assert_owner(deps.storage, &info.sender)?;
+
+    let mut paused = query_paused_actions(deps.storage)?;
+    paused.retain(|p| p != &pause);
+    PAUSED_ACTIONS.save(deps.storage, &paused)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause_action")
+        .add_attribute("unpaused_action", format!("{:?}", pause.action))
+        .add_attribute("target", pause.target.unwrap_or_else(|| "all".to_string())))
+}
+
+/// Rejects actions paused entirely, or for any of the denoms or vaults they act upon
+pub fn assert_actions_not_paused(storage: &dyn Storage, actions: &[Action]) -> ContractResult<()> {
+    let paused = query_paused_actions(storage)?;
+    if paused.is_empty() {
+        return Ok(());
+    }
+
+    actions
+        .iter()
+        .flat_map(|a| a.pause_targets())
+        .try_for_each(|(action, targets)| assert_not_in_paused(&paused, action, &targets))
+}
+
+/// Rejects a step of a composite action whose targets are only known while it runs
+pub fn assert_not_paused(
+    storage: &dyn Storage,
+    action: PausableAction,
+    targets: &[String],
+) -> ContractResult<()> {
+    let paused = query_paused_actions(storage)?;
+    assert_not_in_paused(&paused, action, targets)
+}
+
+fn assert_not_in_paused(
+    paused: &[ActionPause],
+    action: PausableAction,
+    targets: &[String],
+) -> ContractResult<()> {
+    let hit = paused.iter().find(|p| {
+        p.action == action && p.target.as_ref().map_or(true, |target| targets.contains(target))
+    });
+    if let Some(pause) = hit {
+        return Err(ContractError::ActionPaused {
+            action,
+            target: pause.target.clone(),
+        });
+    }
+    Ok(())
+}
+
+pub fn query_paused_actions(storage: &dyn Storage) -> StdResult<Vec<ActionPause>> {
+    Ok(PAUSED_ACTIONS.may_load(storage)?.unwrap_or_default())
+}
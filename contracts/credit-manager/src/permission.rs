@@ -0,0 +1,108 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response};
+use cw_utils::Expiration;
+use mars_rover::{
+    error::{ContractError, ContractResult},
+    msg::{
+        execute::{Action, ActionPermission},
+        query::Delegate,
+    },
+};
+
+use crate::{
+    state::DELEGATES,
+    utils::{assert_is_token_owner, query_nft_token_owner, query_nft_transfer_nonce},
+};
+
+pub fn grant_permission(
+    deps: DepsMut,
+    info: MessageInfo,
+    account_id: &str,
+    delegate: &str,
+    permissions: Vec<ActionPermission>,
+    expires: Option<Expiration>,
+) -> ContractResult<Response> {
+    assert_is_token_owner(&deps, &info.sender, account_id)?;
+
+    let delegate_addr = deps.api.addr_validate(delegate)?;
+    let transfer_nonce = query_nft_transfer_nonce(deps, account_id)?;
+    DELEGATES.save(
+        deps.storage,
+        (account_id, &delegate_addr),
+        &Delegate {
+            delegate: delegate_addr.to_string(),
+            granter: info.sender.to_string(),
+            transfer_nonce,
+            permissions,
+            expires,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_permission")
+        .add_attribute("account_id", account_id)
+        .add_attribute("delegate", delegate_addr))
+}
+
+pub fn revoke_permission(
+    deps: DepsMut,
+    info: MessageInfo,
+    account_id: &str,
+    delegate: &str,
+) -> ContractResult<Response> {
+    assert_is_token_owner(&deps, &info.sender, account_id)?;
+
+    let delegate_addr = deps.api.addr_validate(delegate)?;
+    DELEGATES.remove(deps.storage, (account_id, &delegate_addr));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_permission")
+        .add_attribute("account_id", account_id)
+        .add_attribute("delegate", delegate_addr))
+}
+
+/// Asserts the sender is either the owner of the account or a delegate permitted to
+/// perform all of the actions. Returns the owner of the account.
+pub fn assert_is_owner_or_delegate(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    account_id: &str,
+    actions: &[Action],
+) -> ContractResult<Addr> {
+    let owner = query_nft_token_owner(deps, account_id)?;
+    if sender == &owner {
+        return Ok(sender.clone());
+    }
+
+    // Grants made before the account was last transferred are not valid, even if it has come
+    // back to the owner who granted them
+    let transfer_nonce = query_nft_transfer_nonce(deps.as_ref(), account_id)?;
+    let delegate = DELEGATES
+        .may_load(deps.storage, (account_id, sender))?
+        .filter(|delegate| delegate.granter == owner && delegate.transfer_nonce == transfer_nonce)
+        .ok_or_else(|| ContractError::NotTokenOwner {
+            user: sender.to_string(),
+            account_id: account_id.to_string(),
+        })?;
+
+    if delegate.expires.map_or(false, |expires| expires.is_expired(&env.block)) {
+        return Err(ContractError::PermissionExpired {
+            delegate: sender.to_string(),
+            account_id: account_id.to_string(),
+        });
+    }
+
+    for action in actions {
+        match action.permission() {
+            Some(permission) if delegate.permissions.contains(&permission) => {}
+            _ => {
+                return Err(ContractError::Unauthorized {
+                    user: sender.to_string(),
+                    action: format!("{action:?}"),
+                })
+            }
+        }
+    }
+
+    Ok(Addr::unchecked(owner))
+}
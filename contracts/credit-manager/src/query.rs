@@ -1,27 +1,33 @@
-use cosmwasm_std::{Coin, Deps, Env, Order, StdResult};
+use cosmwasm_std::{Addr, Coin, Deps, Env, Order, StdResult};
 use cw_paginate::paginate_map;
-use cw_storage_plus::Bound;
+use cw_storage_plus::{Bound, PrefixBound};
 use mars_red_bank_types::oracle::ActionKind;
 use mars_rover::{
     adapters::vault::{VaultBase, VaultPosition, VaultPositionValue, VaultUnchecked},
     error::ContractResult,
     msg::query::{
-        Account, CoinBalanceResponseItem, ConfigResponse, DebtAmount, DebtShares, Positions,
-        SharesResponseItem, VaultPositionResponseItem, VaultUtilizationResponse,
+        Account, CoinBalanceResponseItem, ConfigResponse, DebtAmount, DebtShares, Delegate,
+        Positions, SharesResponseItem, TriggerOrder, VaultPositionResponseItem,
+        VaultUtilizationResponse,
     },
 };
 use mars_rover_health_types::AccountKind;
 
 use crate::{
     state::{
-        ACCOUNT_KINDS, ACCOUNT_NFT, COIN_BALANCES, DEBT_SHARES, HEALTH_CONTRACT, INCENTIVES,
-        MAX_SLIPPAGE, MAX_UNLOCKING_POSITIONS, ORACLE, OWNER, PARAMS, RED_BANK, REWARDS_COLLECTOR,
-        SWAPPER, TOTAL_DEBT_SHARES, VAULT_POSITIONS, ZAPPER,
+        ACCOUNT_KINDS, ACCOUNT_NFT, BAD_DEBT_DUST_THRESHOLD, COIN_BALANCES, CONFIG_UPDATE_DELAY,
+        DEBT_SHARES, DELEGATES, FLASH_LOAN_FEE, HEALTH_CONTRACT, INCENTIVES, KEEPER_FEE,
+        LIQUIDATION_AUCTION, MAX_SLIPPAGE, MAX_UNLOCKING_POSITIONS, ORACLE, OWNER, PARAMS,
+        RED_BANK, REWARDS_COLLECTOR, SWAPPER, TOTAL_DEBT_SHARES, TRIGGER_ORDERS, VAULT_POSITIONS,
+        ZAPPER,
     },
     utils::debt_shares_to_amount,
     vault::vault_utilization_in_deposit_cap_denom,
 };
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 pub fn query_accounts(
     deps: Deps,
     owner: String,
@@ -45,6 +51,39 @@ pub fn query_accounts(
         .collect()
 }
 
+/// Enumerate accounts with outstanding debt, each listed once regardless of the number of debt denoms
+pub fn query_accounts_with_debt(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<Vec<Account>> {
+    let start = start_after.as_deref().map(PrefixBound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let mut account_ids: Vec<String> = vec![];
+    for item in DEBT_SHARES.prefix_range(deps.storage, start, None, Order::Ascending) {
+        let ((account_id, _), _) = item?;
+        if account_ids.last() == Some(&account_id) {
+            continue;
+        }
+        if account_ids.len() == limit {
+            break;
+        }
+        account_ids.push(account_id);
+    }
+
+    account_ids
+        .into_iter()
+        .map(|id| {
+            let kind = ACCOUNT_KINDS.may_load(deps.storage, &id)?.unwrap_or(AccountKind::Default);
+            Ok(Account {
+                id,
+                kind,
+            })
+        })
+        .collect()
+}
+
 pub fn query_config(deps: Deps) -> ContractResult<ConfigResponse> {
     Ok(ConfigResponse {
         ownership: OWNER.query(deps.storage)?,
@@ -59,6 +98,13 @@ pub fn query_config(deps: Deps) -> ContractResult<ConfigResponse> {
         zapper: ZAPPER.load(deps.storage)?.address().into(),
         health_contract: HEALTH_CONTRACT.load(deps.storage)?.address().into(),
         rewards_collector: REWARDS_COLLECTOR.may_load(deps.storage)?,
+        keeper_fee: KEEPER_FEE.may_load(deps.storage)?,
+        flash_loan_fee: FLASH_LOAN_FEE.may_load(deps.storage)?.unwrap_or_default(),
+        liquidation_auction: LIQUIDATION_AUCTION.may_load(deps.storage)?,
+        bad_debt_dust_threshold: BAD_DEBT_DUST_THRESHOLD
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        config_update_delay: CONFIG_UPDATE_DELAY.may_load(deps.storage)?.unwrap_or_default(),
     })
 }
 
@@ -89,7 +135,7 @@ pub fn query_all_coin_balances(
     })
 }
 
-fn query_debt_amounts(deps: Deps, account_id: &str) -> ContractResult<Vec<DebtAmount>> {
+pub fn query_debt_amounts(deps: Deps, account_id: &str) -> ContractResult<Vec<DebtAmount>> {
     DEBT_SHARES
         .prefix(account_id)
         .range(deps.storage, None, None, Order::Ascending)
@@ -214,3 +260,46 @@ pub fn query_vault_position_value(
     let oracle = ORACLE.load(deps.storage)?;
     vault_position.query_values(&deps.querier, &oracle, ActionKind::Default)
 }
+
+pub fn query_trigger_orders(
+    deps: Deps,
+    account_id: &str,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<TriggerOrder>> {
+    let start = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    TRIGGER_ORDERS
+        .prefix(account_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+pub fn query_all_trigger_orders(
+    deps: Deps,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<TriggerOrder>> {
+    let start =
+        start_after.as_ref().map(|(account_id, id)| Bound::exclusive((account_id.as_str(), *id)));
+    paginate_map(&TRIGGER_ORDERS, deps.storage, start, limit, |_, order| Ok(order))
+}
+
+pub fn query_delegates(
+    deps: Deps,
+    account_id: &str,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Delegate>> {
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    DELEGATES
+        .prefix(account_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
@@ -2,16 +2,27 @@ use cosmwasm_std::{to_binary, Addr, CosmosMsg, DepsMut, Env, Response, WasmMsg};
 use mars_rover::{
     error::ContractResult,
     msg::{
-        execute::{ActionAmount, ActionCoin, CallbackMsg},
+        execute::{ActionAmount, ActionCoin, CallbackMsg, Recipient},
         ExecuteMsg,
     },
 };
 
 use crate::{query::query_coin_balances, utils::query_nft_token_owner};
 
-pub fn refund_coin_balances(deps: DepsMut, env: Env, account_id: &str) -> ContractResult<Response> {
+/// Withdraws every coin balance to `recipient`, the account owner if `None`
+pub fn refund_coin_balances(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    recipient: Option<Recipient>,
+) -> ContractResult<Response> {
     let coins = query_coin_balances(deps.as_ref(), account_id)?;
-    let account_nft_owner = query_nft_token_owner(deps.as_ref(), account_id)?;
+    let recipient = match recipient {
+        Some(recipient) => recipient,
+        None => {
+            Recipient::Wallet(Addr::unchecked(query_nft_token_owner(deps.as_ref(), account_id)?))
+        }
+    };
     let withdraw_msgs = coins
         .into_iter()
         .map(|coin| {
@@ -26,7 +37,7 @@ pub fn refund_coin_balances(deps: DepsMut, env: Env, account_id: &str) -> Contra
                 msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::Withdraw {
                     account_id: account_id.to_string(),
                     coin: action_coin,
-                    recipient: Addr::unchecked(account_nft_owner.clone()),
+                    recipient: recipient.clone(),
                 }))?,
             }))
         })
@@ -34,5 +45,6 @@ pub fn refund_coin_balances(deps: DepsMut, env: Env, account_id: &str) -> Contra
     Ok(Response::new()
         .add_messages(withdraw_msgs)
         .add_attribute("action", "callback/refund_coin_balances")
-        .add_attribute("account_id", account_id.to_string()))
+        .add_attribute("account_id", account_id.to_string())
+        .add_attribute("recipient", recipient.address().to_string()))
 }
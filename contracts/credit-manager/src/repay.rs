@@ -1,21 +1,24 @@
 use std::cmp::min;
 
 use cosmwasm_std::{
-    to_binary, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, Uint128,
-    WasmMsg,
+    to_binary, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Uint128, WasmMsg,
 };
 use cw_utils::one_coin;
+use mars_red_bank_types::oracle::ActionKind;
 use mars_rover::{
     error::{ContractError, ContractResult},
     msg::{
-        execute::{ActionCoin, CallbackMsg::Repay},
+        execute::{ActionAmount, ActionCoin, CallbackMsg, CallbackMsg::Repay},
         ExecuteMsg,
     },
 };
 
 use crate::{
-    state::{DEBT_SHARES, RED_BANK, TOTAL_DEBT_SHARES},
-    utils::{debt_shares_to_amount, decrement_coin_balance, increment_coin_balance},
+    state::{COIN_BALANCES, DEBT_SHARES, ORACLE, RED_BANK, TOTAL_DEBT_SHARES},
+    utils::{
+        assert_slippage, debt_shares_to_amount, decrement_coin_balance, increment_coin_balance,
+    },
 };
 
 pub fn repay(deps: DepsMut, account_id: &str, coin: &ActionCoin) -> ContractResult<Response> {
@@ -171,3 +174,93 @@ pub fn repay_from_wallet(
 
     Ok(response)
 }
+
+/// Swaps collateral into the debt denom. The swap output is only known once the swap has
+/// taken place, so it is repaid in a subsequent callback.
+pub fn repay_from_collateral(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    collateral: ActionCoin,
+    debt_denom: &str,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    assert_slippage(deps.storage, slippage)?;
+
+    // Ensure there is debt to repay before swapping
+    current_debt_for_denom(deps.as_ref(), account_id, debt_denom)?;
+
+    let previous_balance =
+        COIN_BALANCES.may_load(deps.storage, (account_id, debt_denom))?.unwrap_or(Uint128::zero());
+
+    // The account may be unhealthy, so no health check guards the swap. Instead the output must be
+    // worth at least the collateral swapped, less the slippage allowance.
+    let collateral_amount = match collateral.amount {
+        ActionAmount::Exact(a) => a,
+        ActionAmount::AccountBalance => COIN_BALANCES
+            .may_load(deps.storage, (account_id, &collateral.denom))?
+            .unwrap_or(Uint128::zero()),
+    };
+    let oracle = ORACLE.load(deps.storage)?;
+    let collateral_price =
+        oracle.query_price(&deps.querier, &collateral.denom, ActionKind::Default)?.price;
+    let debt_price = oracle.query_price(&deps.querier, debt_denom, ActionKind::Default)?.price;
+    let min_amount_out = collateral_amount.checked_mul_floor(
+        collateral_price.checked_div(debt_price)?.checked_mul(Decimal::one() - slippage)?,
+    )?;
+
+    let callback_msgs = [
+        CallbackMsg::SwapExactIn {
+            account_id: account_id.to_string(),
+            coin_in: collateral,
+            denom_out: debt_denom.to_string(),
+            slippage,
+        },
+        CallbackMsg::RepaySwapOutput {
+            account_id: account_id.to_string(),
+            denom: debt_denom.to_string(),
+            previous_balance,
+            min_amount_out,
+        },
+    ]
+    .iter()
+    .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+    .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new()
+        .add_messages(callback_msgs)
+        .add_attribute("action", "repay_from_collateral")
+        .add_attribute("account_id", account_id)
+        .add_attribute("debt_denom", debt_denom))
+}
+
+/// Repays debt with what the swap added to the coin balance. Whatever exceeds the debt
+/// stays in the account.
+pub fn repay_swap_output(
+    deps: DepsMut,
+    account_id: &str,
+    denom: &str,
+    previous_balance: Uint128,
+    min_amount_out: Uint128,
+) -> ContractResult<Response> {
+    let balance =
+        COIN_BALANCES.may_load(deps.storage, (account_id, denom))?.unwrap_or(Uint128::zero());
+    let swap_output = balance.checked_sub(previous_balance)?;
+
+    if swap_output < min_amount_out {
+        return Err(ContractError::SwapOutputTooLow {
+            denom: denom.to_string(),
+            received: swap_output,
+            minimum: min_amount_out,
+        });
+    }
+
+    repay(
+        deps,
+        account_id,
+        &ActionCoin {
+            denom: denom.to_string(),
+            amount: ActionAmount::Exact(swap_output),
+        },
+    )
+}
@@ -0,0 +1,108 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, MessageInfo, Response, StdResult, Storage};
+use cw_paginate::paginate_map;
+use cw_storage_plus::Bound;
+use mars_owner::OwnerError;
+use mars_rover::{
+    error::{ContractError, ContractResult},
+    msg::{
+        instantiate::{ConfigUpdates, Role},
+        query::RolesResponseItem,
+    },
+};
+
+use crate::state::{OWNER, ROLES};
+
+pub fn grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: &str,
+    role: Role,
+) -> ContractResult<Response> {
+    OWNER.assert_owner(deps.storage, &info.sender)?;
+
+    let addr = deps.api.addr_validate(address)?;
+    let mut roles = ROLES.may_load(deps.storage, &addr)?.unwrap_or_default();
+    if !roles.contains(&role) {
+        roles.push(role.clone());
+        ROLES.save(deps.storage, &addr, &roles)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("address", addr)
+        .add_attribute("role", format!("{role:?}")))
+}
+
+pub fn revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: &str,
+    role: Role,
+) -> ContractResult<Response> {
+    OWNER.assert_owner(deps.storage, &info.sender)?;
+
+    let addr = deps.api.addr_validate(address)?;
+    let mut roles = ROLES.may_load(deps.storage, &addr)?.unwrap_or_default();
+    roles.retain(|r| r != &role);
+    if roles.is_empty() {
+        ROLES.remove(deps.storage, &addr);
+    } else {
+        ROLES.save(deps.storage, &addr, &roles)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("address", addr)
+        .add_attribute("role", format!("{role:?}")))
+}
+
+pub fn has_role(storage: &dyn Storage, addr: &Addr, role: &Role) -> StdResult<bool> {
+    Ok(ROLES.may_load(storage, addr)?.unwrap_or_default().contains(role))
+}
+
+/// The owner may update any field. Others must hold the role gating each of the fields set,
+/// and can not update owner-only fields.
+pub fn assert_can_update_config(
+    storage: &dyn Storage,
+    sender: &Addr,
+    updates: &ConfigUpdates,
+) -> ContractResult<()> {
+    if OWNER.is_owner(storage, sender)? {
+        return Ok(());
+    }
+
+    let roles = ROLES.may_load(storage, sender)?.unwrap_or_default();
+    if roles.is_empty() {
+        return Err(OwnerError::NotOwner {}.into());
+    }
+
+    for (field, required_role) in updates.required_roles() {
+        let Some(role) = required_role else {
+            return Err(OwnerError::NotOwner {}.into());
+        };
+        if !roles.contains(&role) {
+            return Err(ContractError::MissingRole {
+                user: sender.to_string(),
+                role,
+                field: field.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn query_roles(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RolesResponseItem>> {
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+    paginate_map(&ROLES, deps.storage, start, limit, |address, roles| {
+        Ok(RolesResponseItem {
+            address: address.to_string(),
+            roles,
+        })
+    })
+}
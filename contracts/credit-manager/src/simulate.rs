@@ -0,0 +1,564 @@
+use std::{cmp::min, collections::BTreeSet};
+
+use cosmwasm_std::{Coin, Deps, Env, Uint128};
+use mars_rover::{
+    adapters::vault::{
+        UnlockingChange, UpdateType, Vault, VaultPosition, VaultPositionUpdate,
+        VaultUnlockingPosition,
+    },
+    error::{ContractError, ContractResult},
+    msg::{
+        execute::{Action, ActionAmount, ActionCoin},
+        query::{
+            DebtAmount, Positions, SimulateActionsResponse, SimulationRejection, SimulationRule,
+        },
+    },
+};
+use mars_rover_health_types::AccountKind;
+
+use crate::{
+    borrow::DEFAULT_DEBT_SHARES_PER_COIN_BORROWED,
+    execute::no_health_check,
+    health::{assert_health_not_weakened, compute_health_values},
+    hls::assert_positions_follow_hls_rules,
+    leverage::leverage_amount,
+    pause::assert_actions_not_paused,
+    permission::assert_is_owner_or_delegate,
+    query::query_positions,
+    repay::current_debt_for_denom,
+    state::{PARAMS, RED_BANK, SWAPPER, TOTAL_DEBT_SHARES, ZAPPER},
+    utils::get_account_kind,
+};
+
+/// Applies the actions to an in-memory copy of the account's positions and runs the same
+/// end-of-bundle assertions `UpdateCreditAccount` would. Async steps (swaps, zaps, vaults)
+/// are estimated via queries, so the resulting positions are not guaranteed to be exact.
+pub fn query_simulate_actions(
+    deps: Deps,
+    env: Env,
+    sender: &str,
+    account_id: &str,
+    actions: Vec<Action>,
+) -> ContractResult<SimulateActionsResponse> {
+    let sender = deps.api.addr_validate(sender)?;
+    assert_is_owner_or_delegate(deps, &env, &sender, account_id, &actions)?;
+    assert_actions_not_paused(deps.storage, &actions)?;
+
+    let kind = get_account_kind(deps.storage, account_id)?;
+    let prev_positions = query_positions(deps, account_id)?;
+    let health_before = compute_health_values(deps, &kind, &prev_positions)?;
+
+    let no_health_check = no_health_check(&actions);
+
+    let mut positions = prev_positions.clone();
+    let mut denoms_for_cap_check = BTreeSet::new();
+    for action in actions {
+        simulate_action(deps, &kind, &mut positions, &mut denoms_for_cap_check, action)?;
+    }
+    positions.deposits.sort_by(|a, b| a.denom.cmp(&b.denom));
+    positions.lends.sort_by(|a, b| a.denom.cmp(&b.denom));
+    positions.debts.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    let health_after = compute_health_values(deps, &kind, &positions)?;
+
+    // Assertions are run in the same order as the callbacks of `UpdateCreditAccount`
+    let mut rejected_by = None;
+    if kind == AccountKind::HighLeveredStrategy {
+        if let Err(err) = assert_positions_follow_hls_rules(deps, &positions) {
+            rejected_by = Some(rejection(SimulationRule::HlsRules, err));
+        }
+    }
+    if rejected_by.is_none() && !no_health_check {
+        if let Err(err) = assert_health_not_weakened(
+            account_id,
+            &(&health_before).into(),
+            &(&health_after).into(),
+        ) {
+            rejected_by = Some(rejection(SimulationRule::MaxLtv, err));
+        }
+    }
+    if rejected_by.is_none() {
+        if let Err(err) =
+            assert_simulated_deposit_caps(deps, &prev_positions, &positions, denoms_for_cap_check)
+        {
+            rejected_by = Some(rejection(SimulationRule::DepositCaps, err));
+        }
+    }
+
+    Ok(SimulateActionsResponse {
+        positions,
+        health_before,
+        health_after,
+        rejected_by,
+    })
+}
+
+fn rejection(rule: SimulationRule, err: ContractError) -> SimulationRejection {
+    SimulationRejection {
+        rule,
+        reason: err.to_string(),
+    }
+}
+
+/// The total deposit of a denom only goes up by what the account gained in the simulation
+fn assert_simulated_deposit_caps(
+    deps: Deps,
+    prev: &Positions,
+    curr: &Positions,
+    denoms: BTreeSet<String>,
+) -> ContractResult<()> {
+    let params = PARAMS.load(deps.storage)?;
+
+    for denom in denoms {
+        let total = params.query_total_deposit(&deps.querier, &denom)?;
+        let prev_amount = amount_of(&prev.deposits, &denom) + amount_of(&prev.lends, &denom);
+        let curr_amount = amount_of(&curr.deposits, &denom) + amount_of(&curr.lends, &denom);
+        let amount = total.amount + curr_amount.saturating_sub(prev_amount);
+
+        if amount > total.cap {
+            return Err(ContractError::AboveAssetDepositCap {
+                new_value: Coin {
+                    denom,
+                    amount,
+                },
+                maximum: total.cap,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn simulate_action(
+    deps: Deps,
+    kind: &AccountKind,
+    positions: &mut Positions,
+    denoms_for_cap_check: &mut BTreeSet<String>,
+    action: Action,
+) -> ContractResult<()> {
+    match action {
+        Action::Deposit(coin) => {
+            add_coin(&mut positions.deposits, &coin)?;
+            denoms_for_cap_check.insert(coin.denom);
+        }
+        Action::Withdraw(coin)
+        | Action::WithdrawToRecipient {
+            coin,
+            ..
+        } => {
+            let coin = resolve_action_coin(&positions.deposits, &coin);
+            sub_coin(&mut positions.deposits, &coin)?;
+        }
+        Action::Borrow(coin) => {
+            if coin.amount.is_zero() {
+                return Err(ContractError::NoAmount);
+            }
+            let shares = debt_shares_for_amount(deps, &coin)?;
+            match positions.debts.iter_mut().find(|d| d.denom == coin.denom) {
+                Some(debt) => {
+                    debt.amount = debt.amount.checked_add(coin.amount)?;
+                    debt.shares = debt.shares.checked_add(shares)?;
+                }
+                None => positions.debts.push(DebtAmount {
+                    denom: coin.denom.clone(),
+                    shares,
+                    amount: coin.amount,
+                }),
+            }
+            add_coin(&mut positions.deposits, &coin)?;
+        }
+        Action::Repay {
+            recipient_account_id: None,
+            coin,
+        } => {
+            let Some(index) = positions.debts.iter().position(|d| d.denom == coin.denom) else {
+                return Err(ContractError::NoDebt);
+            };
+            let debt = &mut positions.debts[index];
+            let coin_to_repay = Coin {
+                denom: coin.denom.clone(),
+                amount: min(
+                    debt.amount,
+                    coin.amount.value().unwrap_or(amount_of(&positions.deposits, &coin.denom)),
+                ),
+            };
+            if coin_to_repay.amount == debt.amount {
+                positions.debts.remove(index);
+            } else {
+                let shares_to_repay =
+                    debt.shares.checked_multiply_ratio(coin_to_repay.amount, debt.amount)?;
+                debt.shares = debt.shares.checked_sub(shares_to_repay)?;
+                debt.amount = debt.amount.checked_sub(coin_to_repay.amount)?;
+            }
+            sub_coin(&mut positions.deposits, &coin_to_repay)?;
+        }
+        Action::Repay {
+            recipient_account_id: Some(recipient),
+            coin,
+        } => {
+            let (debt_amount, _) = current_debt_for_denom(deps, &recipient, &coin.denom)?;
+            let coin_to_repay = Coin {
+                denom: coin.denom.clone(),
+                amount: min(debt_amount, coin.amount.value().unwrap_or(Uint128::MAX)),
+            };
+            sub_coin(&mut positions.deposits, &coin_to_repay)?;
+        }
+        Action::Lend(coin) => {
+            let coin = resolve_action_coin(&positions.deposits, &coin);
+            if coin.amount.is_zero() {
+                return Err(ContractError::NoAmount);
+            }
+            sub_coin(&mut positions.deposits, &coin)?;
+            add_coin(&mut positions.lends, &coin)?;
+        }
+        Action::Reclaim(coin) => {
+            let coin = resolve_action_coin(&positions.lends, &coin);
+            if coin.amount.is_zero() {
+                return Err(ContractError::NoneLent);
+            }
+            sub_coin(&mut positions.lends, &coin)?;
+            add_coin(&mut positions.deposits, &coin)?;
+        }
+        // Claimed rewards are always sent to the recipient, leaving the account's positions as is
+        Action::ClaimRewards {
+            ..
+        } => {}
+        Action::EnterVault {
+            vault,
+            coin,
+        } => {
+            let vault = vault.check(deps.api)?;
+            let coin = resolve_action_coin(&positions.deposits, &coin);
+            sub_coin(&mut positions.deposits, &coin)?;
+            let vault_coins = vault.query_preview_deposit(&deps.querier, coin.amount)?;
+            let update = match vault.query_lockup_duration(&deps.querier).ok() {
+                None => VaultPositionUpdate::Unlocked(UpdateType::Increment(vault_coins)),
+                Some(_) => VaultPositionUpdate::Locked(UpdateType::Increment(vault_coins)),
+            };
+            update_vault_position(positions, &vault, update)?;
+        }
+        Action::ExitVault {
+            vault,
+            amount,
+        } => {
+            let vault = vault.check(deps.api)?;
+            update_vault_position(
+                positions,
+                &vault,
+                VaultPositionUpdate::Unlocked(UpdateType::Decrement(amount)),
+            )?;
+            let base_token = vault.query_info(&deps.querier)?.base_token;
+            let base_amount = vault.query_preview_redeem(&deps.querier, amount)?;
+            add_coin(&mut positions.deposits, &Coin::new(base_amount.u128(), base_token))?;
+        }
+        Action::RequestVaultUnlock {
+            vault,
+            amount,
+        } => {
+            let vault = vault.check(deps.api)?;
+            update_vault_position(
+                positions,
+                &vault,
+                VaultPositionUpdate::Locked(UpdateType::Decrement(amount)),
+            )?;
+            // The unlocking position id is only known once the vault issues it
+            let base_token = vault.query_info(&deps.querier)?.base_token;
+            let base_amount = vault.query_preview_redeem(&deps.querier, amount)?;
+            update_vault_position(
+                positions,
+                &vault,
+                VaultPositionUpdate::Unlocking(UnlockingChange::Add(VaultUnlockingPosition {
+                    id: 0,
+                    coin: Coin::new(base_amount.u128(), base_token),
+                })),
+            )?;
+        }
+        Action::ExitVaultUnlocked {
+            id,
+            vault,
+        } => {
+            let vault = vault.check(deps.api)?;
+            let unlocking = positions
+                .vaults
+                .iter()
+                .find(|v| v.vault.address == vault.address)
+                .and_then(|v| v.amount.get_unlocking_position(id))
+                .ok_or_else(|| ContractError::NoPositionMatch(id.to_string()))?;
+            update_vault_position(
+                positions,
+                &vault,
+                VaultPositionUpdate::Unlocking(UnlockingChange::Decrement {
+                    id,
+                    amount: unlocking.coin.amount,
+                }),
+            )?;
+            add_coin(&mut positions.deposits, &unlocking.coin)?;
+        }
+        Action::Liquidate {
+            ..
+        } => return Err(ContractError::SimulationNotSupported("Liquidate".to_string())),
+        Action::SwapExactIn {
+            coin_in,
+            denom_out,
+            ..
+        } => {
+            let coin_in = resolve_action_coin(&positions.deposits, &coin_in);
+            if coin_in.amount.is_zero() {
+                return Err(ContractError::NoAmount);
+            }
+            sub_coin(&mut positions.deposits, &coin_in)?;
+            let swapper = SWAPPER.load(deps.storage)?;
+            let amount_out = swapper.estimate_exact_in_swap(&deps.querier, &coin_in, &denom_out)?;
+            add_coin(&mut positions.deposits, &Coin::new(amount_out.u128(), denom_out.clone()))?;
+            denoms_for_cap_check.insert(denom_out);
+        }
+        Action::SwapExactOut {
+            denom_in,
+            coin_out,
+            max_amount_in,
+        } => {
+            if denom_in == coin_out.denom {
+                return Err(ContractError::SwapSameDenom(denom_in));
+            }
+            let swapper = SWAPPER.load(deps.storage)?;
+            let amount_in = swapper.estimate_exact_out_swap(&deps.querier, &denom_in, &coin_out)?;
+            let max_amount_in = match max_amount_in {
+                ActionAmount::Exact(a) => a,
+                ActionAmount::AccountBalance => amount_of(&positions.deposits, &denom_in),
+            };
+            // The swapper is sent the full max amount, so it must be covered
+            amount_of(&positions.deposits, &denom_in).checked_sub(max_amount_in)?;
+            sub_coin(&mut positions.deposits, &Coin::new(amount_in.u128(), denom_in))?;
+            add_coin(&mut positions.deposits, &coin_out)?;
+            denoms_for_cap_check.insert(coin_out.denom);
+        }
+        Action::ProvideLiquidity {
+            coins_in,
+            lp_token_out,
+            ..
+        } => {
+            let coins_in = coins_in
+                .iter()
+                .map(|c| resolve_action_coin(&positions.deposits, c))
+                .filter(|c| !c.amount.is_zero())
+                .collect::<Vec<_>>();
+            for coin in coins_in.iter() {
+                sub_coin(&mut positions.deposits, coin)?;
+            }
+            let zapper = ZAPPER.load(deps.storage)?;
+            let lp_amount =
+                zapper.estimate_provide_liquidity(&deps.querier, &lp_token_out, &coins_in)?;
+            add_coin(&mut positions.deposits, &Coin::new(lp_amount.u128(), lp_token_out))?;
+        }
+        Action::WithdrawLiquidity {
+            lp_token,
+            ..
+        } => {
+            let lp_token = resolve_action_coin(&positions.deposits, &lp_token);
+            if lp_token.amount.is_zero() {
+                return Err(ContractError::NoAmount);
+            }
+            sub_coin(&mut positions.deposits, &lp_token)?;
+            let zapper = ZAPPER.load(deps.storage)?;
+            let coins_out = zapper.estimate_withdraw_liquidity(&deps.querier, &lp_token)?;
+            for coin in coins_out.iter() {
+                add_coin(&mut positions.deposits, coin)?;
+            }
+        }
+        Action::RefundAllCoinBalances {
+            ..
+        } => positions.deposits.clear(),
+        Action::FlashBorrow(..) => {
+            return Err(ContractError::SimulationNotSupported("FlashBorrow".to_string()))
+        }
+        Action::FlashRepay {} => {
+            return Err(ContractError::SimulationNotSupported("FlashRepay".to_string()))
+        }
+        Action::ClosePosition {
+            ..
+        } => return Err(ContractError::SimulationNotSupported("ClosePosition".to_string())),
+        // Only the positions leaving the account are simulated, the recipient is not checked
+        Action::TransferToAccount {
+            coins,
+            debt,
+            vault_positions,
+            ..
+        } => {
+            for coin in coins.iter() {
+                let coin = resolve_action_coin(&positions.deposits, coin);
+                if coin.amount.is_zero() {
+                    return Err(ContractError::NoAmount);
+                }
+                sub_coin(&mut positions.deposits, &coin)?;
+            }
+            for coin in debt.iter() {
+                let Some(index) = positions.debts.iter().position(|d| d.denom == coin.denom) else {
+                    return Err(ContractError::NoDebt);
+                };
+                let debt = &mut positions.debts[index];
+                let amount = min(debt.amount, coin.amount.value().unwrap_or(Uint128::MAX));
+                if amount == debt.amount {
+                    positions.debts.remove(index);
+                } else {
+                    debt.shares = debt
+                        .shares
+                        .checked_sub(debt.shares.checked_multiply_ratio(amount, debt.amount)?)?;
+                    debt.amount = debt.amount.checked_sub(amount)?;
+                }
+            }
+            for vault in vault_positions.iter() {
+                let vault = vault.check(deps.api)?;
+                let Some(index) =
+                    positions.vaults.iter().position(|v| v.vault.address == vault.address)
+                else {
+                    return Err(ContractError::InvalidTransfer {
+                        reason: format!(
+                            "{} has no position in vault {}",
+                            positions.account_id, vault.address
+                        ),
+                    });
+                };
+                positions.vaults.remove(index);
+            }
+        }
+        Action::Leverage {
+            collateral_denom,
+            debt_denom,
+            target_ltv,
+            slippage,
+        } => {
+            let amount = leverage_amount(
+                deps,
+                kind,
+                positions,
+                &collateral_denom,
+                &debt_denom,
+                target_ltv,
+                slippage,
+            )?;
+            let coin = Coin::new(amount.u128(), debt_denom);
+            simulate_action(
+                deps,
+                kind,
+                positions,
+                denoms_for_cap_check,
+                Action::Borrow(coin.clone()),
+            )?;
+            simulate_action(
+                deps,
+                kind,
+                positions,
+                denoms_for_cap_check,
+                Action::SwapExactIn {
+                    coin_in: ActionCoin::from(&coin),
+                    denom_out: collateral_denom,
+                    slippage,
+                },
+            )?;
+        }
+        Action::RepayFromCollateral {
+            collateral,
+            debt_denom,
+            ..
+        } => {
+            if !positions.debts.iter().any(|d| d.denom == debt_denom) {
+                return Err(ContractError::NoDebt);
+            }
+            let coin_in = resolve_action_coin(&positions.deposits, &collateral);
+            if coin_in.amount.is_zero() {
+                return Err(ContractError::NoAmount);
+            }
+            sub_coin(&mut positions.deposits, &coin_in)?;
+            denoms_for_cap_check.insert(debt_denom.clone());
+            let swapper = SWAPPER.load(deps.storage)?;
+            let amount_out =
+                swapper.estimate_exact_in_swap(&deps.querier, &coin_in, &debt_denom)?;
+            add_coin(&mut positions.deposits, &Coin::new(amount_out.u128(), debt_denom.clone()))?;
+            simulate_action(
+                deps,
+                kind,
+                positions,
+                denoms_for_cap_check,
+                Action::Repay {
+                    recipient_account_id: None,
+                    coin: ActionCoin {
+                        denom: debt_denom,
+                        amount: ActionAmount::Exact(amount_out),
+                    },
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn debt_shares_for_amount(deps: Deps, coin: &Coin) -> ContractResult<Uint128> {
+    let red_bank = RED_BANK.load(deps.storage)?;
+    let total_debt_amount = red_bank.query_debt(&deps.querier, &coin.denom)?;
+    if total_debt_amount.is_zero() {
+        Ok(coin.amount.checked_mul(DEFAULT_DEBT_SHARES_PER_COIN_BORROWED)?)
+    } else {
+        Ok(TOTAL_DEBT_SHARES
+            .load(deps.storage, &coin.denom)?
+            .checked_multiply_ratio(coin.amount, total_debt_amount)?)
+    }
+}
+
+fn update_vault_position(
+    positions: &mut Positions,
+    vault: &Vault,
+    update: VaultPositionUpdate,
+) -> ContractResult<()> {
+    let index = match positions.vaults.iter().position(|v| v.vault.address == vault.address) {
+        Some(index) => index,
+        None => {
+            positions.vaults.push(VaultPosition {
+                vault: vault.clone(),
+                amount: update.default_amount(),
+            });
+            positions.vaults.len() - 1
+        }
+    };
+    positions.vaults[index].amount.update(update)?;
+    if positions.vaults[index].amount.is_empty() {
+        positions.vaults.remove(index);
+    }
+    Ok(())
+}
+
+fn amount_of(coins: &[Coin], denom: &str) -> Uint128 {
+    coins.iter().find(|c| c.denom == denom).map(|c| c.amount).unwrap_or_default()
+}
+
+fn resolve_action_coin(coins: &[Coin], coin: &ActionCoin) -> Coin {
+    Coin {
+        denom: coin.denom.clone(),
+        amount: match coin.amount {
+            ActionAmount::Exact(a) => a,
+            ActionAmount::AccountBalance => amount_of(coins, &coin.denom),
+        },
+    }
+}
+
+fn add_coin(coins: &mut Vec<Coin>, coin: &Coin) -> ContractResult<()> {
+    if coin.amount.is_zero() {
+        return Ok(());
+    }
+    match coins.iter_mut().find(|c| c.denom == coin.denom) {
+        Some(c) => c.amount = c.amount.checked_add(coin.amount)?,
+        None => coins.push(coin.clone()),
+    }
+    Ok(())
+}
+
+fn sub_coin(coins: &mut Vec<Coin>, coin: &Coin) -> ContractResult<()> {
+    let current = amount_of(coins, &coin.denom);
+    let new_amount = current.checked_sub(coin.amount)?;
+    if new_amount.is_zero() {
+        coins.retain(|c| c.denom != coin.denom);
+    } else if let Some(c) = coins.iter_mut().find(|c| c.denom == coin.denom) {
+        c.amount = new_amount;
+    }
+    Ok(())
+}
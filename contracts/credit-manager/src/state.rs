@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
 use cw_storage_plus::{Item, Map};
 use mars_owner::Owner;
 use mars_rover::{
@@ -7,6 +7,11 @@ use mars_rover::{
         params::Params, red_bank::RedBank, rewards_collector::RewardsCollector, swap::Swapper,
         vault::VaultPositionAmount, zapper::Zapper,
     },
+    msg::{
+        execute::ActionPause,
+        instantiate::{LiquidationAuction, Role},
+        query::{ConfigProposal, Delegate, TriggerOrder},
+    },
     reentrancy_guard::ReentrancyGuard,
 };
 use mars_rover_health_types::AccountKind;
@@ -26,8 +31,24 @@ pub const INCENTIVES: Item<Incentives> = Item::new("incentives");
 // Config
 pub const OWNER: Owner = Owner::new("owner");
 pub const MAX_UNLOCKING_POSITIONS: Item<Uint128> = Item::new("max_unlocking_positions");
-pub const REENTRANCY_GUARD: ReentrancyGuard = ReentrancyGuard::new("reentrancy_guard");
+pub const REENTRANCY_GUARD: ReentrancyGuard =
+    ReentrancyGuard::new("reentrancy_guard", "account_reentrancy_guards");
 pub const MAX_SLIPPAGE: Item<Decimal> = Item::new("max_slippage");
+pub const KEEPER_FEE: Item<Coin> = Item::new("keeper_fee");
+pub const FLASH_LOAN_FEE: Item<Decimal> = Item::new("flash_loan_fee");
+pub const LIQUIDATION_AUCTION: Item<LiquidationAuction> = Item::new("liquidation_auction");
+pub const BAD_DEBT_DUST_THRESHOLD: Item<Uint128> = Item::new("bad_debt_dust_threshold");
+pub const CONFIG_UPDATE_DELAY: Item<u64> = Item::new("config_update_delay"); // Seconds
+
+// Config updates awaiting the config update delay
+pub const CONFIG_PROPOSALS: Map<u64, ConfigProposal> = Map::new("config_proposals"); // Map<ProposalId, ConfigProposal>
+pub const NEXT_CONFIG_PROPOSAL_ID: Item<u64> = Item::new("next_config_proposal_id");
+
+// Actions paused by the emergency owner
+pub const PAUSED_ACTIONS: Item<Vec<ActionPause>> = Item::new("paused_actions");
+
+// Administrative roles granted by the owner
+pub const ROLES: Map<&Addr, Vec<Role>> = Map::new("roles"); // Map<Addr, Roles>
 
 // Positions
 pub const ACCOUNT_KINDS: Map<&str, AccountKind> = Map::new("account_types"); // Map<AccountId, AccountKind>
@@ -37,6 +58,22 @@ pub const TOTAL_DEBT_SHARES: Map<&str, Uint128> = Map::new("total_debt_shares");
 
 pub const VAULT_POSITIONS: Map<(&str, Addr), VaultPositionAmount> = Map::new("vault_positions"); // Map<(AccountId, VaultAddr), VaultPositionAmount>
 
+// Block height at which the account was first recorded as liquidatable, cleared once healthy
+pub const LIQUIDATABLE_SINCE: Map<&str, u64> = Map::new("liquidatable_since"); // Map<AccountId, BlockHeight>
+
+// Debt written off against the rewards-collector account's balances
+pub const BAD_DEBT: Map<(&str, &str), Uint128> = Map::new("bad_debt"); // Map<(AccountId, Denom), Amount>
+
+// Delegated permissions
+pub const DELEGATES: Map<(&str, &Addr), Delegate> = Map::new("delegates"); // Map<(AccountId, DelegateAddr), Delegate>
+
+// Trigger orders
+pub const TRIGGER_ORDERS: Map<(&str, u64), TriggerOrder> = Map::new("trigger_orders"); // Map<(AccountId, TriggerOrderId), TriggerOrder>
+pub const NEXT_TRIGGER_ORDER_ID: Item<u64> = Item::new("next_trigger_order_id");
+
+// Flash loans outstanding within the bundle being executed
+pub const FLASH_LOANS: Map<(&str, &str), Uint128> = Map::new("flash_loans"); // Map<(AccountId, Denom), Amount>
+
 // Temporary state to save variables to be used on reply handling
 pub const VAULT_REQUEST_TEMP_STORAGE: Item<RequestTempStorage> =
     Item::new("vault_request_temp_var");
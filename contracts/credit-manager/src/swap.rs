@@ -1,11 +1,17 @@
-use cosmwasm_std::{Coin, Decimal, DepsMut, Env, Response, Uint128};
+use cosmwasm_std::{
+    to_binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, Response, Uint128, WasmMsg,
+};
 use mars_rover::{
     error::{ContractError, ContractResult},
-    msg::execute::{ActionAmount, ActionCoin, ChangeExpected},
+    msg::{
+        execute::{ActionAmount, ActionCoin, CallbackMsg, ChangeExpected},
+        ExecuteMsg,
+    },
 };
 
 use crate::{
     state::{COIN_BALANCES, SWAPPER},
+    update_coin_balances::query_balance,
     utils::{
         assert_coin_is_whitelisted, assert_slippage, decrement_coin_balance, update_balance_msg,
     },
@@ -58,3 +64,97 @@ pub fn swap_exact_in(
         .add_attribute("coin_in", coin_in_to_trade.to_string())
         .add_attribute("denom_out", denom_out))
 }
+
+pub fn swap_exact_out(
+    mut deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    denom_in: &str,
+    coin_out: &Coin,
+    max_amount_in: &ActionAmount,
+) -> ContractResult<Response> {
+    assert_coin_is_whitelisted(&mut deps, &coin_out.denom)?;
+
+    if denom_in == coin_out.denom {
+        return Err(ContractError::SwapSameDenom(denom_in.to_string()));
+    }
+
+    if coin_out.amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    let balance_in =
+        COIN_BALANCES.may_load(deps.storage, (account_id, denom_in))?.unwrap_or(Uint128::zero());
+    let max_amount_in = match max_amount_in {
+        ActionAmount::Exact(a) => *a,
+        ActionAmount::AccountBalance => balance_in,
+    };
+
+    if max_amount_in.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    // The account must be able to cover the full amount sent to the swapper
+    balance_in.checked_sub(max_amount_in)?;
+
+    // The swapper refunds whatever input was not spent, so the account is only charged for the
+    // net decrease of the input denom. The output denom is credited after the swap.
+    let update_coin_in_balance_msg = update_balance_msg(
+        &deps.querier,
+        &env.contract.address,
+        account_id,
+        denom_in,
+        ChangeExpected::Decrease,
+    )?;
+    // The swapper must deliver at least the requested output
+    let assert_min_received_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::AssertMinReceived {
+            previous_balance: query_balance(&deps.querier, &env.contract.address, &coin_out.denom)?,
+            minimum: coin_out.amount,
+        }))?,
+    });
+    let update_coin_out_balance_msg = update_balance_msg(
+        &deps.querier,
+        &env.contract.address,
+        account_id,
+        &coin_out.denom,
+        ChangeExpected::Increase,
+    )?;
+
+    let swapper = SWAPPER.load(deps.storage)?;
+
+    Ok(Response::new()
+        .add_message(swapper.swap_exact_out_msg(denom_in, coin_out, max_amount_in)?)
+        .add_message(update_coin_in_balance_msg)
+        .add_message(assert_min_received_msg)
+        .add_message(update_coin_out_balance_msg)
+        .add_attribute("action", "swap_exact_out")
+        .add_attribute("account_id", account_id)
+        .add_attribute("denom_in", denom_in)
+        .add_attribute("max_amount_in", max_amount_in)
+        .add_attribute("coin_out", coin_out.to_string()))
+}
+
+pub fn assert_min_received(
+    deps: Deps,
+    env: Env,
+    previous_balance: Coin,
+    minimum: Uint128,
+) -> ContractResult<Response> {
+    let balance = query_balance(&deps.querier, &env.contract.address, &previous_balance.denom)?;
+    let received = balance.amount.saturating_sub(previous_balance.amount);
+
+    if received < minimum {
+        return Err(ContractError::SwapOutputTooLow {
+            denom: previous_balance.denom,
+            received,
+            minimum,
+        });
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "callback/assert_min_received")
+        .add_attribute("coin_received", Coin::new(received.u128(), balance.denom).to_string()))
+}
@@ -0,0 +1,111 @@
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+use cw_paginate::paginate_map;
+use cw_storage_plus::Bound;
+use mars_rover::{
+    error::{ContractError, ContractResult},
+    msg::{instantiate::ConfigUpdates, query::ConfigProposal},
+};
+
+use crate::{
+    roles::assert_can_update_config,
+    state::{CONFIG_PROPOSALS, CONFIG_UPDATE_DELAY, NEXT_CONFIG_PROPOSAL_ID, OWNER},
+    update_config::apply_config_updates,
+};
+
+/// While the config update delay is non-zero, swapping contract addresses or changing the delay
+/// must go through a config proposal so users have time to react
+pub fn assert_not_timelocked(storage: &dyn Storage, updates: &ConfigUpdates) -> ContractResult<()> {
+    let delay = CONFIG_UPDATE_DELAY.may_load(storage)?.unwrap_or_default();
+    if delay == 0 {
+        return Ok(());
+    }
+
+    match updates.timelocked_field() {
+        Some(field) => Err(ContractError::ConfigUpdateTimelocked {
+            field: field.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+pub fn propose_config_update(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    updates: ConfigUpdates,
+) -> ContractResult<Response> {
+    assert_can_update_config(deps.storage, &info.sender, &updates)?;
+
+    let proposal_id = NEXT_CONFIG_PROPOSAL_ID.may_load(deps.storage)?.unwrap_or(1);
+    NEXT_CONFIG_PROPOSAL_ID.save(deps.storage, &(proposal_id + 1))?;
+
+    let delay = CONFIG_UPDATE_DELAY.may_load(deps.storage)?.unwrap_or_default();
+    let executable_at = env.block.time.seconds() + delay;
+
+    CONFIG_PROPOSALS.save(
+        deps.storage,
+        proposal_id,
+        &ConfigProposal {
+            proposal_id,
+            updates,
+            executable_at,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_config_update")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("executable_at", executable_at.to_string()))
+}
+
+/// Applies the proposal on behalf of anyone permitted to update the fields it sets. The emergency
+/// owner can cancel proposals, but not execute them.
+pub fn execute_config_update(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> ContractResult<Response> {
+    let proposal = CONFIG_PROPOSALS.load(deps.storage, proposal_id)?;
+    assert_can_update_config(deps.storage, &info.sender, &proposal.updates)?;
+
+    if env.block.time.seconds() < proposal.executable_at {
+        return Err(ContractError::ConfigProposalNotReady {
+            proposal_id,
+            executable_at: proposal.executable_at,
+        });
+    }
+
+    CONFIG_PROPOSALS.remove(deps.storage, proposal_id);
+
+    let response = Response::new()
+        .add_attribute("action", "execute_config_update")
+        .add_attribute("proposal_id", proposal_id.to_string());
+    apply_config_updates(deps, env, response, proposal.updates)
+}
+
+pub fn cancel_config_update(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> ContractResult<Response> {
+    if !OWNER.is_owner(deps.storage, &info.sender)? {
+        OWNER.assert_emergency_owner(deps.storage, &info.sender)?;
+    }
+
+    CONFIG_PROPOSALS.load(deps.storage, proposal_id)?;
+    CONFIG_PROPOSALS.remove(deps.storage, proposal_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_config_update")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn query_config_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ConfigProposal>> {
+    let start = start_after.map(Bound::exclusive);
+    paginate_map(&CONFIG_PROPOSALS, deps.storage, start, limit, |_, proposal| Ok(proposal))
+}
@@ -0,0 +1,166 @@
+use std::cmp::min;
+
+use cosmwasm_std::{Coin, Deps, DepsMut, Response, Storage, Uint128};
+use mars_rover::{
+    adapters::vault::{
+        UnlockingChange, UpdateType, Vault, VaultPositionAmount, VaultPositionUpdate,
+    },
+    error::{ContractError, ContractResult},
+    msg::execute::ActionCoin,
+};
+
+use crate::{
+    repay::current_debt_for_denom,
+    state::{COIN_BALANCES, DEBT_SHARES, VAULT_POSITIONS},
+    utils::{decrement_coin_balance, increment_coin_balance, query_nft_token_owner},
+    vault::{assert_under_max_unlocking_limit, update_vault_position},
+};
+
+/// Moves coins, debt and whole vault positions to another account of the same owner. Debt is moved
+/// as debt shares, so the total debt shares of each denom are unchanged.
+pub fn transfer_to_account(
+    deps: DepsMut,
+    account_id: &str,
+    recipient_account_id: &str,
+    coins: &[ActionCoin],
+    debt: &[ActionCoin],
+    vault_positions: &[Vault],
+) -> ContractResult<Response> {
+    assert_same_owner(deps.as_ref(), account_id, recipient_account_id)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "transfer_to_account")
+        .add_attribute("account_id", account_id)
+        .add_attribute("recipient_account_id", recipient_account_id);
+
+    for coin in coins {
+        let amount = match coin.amount.value() {
+            Some(amount) => amount,
+            None => {
+                COIN_BALANCES.may_load(deps.storage, (account_id, &coin.denom))?.unwrap_or_default()
+            }
+        };
+        if amount.is_zero() {
+            return Err(ContractError::NoAmount);
+        }
+        let coin = Coin {
+            denom: coin.denom.clone(),
+            amount,
+        };
+        decrement_coin_balance(deps.storage, account_id, &coin)?;
+        increment_coin_balance(deps.storage, recipient_account_id, &coin)?;
+        response = response.add_attribute("coin_transferred", coin.to_string());
+    }
+
+    for coin in debt {
+        let (debt_amount, debt_shares) =
+            current_debt_for_denom(deps.as_ref(), account_id, &coin.denom)?;
+        let amount = min(debt_amount, coin.amount.value().unwrap_or(Uint128::MAX));
+        if amount.is_zero() {
+            return Err(ContractError::NoAmount);
+        }
+
+        let shares = if amount == debt_amount {
+            DEBT_SHARES.remove(deps.storage, (account_id, &coin.denom));
+            debt_shares
+        } else {
+            let shares = debt_shares.checked_multiply_ratio(amount, debt_amount)?;
+            DEBT_SHARES.save(
+                deps.storage,
+                (account_id, &coin.denom),
+                &debt_shares.checked_sub(shares)?,
+            )?;
+            shares
+        };
+        DEBT_SHARES.update(
+            deps.storage,
+            (recipient_account_id, &coin.denom),
+            |existing| -> ContractResult<_> {
+                Ok(existing.unwrap_or_default().checked_add(shares)?)
+            },
+        )?;
+
+        response = response
+            .add_attribute("debt_transferred", format!("{}{}", amount, coin.denom))
+            .add_attribute("debt_shares_transferred", shares);
+    }
+
+    for vault in vault_positions {
+        let Some(amount) =
+            VAULT_POSITIONS.may_load(deps.storage, (account_id, vault.address.clone()))?
+        else {
+            return Err(ContractError::InvalidTransfer {
+                reason: format!("{} has no position in vault {}", account_id, vault.address),
+            });
+        };
+        transfer_vault_position(deps.storage, account_id, recipient_account_id, vault, amount)?;
+        response = response.add_attribute("vault_position_transferred", vault.address.to_string());
+    }
+
+    Ok(response)
+}
+
+fn assert_same_owner(
+    deps: Deps,
+    account_id: &str,
+    recipient_account_id: &str,
+) -> ContractResult<()> {
+    if account_id == recipient_account_id {
+        return Err(ContractError::InvalidTransfer {
+            reason: "recipient must be another account".to_string(),
+        });
+    }
+    if query_nft_token_owner(deps, account_id)?
+        != query_nft_token_owner(deps, recipient_account_id)?
+    {
+        return Err(ContractError::InvalidTransfer {
+            reason: format!("{recipient_account_id} is not owned by the owner of {account_id}"),
+        });
+    }
+    Ok(())
+}
+
+/// Vault coins are held by the credit manager on behalf of all accounts, only the accounting moves.
+/// Unlocking entries keep their vault-assigned ids and are subject to the max unlocking limit.
+pub fn transfer_vault_position(
+    storage: &mut dyn Storage,
+    account_id: &str,
+    recipient_account_id: &str,
+    vault: &Vault,
+    amount: VaultPositionAmount,
+) -> ContractResult<()> {
+    let (decrement, increment) = match &amount {
+        VaultPositionAmount::Unlocked(a) => (
+            VaultPositionUpdate::Unlocked(UpdateType::Decrement(a.total())),
+            VaultPositionUpdate::Unlocked(UpdateType::Increment(a.total())),
+        ),
+        VaultPositionAmount::Locking(a) => (
+            VaultPositionUpdate::Locked(UpdateType::Decrement(a.locked.total())),
+            VaultPositionUpdate::Locked(UpdateType::Increment(a.locked.total())),
+        ),
+    };
+    if !(amount.unlocked().is_zero() && amount.locked().is_zero()) {
+        update_vault_position(storage, account_id, &vault.address, decrement)?;
+        update_vault_position(storage, recipient_account_id, &vault.address, increment)?;
+    }
+
+    for unlocking in amount.unlocking().positions() {
+        assert_under_max_unlocking_limit(storage, recipient_account_id, vault)?;
+        update_vault_position(
+            storage,
+            account_id,
+            &vault.address,
+            VaultPositionUpdate::Unlocking(UnlockingChange::Decrement {
+                id: unlocking.id,
+                amount: unlocking.coin.amount,
+            }),
+        )?;
+        update_vault_position(
+            storage,
+            recipient_account_id,
+            &vault.address,
+            VaultPositionUpdate::Unlocking(UnlockingChange::Add(unlocking)),
+        )?;
+    }
+    Ok(())
+}
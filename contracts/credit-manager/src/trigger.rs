@@ -0,0 +1,150 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response};
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    error::{ContractError, ContractResult},
+    msg::{
+        execute::{Action, CallbackMsg, Recipient, TriggerCondition},
+        query::TriggerOrder,
+    },
+};
+
+use crate::{
+    execute::process_actions,
+    state::{HEALTH_CONTRACT, KEEPER_FEE, NEXT_TRIGGER_ORDER_ID, ORACLE, TRIGGER_ORDERS},
+    utils::{
+        assert_is_token_owner, get_account_kind, query_nft_token_owner, query_nft_transfer_nonce,
+    },
+};
+
+pub fn create_trigger_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    account_id: &str,
+    actions: Vec<Action>,
+    condition: TriggerCondition,
+) -> ContractResult<Response> {
+    assert_is_token_owner(&deps, &info.sender, account_id)?;
+
+    // Keepers do not send funds on behalf of the account
+    if actions.iter().any(|action| matches!(action, Action::Deposit(..))) {
+        return Err(ContractError::TriggerActionNotAllowed("Deposit".to_string()));
+    }
+
+    let transfer_nonce = query_nft_transfer_nonce(deps.as_ref(), account_id)?;
+
+    let trigger_order_id = NEXT_TRIGGER_ORDER_ID.may_load(deps.storage)?.unwrap_or(1);
+    NEXT_TRIGGER_ORDER_ID.save(deps.storage, &(trigger_order_id + 1))?;
+
+    TRIGGER_ORDERS.save(
+        deps.storage,
+        (account_id, trigger_order_id),
+        &TriggerOrder {
+            account_id: account_id.to_string(),
+            trigger_order_id,
+            owner: info.sender.to_string(),
+            transfer_nonce,
+            actions,
+            condition,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_trigger_order")
+        .add_attribute("account_id", account_id)
+        .add_attribute("trigger_order_id", trigger_order_id.to_string()))
+}
+
+pub fn cancel_trigger_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    account_id: &str,
+    trigger_order_id: u64,
+) -> ContractResult<Response> {
+    assert_is_token_owner(&deps, &info.sender, account_id)?;
+
+    TRIGGER_ORDERS.load(deps.storage, (account_id, trigger_order_id))?;
+    TRIGGER_ORDERS.remove(deps.storage, (account_id, trigger_order_id));
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_trigger_order")
+        .add_attribute("account_id", account_id)
+        .add_attribute("trigger_order_id", trigger_order_id.to_string()))
+}
+
+pub fn execute_trigger_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: &str,
+    trigger_order_id: u64,
+) -> ContractResult<Response> {
+    let order = TRIGGER_ORDERS.load(deps.storage, (account_id, trigger_order_id))?;
+    TRIGGER_ORDERS.remove(deps.storage, (account_id, trigger_order_id));
+
+    // Orders were authorized by the owner at the time of creation. Once the account has been
+    // transferred they are void, even if it has come back to the same owner since.
+    let owner = query_nft_token_owner(deps.as_ref(), account_id)?;
+    let transfer_nonce = query_nft_transfer_nonce(deps.as_ref(), account_id)?;
+    if owner != order.owner || transfer_nonce != order.transfer_nonce {
+        return Err(ContractError::TriggerOrderInvalidated(trigger_order_id));
+    }
+
+    if !is_condition_met(deps.as_ref(), account_id, &order.condition)? {
+        return Err(ContractError::TriggerConditionNotMet(trigger_order_id));
+    }
+
+    // The keeper fee is withdrawn from the account before health is asserted
+    let fee_callbacks = match KEEPER_FEE.may_load(deps.storage)? {
+        Some(fee) if !fee.amount.is_zero() => vec![CallbackMsg::Withdraw {
+            account_id: account_id.to_string(),
+            coin: (&fee).into(),
+            recipient: Recipient::Wallet(info.sender.clone()),
+        }],
+        _ => vec![],
+    };
+
+    let response = process_actions(
+        deps,
+        env,
+        account_id,
+        &Addr::unchecked(order.owner),
+        info.funds,
+        order.actions,
+        fee_callbacks,
+    )?;
+
+    Ok(response
+        .add_attribute("trigger_order_id", trigger_order_id.to_string())
+        .add_attribute("keeper", info.sender))
+}
+
+fn is_condition_met(
+    deps: Deps,
+    account_id: &str,
+    condition: &TriggerCondition,
+) -> ContractResult<bool> {
+    match condition {
+        TriggerCondition::OraclePrice {
+            denom,
+            comparison,
+            price,
+        } => {
+            let oracle = ORACLE.load(deps.storage)?;
+            let current_price =
+                oracle.query_price(&deps.querier, denom, ActionKind::Default)?.price;
+            Ok(comparison.is_met(current_price, *price))
+        }
+        TriggerCondition::HealthFactor {
+            threshold,
+        } => {
+            let kind = get_account_kind(deps.storage, account_id)?;
+            let health = HEALTH_CONTRACT.load(deps.storage)?.query_health_values(
+                &deps.querier,
+                account_id,
+                kind,
+                ActionKind::Default,
+            )?;
+            Ok(health.max_ltv_health_factor.map_or(false, |hf| hf < *threshold))
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use cosmwasm_std::{
-    Addr, BalanceResponse, BankQuery, Coin, Decimal, DepsMut, Env, QuerierWrapper, QueryRequest,
-    Response, StdResult,
+    Addr, BalanceResponse, BankMsg, BankQuery, Coin, Decimal, DepsMut, Env, QuerierWrapper,
+    QueryRequest, Response, StdResult,
 };
 use mars_rover::{
     error::{ContractError::BalanceChange, ContractResult},
@@ -75,18 +75,54 @@ fn change_response(
 }
 
 pub fn update_coin_balance_after_vault_liquidation(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     account_id: &str,
     prev: &Coin,
     protocol_fee: Decimal,
 ) -> ContractResult<Response> {
+    let coin_to_increment = collect_vault_liquidation_fee(deps.branch(), &env, prev, protocol_fee)?;
+    increment_coin_balance(deps.storage, account_id, &coin_to_increment)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_coin_balance_after_vault_liquidation")
+        .add_attribute("account_id", account_id)
+        .add_attribute("coin_incremented", coin_to_increment.to_string()))
+}
+
+pub fn send_coin_after_vault_liquidation(
+    deps: DepsMut,
+    env: Env,
+    recipient: &Addr,
+    prev: &Coin,
+    protocol_fee: Decimal,
+) -> ContractResult<Response> {
+    let coin_to_send = collect_vault_liquidation_fee(deps, &env, prev, protocol_fee)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![coin_to_send.clone()],
+        })
+        .add_attribute("action", "send_coin_after_vault_liquidation")
+        .add_attribute("recipient", recipient)
+        .add_attribute("coin_sent", coin_to_send.to_string()))
+}
+
+/// Transfers the protocol fee portion of the coins withdrawn from the vault to the
+/// rewards-collector account. Returns the remainder, owed to the liquidator.
+fn collect_vault_liquidation_fee(
+    deps: DepsMut,
+    env: &Env,
+    prev: &Coin,
+    protocol_fee: Decimal,
+) -> ContractResult<Coin> {
     let curr = query_balance(&deps.querier, &env.contract.address, &prev.denom)?;
-    let mut amount_to_increment = curr.amount.checked_sub(prev.amount)?;
+    let mut amount = curr.amount.checked_sub(prev.amount)?;
 
     if !protocol_fee.is_zero() {
-        let protocol_fee_amt = amount_to_increment.checked_mul_ceil(protocol_fee)?;
-        amount_to_increment = amount_to_increment.checked_sub(protocol_fee_amt)?;
+        let protocol_fee_amt = amount.checked_mul_ceil(protocol_fee)?;
+        amount = amount.checked_sub(protocol_fee_amt)?;
 
         let rewards_collector_account = REWARDS_COLLECTOR.load(deps.storage)?.account_id;
         increment_coin_balance(
@@ -99,14 +135,8 @@ pub fn update_coin_balance_after_vault_liquidation(
         )?;
     };
 
-    let coin_to_increment = Coin {
+    Ok(Coin {
         denom: curr.denom,
-        amount: amount_to_increment,
-    };
-    increment_coin_balance(deps.storage, account_id, &coin_to_increment)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "update_coin_balance_after_vault_liquidation")
-        .add_attribute("account_id", account_id)
-        .add_attribute("coin_incremented", coin_to_increment.to_string()))
+        amount,
+    })
 }
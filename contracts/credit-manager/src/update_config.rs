@@ -3,18 +3,22 @@ use cw721_base::Action;
 use mars_account_nft_types::{msg::ExecuteMsg as NftExecuteMsg, nft_config::NftConfigUpdates};
 use mars_owner::OwnerUpdate;
 use mars_rover::{
-    adapters::rewards_collector::RewardsCollector, error::ContractResult,
-    msg::instantiate::ConfigUpdates,
+    adapters::rewards_collector::RewardsCollector,
+    error::ContractResult,
+    msg::instantiate::{ConfigUpdates, LiquidationAuctionUpdate},
 };
 use mars_rover_health_types::AccountKind;
 
 use crate::{
     execute::create_credit_account,
+    roles::assert_can_update_config,
     state::{
-        ACCOUNT_NFT, HEALTH_CONTRACT, INCENTIVES, MAX_SLIPPAGE, MAX_UNLOCKING_POSITIONS, ORACLE,
+        ACCOUNT_NFT, BAD_DEBT_DUST_THRESHOLD, CONFIG_UPDATE_DELAY, FLASH_LOAN_FEE, HEALTH_CONTRACT,
+        INCENTIVES, KEEPER_FEE, LIQUIDATION_AUCTION, MAX_SLIPPAGE, MAX_UNLOCKING_POSITIONS, ORACLE,
         OWNER, RED_BANK, REWARDS_COLLECTOR, SWAPPER, ZAPPER,
     },
-    utils::assert_max_slippage,
+    timelock::assert_not_timelocked,
+    utils::{assert_flash_loan_fee, assert_max_slippage},
 };
 
 pub fn update_config(
@@ -23,10 +27,20 @@ pub fn update_config(
     info: MessageInfo,
     updates: ConfigUpdates,
 ) -> ContractResult<Response> {
-    OWNER.assert_owner(deps.storage, &info.sender)?;
+    assert_can_update_config(deps.storage, &info.sender, &updates)?;
+    assert_not_timelocked(deps.storage, &updates)?;
 
-    let mut response = Response::new().add_attribute("action", "update_config");
+    let response = Response::new().add_attribute("action", "update_config");
+    apply_config_updates(deps, env, response, updates)
+}
 
+/// Saves the fields set in `updates`, adding an attribute for each to `response`
+pub fn apply_config_updates(
+    deps: DepsMut,
+    env: Env,
+    mut response: Response,
+    updates: ConfigUpdates,
+) -> ContractResult<Response> {
     if let Some(unchecked) = updates.account_nft {
         let account_nft = unchecked.check(deps.api)?;
         ACCOUNT_NFT.save(deps.storage, &account_nft)?;
@@ -116,6 +130,49 @@ pub fn update_config(
             .add_attribute("value", next_id);
     }
 
+    if let Some(fee) = updates.keeper_fee {
+        KEEPER_FEE.save(deps.storage, &fee)?;
+        response =
+            response.add_attribute("key", "keeper_fee").add_attribute("value", fee.to_string());
+    }
+
+    if let Some(fee) = updates.flash_loan_fee {
+        assert_flash_loan_fee(fee)?;
+        FLASH_LOAN_FEE.save(deps.storage, &fee)?;
+        response =
+            response.add_attribute("key", "flash_loan_fee").add_attribute("value", fee.to_string());
+    }
+
+    match updates.liquidation_auction {
+        Some(LiquidationAuctionUpdate::Set(auction)) => {
+            LIQUIDATION_AUCTION.save(deps.storage, &auction)?;
+            response = response
+                .add_attribute("key", "liquidation_auction_blocks_to_max_bonus")
+                .add_attribute("value", auction.blocks_to_max_bonus.to_string());
+        }
+        Some(LiquidationAuctionUpdate::Disable) => {
+            LIQUIDATION_AUCTION.remove(deps.storage);
+            response = response
+                .add_attribute("key", "liquidation_auction")
+                .add_attribute("value", "disabled");
+        }
+        None => {}
+    }
+
+    if let Some(threshold) = updates.bad_debt_dust_threshold {
+        BAD_DEBT_DUST_THRESHOLD.save(deps.storage, &threshold)?;
+        response = response
+            .add_attribute("key", "bad_debt_dust_threshold")
+            .add_attribute("value", threshold.to_string());
+    }
+
+    if let Some(delay) = updates.config_update_delay {
+        CONFIG_UPDATE_DELAY.save(deps.storage, &delay)?;
+        response = response
+            .add_attribute("key", "config_update_delay")
+            .add_attribute("value", delay.to_string());
+    }
+
     Ok(response)
 }
 
@@ -16,6 +16,7 @@ use mars_rover::{
 use mars_rover_health_types::AccountKind;
 
 use crate::{
+    liquidate::Liquidator,
     state::{
         ACCOUNT_KINDS, ACCOUNT_NFT, COIN_BALANCES, MAX_SLIPPAGE, PARAMS, RED_BANK,
         TOTAL_DEBT_SHARES,
@@ -43,6 +44,15 @@ pub fn assert_max_slippage(max_slippage: Decimal) -> ContractResult<()> {
     Ok(())
 }
 
+pub fn assert_flash_loan_fee(fee: Decimal) -> ContractResult<()> {
+    if fee >= Decimal::one() {
+        return Err(ContractError::InvalidConfig {
+            reason: "Flash loan fee must be less than 1".to_string(),
+        });
+    }
+    Ok(())
+}
+
 pub fn assert_slippage(storage: &dyn Storage, slippage: Decimal) -> ContractResult<()> {
     let max_slippage = MAX_SLIPPAGE.load(storage)?;
     if slippage > max_slippage {
@@ -66,6 +76,12 @@ pub fn query_nft_token_owner(deps: Deps, account_id: &str) -> ContractResult<Str
     Ok(res.owner)
 }
 
+/// Number of times the account NFT has been transferred
+pub fn query_nft_transfer_nonce(deps: Deps, account_id: &str) -> ContractResult<u64> {
+    let account_nft = ACCOUNT_NFT.load(deps.storage)?;
+    Ok(account_nft.query_transfer_nonce(&deps.querier, account_id)?)
+}
+
 pub fn assert_coin_is_whitelisted(deps: &mut DepsMut, denom: &str) -> ContractResult<()> {
     let params = PARAMS.load(deps.storage)?;
     match params.query_asset_params(&deps.querier, denom) {
@@ -148,21 +164,30 @@ pub fn update_balances_msgs(
 pub fn update_balance_after_vault_liquidation_msg(
     querier: &QuerierWrapper,
     credit_manager_addr: &Addr,
-    account_id: &str,
+    liquidator: &Liquidator,
     denom: &str,
     protocol_fee: Decimal,
 ) -> StdResult<CosmosMsg> {
     let previous_balance = query_balance(querier, credit_manager_addr, denom)?;
+    let callback = match liquidator {
+        Liquidator::Account(account_id) => CallbackMsg::UpdateCoinBalanceAfterVaultLiquidation {
+            account_id: account_id.to_string(),
+            previous_balance,
+            protocol_fee,
+        },
+        Liquidator::Wallet {
+            addr,
+            ..
+        } => CallbackMsg::SendCoinAfterVaultLiquidation {
+            recipient: addr.clone(),
+            previous_balance,
+            protocol_fee,
+        },
+    };
     Ok(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: credit_manager_addr.to_string(),
         funds: vec![],
-        msg: to_binary(&ExecuteMsg::Callback(
-            CallbackMsg::UpdateCoinBalanceAfterVaultLiquidation {
-                account_id: account_id.to_string(),
-                previous_balance,
-                protocol_fee,
-            },
-        ))?,
+        msg: to_binary(&ExecuteMsg::Callback(callback))?,
     }))
 }
 
@@ -1,6 +1,6 @@
 use std::cmp::min;
 
-use cosmwasm_std::{Coin, Decimal, DepsMut, Env, Response, Uint128};
+use cosmwasm_std::{Coin, Decimal, Deps, DepsMut, Env, Response, Storage, Uint128};
 use cw_vault_standard::VaultInfoResponse;
 use mars_rover::{
     adapters::vault::{
@@ -11,277 +11,308 @@ use mars_rover::{
 };
 
 use crate::{
-    liquidate::calculate_liquidation, liquidate_deposit::repay_debt, state::VAULT_POSITIONS,
-    utils::update_balance_after_vault_liquidation_msg, vault::update_vault_position,
+    liquidate::{
+        calculate_liquidation_for_state, is_liquidation_profitable, liquidation_amounts_for_state,
+        liquidation_state, preview_liquidation, LiquidatedBucket, LiquidationState, Liquidator,
+    },
+    liquidate_deposit::repay_debt,
+    state::VAULT_POSITIONS,
+    utils::update_balance_after_vault_liquidation_msg,
+    vault::update_vault_position,
 };
 
+/// The part of the liquidatee's vault position requested to be liquidated
+pub enum VaultBucket {
+    /// Amount of vault coins
+    Unlocked(Uint128),
+    /// Amount of vault coins
+    Locked(Uint128),
+    Unlocking(UnlockingPositions),
+}
+
+impl VaultBucket {
+    fn action(&self) -> &str {
+        match self {
+            VaultBucket::Unlocked(_) => "liquidate_vault/unlocked",
+            VaultBucket::Locked(_) => "liquidate_vault/locked",
+            VaultBucket::Unlocking(_) => "liquidate_vault/unlocking",
+        }
+    }
+}
+
+pub fn load_vault_bucket(
+    storage: &dyn Storage,
+    liquidatee_account_id: &str,
+    request_vault: &Vault,
+    position_type: VaultPositionType,
+) -> ContractResult<VaultBucket> {
+    let liquidatee_position =
+        VAULT_POSITIONS.load(storage, (liquidatee_account_id, request_vault.address.clone()))?;
+
+    match (&liquidatee_position, position_type) {
+        (VaultPositionAmount::Unlocked(a), VaultPositionType::UNLOCKED) => {
+            Ok(VaultBucket::Unlocked(a.total()))
+        }
+        (VaultPositionAmount::Locking(a), VaultPositionType::LOCKED) => {
+            Ok(VaultBucket::Locked(a.locked.total()))
+        }
+        (VaultPositionAmount::Locking(_), VaultPositionType::UNLOCKING) => {
+            Ok(VaultBucket::Unlocking(liquidatee_position.unlocking()))
+        }
+        _ => Err(ContractError::MismatchedVaultType),
+    }
+}
+
 pub fn liquidate_vault(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
-    liquidator_account_id: &str,
+    liquidator: &Liquidator,
     liquidatee_account_id: &str,
     debt_coin: Coin,
     request_vault: Vault,
     position_type: VaultPositionType,
 ) -> ContractResult<Response> {
-    let liquidatee_position = VAULT_POSITIONS
-        .load(deps.storage, (liquidatee_account_id, request_vault.address.clone()))?;
+    let bucket =
+        load_vault_bucket(deps.storage, liquidatee_account_id, &request_vault, position_type)?;
+    let action = bucket.action().to_string();
+    let state = liquidation_state(deps.as_ref(), &env, liquidatee_account_id, &debt_coin.denom)?;
+
+    let liquidated = liquidate_vault_bucket(
+        deps.branch(),
+        &env,
+        liquidator,
+        &state,
+        &debt_coin,
+        &request_vault,
+        bucket,
+    )?;
 
-    match liquidatee_position {
-        VaultPositionAmount::Unlocked(a) => match position_type {
-            VaultPositionType::UNLOCKED => liquidate_unlocked(
-                deps,
-                env,
-                liquidator_account_id,
-                liquidatee_account_id,
+    let repay_msgs =
+        repay_debt(deps.storage, &env, liquidator, liquidatee_account_id, &liquidated.debt)?;
+
+    Ok(liquidated.into_response(&action, liquidator, liquidatee_account_id, repay_msgs))
+}
+
+/// Withdraws the requested part of the liquidatee's vault position. Once withdrawn, the
+/// underlying coins are transferred to the liquidator and rewards-collector.
+/// The debt is not repaid here, see `repay_debt`.
+pub fn liquidate_vault_bucket(
+    deps: DepsMut,
+    env: &Env,
+    liquidator: &Liquidator,
+    state: &LiquidationState,
+    debt_coin: &Coin,
+    request_vault: &Vault,
+    bucket: VaultBucket,
+) -> ContractResult<LiquidatedBucket> {
+    let liquidatee_account_id = state.liquidatee_account_id.as_str();
+    let vault_info = request_vault.query_info(&deps.querier)?;
+
+    let (debt, liquidator_request, liquidatee_request, mut msgs) = match bucket {
+        VaultBucket::Unlocked(amount) => {
+            let (debt, liquidator_request, liquidatee_request) = calculate_vault_liquidation(
+                deps.as_ref(),
+                state,
                 debt_coin,
                 request_vault,
-                a.total(),
-            ),
-            _ => Err(ContractError::MismatchedVaultType),
-        },
-        VaultPositionAmount::Locking(ref a) => match position_type {
-            VaultPositionType::LOCKED => liquidate_locked(
-                deps,
-                env,
-                liquidator_account_id,
+                amount,
+                &vault_info,
+            )?;
+
+            update_vault_position(
+                deps.storage,
                 liquidatee_account_id,
+                &request_vault.address,
+                VaultPositionUpdate::Unlocked(UpdateType::Decrement(liquidatee_request.amount)),
+            )?;
+
+            let vault_withdraw_msg =
+                request_vault.withdraw_msg(&deps.querier, liquidatee_request.amount)?;
+
+            (debt, liquidator_request, liquidatee_request, vec![vault_withdraw_msg])
+        }
+        VaultBucket::Locked(amount) => {
+            let (debt, liquidator_request, liquidatee_request) = calculate_vault_liquidation(
+                deps.as_ref(),
+                state,
                 debt_coin,
                 request_vault,
-                a.locked.total(),
-            ),
-            VaultPositionType::UNLOCKING => liquidate_unlocking(
-                deps,
-                env,
-                liquidator_account_id,
+                amount,
+                &vault_info,
+            )?;
+
+            update_vault_position(
+                deps.storage,
                 liquidatee_account_id,
+                &request_vault.address,
+                VaultPositionUpdate::Locked(UpdateType::Decrement(liquidatee_request.amount)),
+            )?;
+
+            let vault_withdraw_msg = request_vault
+                .force_withdraw_locked_msg(&deps.querier, liquidatee_request.amount)?;
+
+            (debt, liquidator_request, liquidatee_request, vec![vault_withdraw_msg])
+        }
+        VaultBucket::Unlocking(unlocking_positions) => {
+            // Unlocking positions are already denominated in the underlying coin
+            let (debt, liquidator_request, liquidatee_request) = calculate_liquidation_for_state(
+                deps.as_ref(),
+                state,
                 debt_coin,
-                request_vault,
-                liquidatee_position.unlocking(),
-            ),
-            _ => Err(ContractError::MismatchedVaultType),
-        },
-    }
-}
+                &vault_info.base_token,
+                unlocking_positions.total(),
+            )?;
 
-fn liquidate_unlocked(
-    deps: DepsMut,
-    env: Env,
-    liquidator_account_id: &str,
-    liquidatee_account_id: &str,
-    debt_coin: Coin,
-    request_vault: Vault,
-    amount: Uint128,
-) -> ContractResult<Response> {
-    let vault_info = request_vault.query_info(&deps.querier)?;
+            let mut total_to_liquidate = liquidatee_request.amount;
+            let mut vault_withdraw_msgs = vec![];
 
-    let (debt, liquidator_request, liquidatee_request) = calculate_vault_liquidation(
-        &deps,
-        liquidatee_account_id,
-        &debt_coin,
-        &request_vault,
-        amount,
-        &vault_info,
-    )?;
+            for u in unlocking_positions.positions() {
+                let amount = min(u.coin.amount, total_to_liquidate);
 
-    let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
+                if amount.is_zero() {
+                    break;
+                }
 
-    update_vault_position(
-        deps.storage,
-        liquidatee_account_id,
-        &request_vault.address,
-        VaultPositionUpdate::Unlocked(UpdateType::Decrement(liquidatee_request.amount)),
-    )?;
+                update_vault_position(
+                    deps.storage,
+                    liquidatee_account_id,
+                    &request_vault.address,
+                    VaultPositionUpdate::Unlocking(UnlockingChange::Decrement {
+                        id: u.id,
+                        amount,
+                    }),
+                )?;
+
+                let msg = request_vault.force_withdraw_unlocking_msg(u.id, Some(amount))?;
+                vault_withdraw_msgs.push(msg);
 
-    let vault_withdraw_msg =
-        request_vault.withdraw_msg(&deps.querier, liquidatee_request.amount)?;
+                total_to_liquidate = total_to_liquidate.checked_sub(amount)?;
+            }
+
+            (debt, liquidator_request, liquidatee_request, vault_withdraw_msgs)
+        }
+    };
 
     let protocol_fee = liquidatee_request.amount.checked_sub(liquidator_request.amount)?;
     let protocol_fee_percentage =
         Decimal::checked_from_ratio(protocol_fee, liquidatee_request.amount)?;
 
-    let update_coin_balance_msg = update_balance_after_vault_liquidation_msg(
+    msgs.push(update_balance_after_vault_liquidation_msg(
         &deps.querier,
         &env.contract.address,
-        liquidator_account_id,
+        liquidator,
         &vault_info.base_token,
         protocol_fee_percentage,
-    )?;
-
-    Ok(Response::new()
-        .add_message(repay_msg)
-        .add_message(vault_withdraw_msg)
-        .add_message(update_coin_balance_msg)
-        .add_attribute("action", "liquidate_vault/unlocked")
-        .add_attribute("account_id", liquidator_account_id)
-        .add_attribute("liquidatee_account_id", liquidatee_account_id)
-        .add_attribute("coin_debt_repaid", debt.to_string())
-        .add_attribute("coin_liquidated", liquidatee_request.to_string())
-        .add_attribute(
-            "protocol_fee_coin",
-            Coin::new(protocol_fee.u128(), liquidatee_request.denom).to_string(),
-        ))
+    )?);
+
+    Ok(LiquidatedBucket {
+        debt,
+        protocol_fee: Coin::new(protocol_fee.u128(), liquidatee_request.denom.clone()),
+        liquidatee_request,
+        msgs,
+    })
 }
 
 /// Converts vault coins to their underlying value. This allows for pricing and liquidation
 /// values to be determined. Afterward, the final amount is converted back into vault coins.
 fn calculate_vault_liquidation(
-    deps: &DepsMut,
-    liquidatee_account_id: &str,
+    deps: Deps,
+    state: &LiquidationState,
     debt_coin: &Coin,
     request_vault: &Vault,
     amount: Uint128,
     vault_info: &VaultInfoResponse,
 ) -> ContractResult<(Coin, Coin, Coin)> {
     let total_underlying = request_vault.query_preview_redeem(&deps.querier, amount)?;
-    let (debt, mut liquidator_request, mut liquidatee_request) = calculate_liquidation(
+    let (debt, liquidator_request, liquidatee_request) = calculate_liquidation_for_state(
         deps,
-        liquidatee_account_id,
+        state,
         debt_coin,
         &vault_info.base_token,
         total_underlying,
     )?;
-    liquidatee_request.denom = vault_info.vault_token.clone();
-    liquidatee_request.amount =
-        amount.checked_multiply_ratio(liquidatee_request.amount, total_underlying)?;
-    liquidator_request.denom = vault_info.vault_token.clone();
-    liquidator_request.amount =
-        amount.checked_multiply_ratio(liquidator_request.amount, total_underlying)?;
-    Ok((debt, liquidator_request, liquidatee_request))
-}
-
-fn liquidate_unlocking(
-    deps: DepsMut,
-    env: Env,
-    liquidator_account_id: &str,
-    liquidatee_account_id: &str,
-    debt_coin: Coin,
-    request_vault: Vault,
-    unlocking_positions: UnlockingPositions,
-) -> ContractResult<Response> {
-    let vault_info = request_vault.query_info(&deps.querier)?;
-
-    let (debt, liquidator_request, liquidatee_request) = calculate_liquidation(
-        &deps,
-        liquidatee_account_id,
-        &debt_coin,
-        &vault_info.base_token,
-        unlocking_positions.total(),
-    )?;
-
-    let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
-
-    let mut total_to_liquidate = liquidatee_request.amount;
-    let mut vault_withdraw_msgs = vec![];
-
-    for u in unlocking_positions.positions() {
-        let amount = min(u.coin.amount, total_to_liquidate);
-
-        if amount.is_zero() {
-            break;
-        }
-
-        update_vault_position(
-            deps.storage,
-            liquidatee_account_id,
-            &request_vault.address,
-            VaultPositionUpdate::Unlocking(UnlockingChange::Decrement {
-                id: u.id,
-                amount,
-            }),
-        )?;
-
-        let msg = request_vault.force_withdraw_unlocking_msg(u.id, Some(amount))?;
-        vault_withdraw_msgs.push(msg);
-
-        total_to_liquidate = total_to_liquidate.checked_sub(amount)?;
-    }
-
-    let protocol_fee = liquidatee_request.amount.checked_sub(liquidator_request.amount)?;
-    let protocol_fee_percentage =
-        Decimal::checked_from_ratio(protocol_fee, liquidatee_request.amount)?;
-
-    let update_coin_balance_msg = update_balance_after_vault_liquidation_msg(
-        &deps.querier,
-        &env.contract.address,
-        liquidator_account_id,
-        &vault_info.base_token,
-        protocol_fee_percentage,
-    )?;
-
-    Ok(Response::new()
-        .add_message(repay_msg)
-        .add_messages(vault_withdraw_msgs)
-        .add_message(update_coin_balance_msg)
-        .add_attribute("action", "liquidate_vault/unlocking")
-        .add_attribute("account_id", liquidator_account_id)
-        .add_attribute("liquidatee_account_id", liquidatee_account_id)
-        .add_attribute("coin_debt_repaid", debt.to_string())
-        .add_attribute("coin_liquidated", liquidatee_request.to_string())
-        .add_attribute(
-            "protocol_fee_coin",
-            Coin::new(protocol_fee.u128(), liquidatee_request.denom).to_string(),
-        ))
+    Ok((
+        debt,
+        into_vault_coin(liquidator_request, amount, total_underlying, vault_info)?,
+        into_vault_coin(liquidatee_request, amount, total_underlying, vault_info)?,
+    ))
 }
 
-fn liquidate_locked(
-    deps: DepsMut,
-    env: Env,
-    liquidator_account_id: &str,
-    liquidatee_account_id: &str,
-    debt_coin: Coin,
-    request_vault: Vault,
+/// Converts an amount of underlying coins back into vault coins
+fn into_vault_coin(
+    underlying: Coin,
     amount: Uint128,
-) -> ContractResult<Response> {
-    let vault_info = request_vault.query_info(&deps.querier)?;
-
-    let (debt, liquidator_request, liquidatee_request) = calculate_vault_liquidation(
-        &deps,
-        liquidatee_account_id,
-        &debt_coin,
-        &request_vault,
-        amount,
-        &vault_info,
-    )?;
-
-    let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
+    total_underlying: Uint128,
+    vault_info: &VaultInfoResponse,
+) -> ContractResult<Coin> {
+    Ok(Coin {
+        denom: vault_info.vault_token.clone(),
+        amount: amount.checked_multiply_ratio(underlying.amount, total_underlying)?,
+    })
+}
 
-    update_vault_position(
+/// Mirrors `liquidate_vault` without asserting the liquidation is profitable.
+/// The request coins are the underlying coins the vault coins redeem for, as that is what the
+/// liquidator receives. The protocol fee is taken out of them the way the
+/// `SendCoinAfterVaultLiquidation` and `UpdateCoinBalanceAfterVaultLiquidation` callbacks do.
+/// Returns -> (Debt Coin, Liquidator Request Coin, Liquidatee Request Coin, Profitable)
+pub fn preview_vault_liquidation(
+    deps: Deps,
+    state: &LiquidationState,
+    debt_coin: &Coin,
+    request_vault: &Vault,
+    position_type: VaultPositionType,
+) -> ContractResult<(Coin, Coin, Coin, bool)> {
+    let bucket = load_vault_bucket(
         deps.storage,
-        liquidatee_account_id,
-        &request_vault.address,
-        VaultPositionUpdate::Locked(UpdateType::Decrement(liquidatee_request.amount)),
+        &state.liquidatee_account_id,
+        request_vault,
+        position_type,
     )?;
+    let vault_info = request_vault.query_info(&deps.querier)?;
 
-    let vault_withdraw_msg =
-        request_vault.force_withdraw_locked_msg(&deps.querier, liquidatee_request.amount)?;
-
-    let protocol_fee = liquidatee_request.amount.checked_sub(liquidator_request.amount)?;
-    let protocol_fee_percentage =
-        Decimal::checked_from_ratio(protocol_fee, liquidatee_request.amount)?;
+    let amount = match bucket {
+        VaultBucket::Unlocked(amount) | VaultBucket::Locked(amount) => amount,
+        // Unlocking positions are liquidated in underlying coins
+        VaultBucket::Unlocking(unlocking_positions) => {
+            return preview_liquidation(
+                deps,
+                state,
+                debt_coin,
+                &vault_info.base_token,
+                unlocking_positions.total(),
+            );
+        }
+    };
 
-    let update_coin_balance_msg = update_balance_after_vault_liquidation_msg(
-        &deps.querier,
-        &env.contract.address,
-        liquidator_account_id,
+    let total_underlying = request_vault.query_preview_redeem(&deps.querier, amount)?;
+    let (debt, liquidator_request, liquidatee_request) = liquidation_amounts_for_state(
+        deps,
+        state,
+        debt_coin,
         &vault_info.base_token,
-        protocol_fee_percentage,
+        total_underlying,
     )?;
-
-    Ok(Response::new()
-        .add_message(repay_msg)
-        .add_message(vault_withdraw_msg)
-        .add_message(update_coin_balance_msg)
-        .add_attribute("action", "liquidate_vault/locked")
-        .add_attribute("account_id", liquidator_account_id)
-        .add_attribute("liquidatee_account_id", liquidatee_account_id)
-        .add_attribute("coin_debt_repaid", debt.to_string())
-        .add_attribute("coin_liquidated", liquidatee_request.to_string())
-        .add_attribute(
-            "protocol_fee_coin",
-            Coin::new(protocol_fee.u128(), liquidatee_request.denom).to_string(),
-        ))
+    let liquidator_vault_coin =
+        into_vault_coin(liquidator_request, amount, total_underlying, &vault_info)?;
+    let liquidatee_vault_coin =
+        into_vault_coin(liquidatee_request, amount, total_underlying, &vault_info)?;
+
+    let liquidatee_underlying =
+        request_vault.query_preview_redeem(&deps.querier, liquidatee_vault_coin.amount)?;
+    let protocol_fee = if liquidatee_vault_coin.amount.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::checked_from_ratio(
+            liquidatee_vault_coin.amount.checked_sub(liquidator_vault_coin.amount)?,
+            liquidatee_vault_coin.amount,
+        )?
+    };
+    let liquidator_underlying =
+        liquidatee_underlying.checked_sub(liquidatee_underlying.checked_mul_ceil(protocol_fee)?)?;
+
+    let liquidator_request = Coin::new(liquidator_underlying.u128(), &vault_info.base_token);
+    let liquidatee_request = Coin::new(liquidatee_underlying.u128(), &vault_info.base_token);
+    let profitable = is_liquidation_profitable(deps, &debt, &liquidator_request)?;
+    Ok((debt, liquidator_request, liquidatee_request, profitable))
 }
@@ -1,7 +1,7 @@
-use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Response};
+use cosmwasm_std::{Coin, Deps, DepsMut, Response};
 use mars_rover::{
     error::{ContractError, ContractResult},
-    msg::execute::{ActionAmount, ActionCoin},
+    msg::execute::{ActionAmount, ActionCoin, Recipient},
 };
 
 use crate::{state::COIN_BALANCES, utils::decrement_coin_balance};
@@ -10,23 +10,21 @@ pub fn withdraw(
     deps: DepsMut,
     account_id: &str,
     coin: &ActionCoin,
-    recipient: Addr,
+    recipient: Recipient,
 ) -> ContractResult<Response> {
     let amount_to_withdraw = get_withdraw_amount(deps.as_ref(), account_id, coin)?;
 
     decrement_coin_balance(deps.storage, account_id, &amount_to_withdraw)?;
 
     // send coin to recipient
-    let transfer_msg = CosmosMsg::Bank(BankMsg::Send {
-        to_address: recipient.to_string(),
-        amount: vec![amount_to_withdraw.clone()],
-    });
+    let transfer_msg = recipient.send_msg(vec![amount_to_withdraw.clone()]);
 
     Ok(Response::new()
         .add_message(transfer_msg)
         .add_attribute("action", "callback/withdraw")
         .add_attribute("account_id", account_id)
-        .add_attribute("coin_withdrawn", amount_to_withdraw.to_string()))
+        .add_attribute("coin_withdrawn", amount_to_withdraw.to_string())
+        .add_attribute("recipient", recipient.address().to_string()))
 }
 
 /// Checks if Exact or Account Balance is passed through Action Coin
@@ -1,4 +1,6 @@
-use cosmwasm_std::Empty;
+use cosmwasm_std::{
+    Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
 use cw_multi_test::{App, Contract, ContractWrapper};
 
 pub fn mock_app() -> App {
@@ -104,3 +106,19 @@ pub fn mock_params_contract() -> Box<dyn Contract<Empty>> {
     );
     Box::new(contract)
 }
+
+/// Dispatches the message it is executed with. Used as a contract recipient attempting reentrancy.
+pub fn mock_forwarder_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |_: DepsMut, _: Env, _: MessageInfo, msg: CosmosMsg| -> StdResult<Response> {
+            Ok(Response::new().add_message(msg))
+        },
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |_: Deps, _: Env, _: Empty| -> StdResult<Binary> {
+            Err(StdError::generic_err("no queries"))
+        },
+    );
+    Box::new(contract)
+}
@@ -5,6 +5,7 @@ use cosmwasm_std::{coins, testing::MockApi, Addr, Coin, Decimal, Empty, StdResul
 use cw721::TokensResponse;
 use cw721_base::{Action::TransferOwnership, Ownership};
 use cw_multi_test::{App, AppResponse, BankSudo, BasicApp, Executor, SudoMsg};
+use cw_utils::Expiration;
 use cw_vault_standard::{
     extensions::lockup::{LockupQueryMsg, UnlockingPosition},
     msg::{ExtensionQueryMsg, VaultStandardQueryMsg::VaultExtension},
@@ -53,18 +54,26 @@ use mars_rover::{
         params::Params,
         red_bank::RedBankUnchecked,
         swap::{
-            EstimateExactInSwapResponse, InstantiateMsg as SwapperInstantiateMsg,
-            QueryMsg::EstimateExactInSwap, Swapper, SwapperBase,
+            EstimateExactInSwapResponse, EstimateExactOutSwapResponse,
+            InstantiateMsg as SwapperInstantiateMsg,
+            QueryMsg::{EstimateExactInSwap, EstimateExactOutSwap},
+            Swapper, SwapperBase,
         },
         vault::{Vault, VaultPosition, VaultPositionValue as VPositionValue, VaultUnchecked},
         zapper::{Zapper, ZapperBase},
     },
     msg::{
-        execute::{Action, CallbackMsg},
-        instantiate::ConfigUpdates,
+        execute::{
+            AccountUpdate, Action, ActionPause, ActionPermission, CallbackMsg, LiquidateRequest,
+            TriggerCondition,
+        },
+        instantiate::{ConfigUpdates, Role},
         query::{
-            Account, CoinBalanceResponseItem, ConfigResponse, DebtShares, Positions,
-            SharesResponseItem, VaultPositionResponseItem, VaultUtilizationResponse,
+            Account, BadDebtResponseItem, BasketLiquidationPreviewResponse,
+            CoinBalanceResponseItem, ConfigProposal, ConfigResponse, DebtShares, Delegate,
+            LiquidationPreviewResponse, Positions, RolesResponseItem, SharesResponseItem,
+            SimulateActionsResponse, TriggerOrder, VaultPositionResponseItem,
+            VaultUtilizationResponse,
         },
         ExecuteMsg, InstantiateMsg, QueryMsg,
         QueryMsg::{EstimateProvideLiquidity, VaultPositionValue},
@@ -77,10 +86,10 @@ use mars_rover_health_types::{
 use mars_zapper_mock::msg::{InstantiateMsg as ZapperInstantiateMsg, LpConfig};
 
 use crate::helpers::{
-    lp_token_info, mock_account_nft_contract, mock_address_provider_contract, mock_health_contract,
-    mock_incentives_contract, mock_oracle_contract, mock_params_contract, mock_red_bank_contract,
-    mock_rover_contract, mock_swapper_contract, mock_v2_zapper_contract, mock_vault_contract,
-    AccountToFund, CoinInfo, VaultTestInfo,
+    lp_token_info, mock_account_nft_contract, mock_address_provider_contract,
+    mock_forwarder_contract, mock_health_contract, mock_incentives_contract, mock_oracle_contract,
+    mock_params_contract, mock_red_bank_contract, mock_rover_contract, mock_swapper_contract,
+    mock_v2_zapper_contract, mock_vault_contract, AccountToFund, CoinInfo, VaultTestInfo,
 };
 
 pub const DEFAULT_RED_BANK_COIN_BALANCE: Uint128 = Uint128::new(1_000_000);
@@ -162,6 +171,20 @@ impl MockEnv {
         )
     }
 
+    pub fn update_credit_accounts(
+        &mut self,
+        sender: &Addr,
+        updates: Vec<AccountUpdate>,
+        send_funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::UpdateCreditAccounts(updates),
+            send_funds,
+        )
+    }
+
     pub fn invoke_callback(
         &mut self,
         sender: &Addr,
@@ -191,6 +214,251 @@ impl MockEnv {
         )
     }
 
+    pub fn liquidate_from_wallet(
+        &mut self,
+        sender: &Addr,
+        liquidatee_account_id: &str,
+        request: LiquidateRequest<VaultUnchecked>,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::LiquidateFromWallet {
+                liquidatee_account_id: liquidatee_account_id.to_string(),
+                request,
+            },
+            funds,
+        )
+    }
+
+    pub fn update_liquidatable_since(
+        &mut self,
+        sender: &Addr,
+        account_id: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::UpdateLiquidatableSince {
+                account_id: account_id.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn write_off_bad_debt(
+        &mut self,
+        sender: &Addr,
+        account_id: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::WriteOffBadDebt {
+                account_id: account_id.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn change_account_kind(
+        &mut self,
+        sender: &Addr,
+        account_id: &str,
+        kind: AccountKind,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::ChangeAccountKind {
+                account_id: account_id.to_string(),
+                kind,
+            },
+            &[],
+        )
+    }
+
+    pub fn merge_accounts(
+        &mut self,
+        sender: &Addr,
+        from_account_id: &str,
+        into_account_id: &str,
+        burn_from_account: bool,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::MergeAccounts {
+                from_account_id: from_account_id.to_string(),
+                into_account_id: into_account_id.to_string(),
+                burn_from_account,
+            },
+            &[],
+        )
+    }
+
+    pub fn pause_action(&mut self, sender: &Addr, pause: ActionPause) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::PauseAction(pause),
+            &[],
+        )
+    }
+
+    pub fn unpause_action(&mut self, sender: &Addr, pause: ActionPause) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::UnpauseAction(pause),
+            &[],
+        )
+    }
+
+    pub fn grant_role(
+        &mut self,
+        sender: &Addr,
+        address: &str,
+        role: Role,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::GrantRole {
+                address: address.to_string(),
+                role,
+            },
+            &[],
+        )
+    }
+
+    pub fn revoke_role(
+        &mut self,
+        sender: &Addr,
+        address: &str,
+        role: Role,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::RevokeRole {
+                address: address.to_string(),
+                role,
+            },
+            &[],
+        )
+    }
+
+    pub fn create_trigger_order(
+        &mut self,
+        account_id: &str,
+        sender: &Addr,
+        actions: Vec<Action>,
+        condition: TriggerCondition,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::CreateTriggerOrder {
+                account_id: account_id.to_string(),
+                actions,
+                condition,
+            },
+            &[],
+        )
+    }
+
+    pub fn cancel_trigger_order(
+        &mut self,
+        account_id: &str,
+        sender: &Addr,
+        trigger_order_id: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::CancelTriggerOrder {
+                account_id: account_id.to_string(),
+                trigger_order_id,
+            },
+            &[],
+        )
+    }
+
+    pub fn execute_trigger_order(
+        &mut self,
+        account_id: &str,
+        sender: &Addr,
+        trigger_order_id: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::ExecuteTriggerOrder {
+                account_id: account_id.to_string(),
+                trigger_order_id,
+            },
+            &[],
+        )
+    }
+
+    pub fn grant_permission(
+        &mut self,
+        account_id: &str,
+        sender: &Addr,
+        delegate: &Addr,
+        permissions: Vec<ActionPermission>,
+        expires: Option<Expiration>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::GrantPermission {
+                account_id: account_id.to_string(),
+                delegate: delegate.to_string(),
+                permissions,
+                expires,
+            },
+            &[],
+        )
+    }
+
+    pub fn revoke_permission(
+        &mut self,
+        account_id: &str,
+        sender: &Addr,
+        delegate: &Addr,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::RevokePermission {
+                account_id: account_id.to_string(),
+                delegate: delegate.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn transfer_account(
+        &mut self,
+        account_id: &str,
+        sender: &Addr,
+        recipient: &Addr,
+    ) -> AnyResult<AppResponse> {
+        let nft_contract = self.query_config().account_nft.unwrap();
+        self.app.execute_contract(
+            sender.clone(),
+            Addr::unchecked(nft_contract),
+            &NftExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: account_id.to_string(),
+            },
+            &[],
+        )
+    }
+
     pub fn update_config(
         &mut self,
         sender: &Addr,
@@ -206,6 +474,49 @@ impl MockEnv {
         )
     }
 
+    pub fn propose_config_update(
+        &mut self,
+        sender: &Addr,
+        updates: ConfigUpdates,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::ProposeConfigUpdate(updates),
+            &[],
+        )
+    }
+
+    pub fn execute_config_update(
+        &mut self,
+        sender: &Addr,
+        proposal_id: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::ExecuteConfigUpdate {
+                proposal_id,
+            },
+            &[],
+        )
+    }
+
+    pub fn cancel_config_update(
+        &mut self,
+        sender: &Addr,
+        proposal_id: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::CancelConfigUpdate {
+                proposal_id,
+            },
+            &[],
+        )
+    }
+
     pub fn update_asset_params(&mut self, update: AssetParamsUpdate) {
         let config = self.query_config();
         self.app
@@ -259,6 +570,20 @@ impl MockEnv {
         Ok(AccountNftUnchecked::new(nft_contract.to_string()))
     }
 
+    pub fn deploy_forwarder(&mut self) -> Addr {
+        let code_id = self.app.store_code(mock_forwarder_contract());
+        self.app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("deployer"),
+                &Empty {},
+                &[],
+                "mock-forwarder",
+                None,
+            )
+            .unwrap()
+    }
+
     pub fn create_credit_account(&mut self, sender: &Addr) -> AnyResult<String> {
         self._create_credit_account(sender, AccountKind::Default)
     }
@@ -375,6 +700,159 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn simulate_actions(
+        &self,
+        account_id: &str,
+        sender: &Addr,
+        actions: Vec<Action>,
+    ) -> StdResult<SimulateActionsResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.rover.clone(),
+            &QueryMsg::SimulateActions {
+                sender: sender.to_string(),
+                account_id: account_id.to_string(),
+                actions,
+            },
+        )
+    }
+
+    pub fn query_liquidation_preview(
+        &self,
+        liquidatee_account_id: &str,
+        debt_coin: Coin,
+        request: LiquidateRequest<VaultUnchecked>,
+    ) -> StdResult<LiquidationPreviewResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.rover.clone(),
+            &QueryMsg::LiquidationPreview {
+                liquidatee_account_id: liquidatee_account_id.to_string(),
+                debt_coin,
+                request,
+            },
+        )
+    }
+
+    pub fn query_basket_liquidation_preview(
+        &self,
+        liquidatee_account_id: &str,
+        debt_coin: Coin,
+        requests: Vec<LiquidateRequest<VaultUnchecked>>,
+    ) -> StdResult<BasketLiquidationPreviewResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.rover.clone(),
+            &QueryMsg::BasketLiquidationPreview {
+                liquidatee_account_id: liquidatee_account_id.to_string(),
+                debt_coin,
+                requests,
+            },
+        )
+    }
+
+    pub fn query_roles(
+        &self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Vec<RolesResponseItem> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::Roles {
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_config_proposals(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Vec<ConfigProposal> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::ConfigProposals {
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_paused_actions(&self) -> Vec<ActionPause> {
+        self.app.wrap().query_wasm_smart(self.rover.clone(), &QueryMsg::PausedActions {}).unwrap()
+    }
+
+    pub fn query_liquidatable_since(&self, account_id: &str) -> Option<u64> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::LiquidatableSince {
+                    account_id: account_id.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_trigger_orders(
+        &self,
+        account_id: &str,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Vec<TriggerOrder> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::TriggerOrders {
+                    account_id: account_id.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_all_trigger_orders(
+        &self,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    ) -> Vec<TriggerOrder> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::AllTriggerOrders {
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_delegates(
+        &self,
+        account_id: &str,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Vec<Delegate> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::Delegates {
+                    account_id: account_id.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_health(
         &self,
         account_id: &str,
@@ -414,6 +892,23 @@ impl MockEnv {
         self.app.wrap().query_balance(addr.clone(), denom).unwrap()
     }
 
+    pub fn query_accounts_with_debt(
+        &self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Vec<Account> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::AccountsWithDebt {
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_config(&self) -> ConfigResponse {
         self.app.wrap().query_wasm_smart(self.rover.clone(), &QueryMsg::Config {}).unwrap()
     }
@@ -560,6 +1055,23 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn query_bad_debt(
+        &self,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> Vec<BadDebtResponseItem> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::BadDebt {
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_all_debt_shares(
         &self,
         start_after: Option<(String, String)>,
@@ -710,6 +1222,24 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn query_swap_exact_out_estimate(
+        &self,
+        denom_in: &str,
+        coin_out: &Coin,
+    ) -> EstimateExactOutSwapResponse {
+        let config = self.query_config();
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                config.swapper,
+                &EstimateExactOutSwap {
+                    denom_in: denom_in.to_string(),
+                    coin_out: coin_out.clone(),
+                },
+            )
+            .unwrap()
+    }
+
     pub fn estimate_provide_liquidity(&self, lp_token_out: &str, coins_in: &[Coin]) -> Uint128 {
         let config = self.query_config();
         self.app
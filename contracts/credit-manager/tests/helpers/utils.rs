@@ -1,5 +1,8 @@
 use cosmwasm_std::Coin;
-use mars_rover::msg::query::DebtAmount;
+use mars_rover::msg::{
+    execute::{Action, ActionCoin},
+    query::DebtAmount,
+};
 
 pub fn get_coin(denom: &str, coins: &[Coin]) -> Coin {
     coins.iter().find(|cv| cv.denom == denom).unwrap().clone()
@@ -8,3 +11,8 @@ pub fn get_coin(denom: &str, coins: &[Coin]) -> Coin {
 pub fn get_debt(denom: &str, coins: &[DebtAmount]) -> DebtAmount {
     coins.iter().find(|coin| coin.denom.as_str() == denom).unwrap().clone()
 }
+
+/// Withdraw action sending the coin to the account owner
+pub fn withdraw(coin: ActionCoin) -> Action {
+    Action::Withdraw(coin)
+}
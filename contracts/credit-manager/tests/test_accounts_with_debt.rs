@@ -0,0 +1,71 @@
+use cosmwasm_std::Addr;
+use mars_rover::msg::execute::Action::{Borrow, Deposit};
+use mars_rover_health_types::AccountKind;
+
+use crate::helpers::{uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn lists_each_account_with_debt_once() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(600), uatom_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let account_a = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_a,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Borrow(uosmo_info.to_coin(10)),
+            Borrow(uatom_info.to_coin(10)),
+        ],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let account_b = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_b,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300))],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let account_c = mock.create_hls_account(&user);
+    mock.update_credit_account(
+        &account_c,
+        &user,
+        vec![Deposit(uatom_info.to_coin(300)), Borrow(uatom_info.to_coin(10))],
+        &[uatom_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let accounts = mock.query_accounts_with_debt(None, None);
+    assert_eq!(
+        accounts.iter().map(|a| (a.id.clone(), a.kind.clone())).collect::<Vec<_>>(),
+        vec![
+            (account_a.clone(), AccountKind::Default),
+            (account_c.clone(), AccountKind::HighLeveredStrategy)
+        ]
+    );
+
+    let accounts = mock.query_accounts_with_debt(None, Some(1));
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts.first().unwrap().id, account_a);
+
+    let accounts = mock.query_accounts_with_debt(Some(account_a), None);
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts.first().unwrap().id, account_c);
+}
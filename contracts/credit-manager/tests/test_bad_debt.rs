@@ -0,0 +1,290 @@
+use cosmwasm_std::{coins, to_binary, Addr, Coin, CosmosMsg, Decimal, Uint128, WasmMsg};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    error::ContractError,
+    msg::{
+        execute::{
+            Action::{Borrow, Deposit, WithdrawToRecipient},
+            ActionAmount, ActionCoin, RecipientBase,
+        },
+        instantiate::ConfigUpdates,
+        query::BadDebtResponseItem,
+        ExecuteMsg,
+    },
+};
+
+use crate::helpers::{
+    assert_err, get_coin, uatom_info, uosmo_info, withdraw, AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn can_not_write_off_debt_of_account_with_collateral() {
+    let (mut mock, account_id) = setup_account_with_debt();
+
+    let res = mock.write_off_bad_debt(&Addr::unchecked("anyone"), &account_id);
+    assert_err(
+        res,
+        ContractError::NoBadDebt {
+            account_id: account_id.clone(),
+        },
+    );
+}
+
+#[test]
+fn can_not_write_off_without_insurance_balance() {
+    let (mut mock, account_id) = setup_account_with_debt();
+    collateral_worth_zero(&mut mock);
+
+    let res = mock.write_off_bad_debt(&Addr::unchecked("anyone"), &account_id);
+    assert_err(
+        res,
+        ContractError::NoInsuranceBalance {
+            account_id: account_id.clone(),
+        },
+    );
+}
+
+#[test]
+fn bad_debt_written_off_against_rewards_collector() {
+    let (mut mock, account_id) = setup_account_with_debt();
+    collateral_worth_zero(&mut mock);
+
+    let uatom_info = uatom_info();
+    let rewards_collector = Addr::unchecked(mock.query_config().rewards_collector.unwrap().address);
+    let rewards_collector_acc_id = mock.query_rewards_collector_account();
+
+    // Insurance only covers part of the debt
+    mock.update_credit_account(
+        &rewards_collector_acc_id,
+        &rewards_collector,
+        vec![Deposit(uatom_info.to_coin(30))],
+        &[uatom_info.to_coin(30)],
+    )
+    .unwrap();
+
+    let res = mock.write_off_bad_debt(&Addr::unchecked("anyone"), &account_id).unwrap();
+    let attr = res
+        .events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == "coin_written_off")
+        .unwrap();
+    assert_eq!(attr.value, uatom_info.to_coin(30).to_string());
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 1);
+    assert_eq!(position.debts.first().unwrap().amount, Uint128::new(21));
+
+    let position = mock.query_positions(&rewards_collector_acc_id);
+    assert_eq!(position.deposits.len(), 0);
+
+    assert_eq!(
+        mock.query_bad_debt(None, None),
+        vec![BadDebtResponseItem {
+            account_id: account_id.clone(),
+            denom: uatom_info.denom.clone(),
+            amount: Uint128::new(30),
+        }]
+    );
+
+    // Once topped up, the rest is written off
+    mock.update_credit_account(
+        &rewards_collector_acc_id,
+        &rewards_collector,
+        vec![Deposit(uatom_info.to_coin(70))],
+        &[uatom_info.to_coin(70)],
+    )
+    .unwrap();
+
+    mock.write_off_bad_debt(&Addr::unchecked("anyone"), &account_id).unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 0);
+
+    let position = mock.query_positions(&rewards_collector_acc_id);
+    assert_eq!(position.deposits, vec![uatom_info.to_coin(49)]);
+
+    assert_eq!(
+        mock.query_bad_debt(None, None),
+        vec![BadDebtResponseItem {
+            account_id: account_id.clone(),
+            denom: uatom_info.denom,
+            amount: Uint128::new(51),
+        }]
+    );
+
+    // Nothing left to write off
+    let res = mock.write_off_bad_debt(&Addr::unchecked("anyone"), &account_id);
+    assert_err(
+        res,
+        ContractError::NoBadDebt {
+            account_id,
+        },
+    );
+}
+
+#[test]
+fn dust_collateral_is_swept_into_rewards_collector() {
+    let (mut mock, account_id) = setup_account_with_debt();
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    // 300 uosmo worth 3
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uosmo_info.denom.clone(),
+        price: Decimal::percent(1),
+    });
+
+    let rewards_collector = Addr::unchecked(mock.query_config().rewards_collector.unwrap().address);
+    let rewards_collector_acc_id = mock.query_rewards_collector_account();
+    mock.update_credit_account(
+        &rewards_collector_acc_id,
+        &rewards_collector,
+        vec![Deposit(uatom_info.to_coin(100))],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    let res = mock.write_off_bad_debt(&Addr::unchecked("anyone"), &account_id);
+    assert_err(
+        res,
+        ContractError::NoBadDebt {
+            account_id: account_id.clone(),
+        },
+    );
+
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            bad_debt_dust_threshold: Some(Uint128::new(3)),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    mock.write_off_bad_debt(&Addr::unchecked("anyone"), &account_id).unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert!(position.deposits.is_empty());
+    assert!(position.debts.is_empty());
+
+    let position = mock.query_positions(&rewards_collector_acc_id);
+    assert_eq!(get_coin(&uosmo_info.denom, &position.deposits), uosmo_info.to_coin(300));
+    assert_eq!(get_coin(&uatom_info.denom, &position.deposits), uatom_info.to_coin(49));
+}
+
+#[test]
+fn write_off_can_not_be_reentered_from_dispatch() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: Addr::unchecked("rewards_collector_contract"),
+            funds: coins(100, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let forwarder = mock.deploy_forwarder();
+
+    let rewards_collector = Addr::unchecked(mock.query_config().rewards_collector.unwrap().address);
+    let rewards_collector_acc_id = mock.query_rewards_collector_account();
+    mock.update_credit_account(
+        &rewards_collector_acc_id,
+        &rewards_collector,
+        vec![Deposit(uatom_info.to_coin(100))],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    // The contract recipient attempts to have the emptied account's debt written off before
+    // health is asserted
+    let write_off_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: mock.rover.to_string(),
+        msg: to_binary(&ExecuteMsg::WriteOffBadDebt {
+            account_id: account_id.clone(),
+        })
+        .unwrap(),
+        funds: vec![],
+    });
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Borrow(uatom_info.to_coin(50)),
+            withdraw(uosmo_info.to_action_coin(300)),
+            WithdrawToRecipient {
+                coin: ActionCoin {
+                    denom: uatom_info.denom.clone(),
+                    amount: ActionAmount::Exact(Uint128::new(50)),
+                },
+                recipient: RecipientBase::Contract {
+                    contract: forwarder.to_string(),
+                    msg: to_binary(&write_off_msg).unwrap(),
+                },
+            },
+        ],
+        &[uosmo_info.to_coin(300)],
+    );
+    assert_err(res, ContractError::ReentrancyGuard("Reentrancy guard is active".to_string()));
+
+    assert!(mock.query_bad_debt(None, None).is_empty());
+    let position = mock.query_positions(&rewards_collector_acc_id);
+    assert_eq!(position.deposits, vec![uatom_info.to_coin(100)]);
+}
+
+/// Account holds 300 uosmo of collateral with 51 uatom of debt
+fn setup_account_with_debt() -> (MockEnv, String) {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: Addr::unchecked("rewards_collector_contract"),
+            funds: coins(100, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Borrow(uatom_info.to_coin(50)),
+            withdraw(uatom_info.to_action_coin(50)),
+        ],
+        &[Coin::new(300, uosmo_info.denom)],
+    )
+    .unwrap();
+
+    (mock, account_id)
+}
+
+fn collateral_worth_zero(mock: &mut MockEnv) {
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uosmo_info().denom,
+        price: Decimal::zero(),
+    });
+}
@@ -0,0 +1,223 @@
+use cosmwasm_std::{coins, to_binary, Addr, CosmosMsg, Uint128, WasmMsg};
+use cw_multi_test::Executor;
+use mars_rover::{
+    error::ContractError,
+    msg::{
+        execute::{
+            Action::{Borrow, Deposit, WithdrawToRecipient},
+            ActionAmount, ActionCoin, RecipientBase,
+        },
+        ExecuteMsg,
+    },
+};
+use mars_rover_health_types::AccountKind;
+
+use crate::helpers::{assert_err, uatom_info, ujake_info, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn only_owner_can_change_account_kind() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let user = Addr::unchecked("user");
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let bad_guy = Addr::unchecked("bad_guy");
+    let res = mock.change_account_kind(&bad_guy, &account_id, AccountKind::HighLeveredStrategy);
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: bad_guy.to_string(),
+            account_id,
+        },
+    )
+}
+
+#[test]
+fn kind_must_change() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let user = Addr::unchecked("user");
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.change_account_kind(&user, &account_id, AccountKind::Default);
+    assert_err(
+        res,
+        ContractError::AccountKindUnchanged {
+            account_id,
+            kind: AccountKind::Default.to_string(),
+        },
+    )
+}
+
+#[test]
+fn positions_must_follow_hls_rules() {
+    let atom_info = uatom_info();
+    let jake_info = ujake_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[atom_info.clone(), jake_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, jake_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(jake_info.to_coin(300)), Borrow(atom_info.to_coin(10))],
+        &[jake_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = mock.change_account_kind(&user, &account_id, AccountKind::HighLeveredStrategy);
+    assert_err(
+        res,
+        ContractError::HLS {
+            reason: format!(
+                "{} deposit is not a correlated asset to debt {}",
+                jake_info.denom, atom_info.denom
+            ),
+        },
+    );
+    assert_eq!(mock.query_account_kind(&account_id), AccountKind::Default);
+}
+
+#[test]
+fn must_be_healthy_under_new_kind() {
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(100, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+
+    // Only healthy under the higher HLS max LTV
+    let account_id = mock.create_hls_account(&user);
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(atom_info.to_coin(100)), Borrow(atom_info.to_coin(550))],
+        &[atom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    let err: ContractError = mock
+        .change_account_kind(&user, &account_id, AccountKind::Default)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AboveMaxLTV { .. }));
+    assert_eq!(mock.query_account_kind(&account_id), AccountKind::HighLeveredStrategy);
+}
+
+#[test]
+fn default_account_converted_to_hls() {
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(100, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(atom_info.to_coin(100)), Borrow(atom_info.to_coin(400))],
+        &[atom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    // Further leverage is above the default max LTV
+    let res =
+        mock.update_credit_account(&account_id, &user, vec![Borrow(atom_info.to_coin(150))], &[]);
+    let err: ContractError = res.unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::AboveMaxLTV { .. }));
+
+    let res =
+        mock.change_account_kind(&user, &account_id, AccountKind::HighLeveredStrategy).unwrap();
+    let attr = |key: &str| {
+        res.events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == key)
+            .map(|a| a.value.clone())
+            .unwrap()
+    };
+    assert_eq!(attr("prev_kind"), AccountKind::Default.to_string());
+    assert_eq!(attr("kind"), AccountKind::HighLeveredStrategy.to_string());
+    assert_eq!(mock.query_account_kind(&account_id), AccountKind::HighLeveredStrategy);
+
+    // Allowed under the HLS max LTV
+    mock.update_credit_account(&account_id, &user, vec![Borrow(atom_info.to_coin(150))], &[])
+        .unwrap();
+}
+
+#[test]
+fn kind_can_not_change_mid_dispatch() {
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(100, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+
+    // The account is owned by a contract that switches it to HLS when it receives a withdrawal
+    let forwarder = mock.deploy_forwarder();
+    let account_id = mock.create_credit_account(&forwarder).unwrap();
+
+    let change_kind_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: mock.rover.to_string(),
+        msg: to_binary(&ExecuteMsg::ChangeAccountKind {
+            account_id: account_id.clone(),
+            kind: AccountKind::HighLeveredStrategy,
+        })
+        .unwrap(),
+        funds: vec![],
+    });
+    let update_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: mock.rover.to_string(),
+        msg: to_binary(&ExecuteMsg::UpdateCreditAccount {
+            account_id: account_id.clone(),
+            actions: vec![
+                Deposit(atom_info.to_coin(100)),
+                WithdrawToRecipient {
+                    coin: ActionCoin {
+                        denom: atom_info.denom.clone(),
+                        amount: ActionAmount::Exact(Uint128::new(10)),
+                    },
+                    recipient: RecipientBase::Contract {
+                        contract: forwarder.to_string(),
+                        msg: to_binary(&change_kind_msg).unwrap(),
+                    },
+                },
+                Borrow(atom_info.to_coin(400)),
+            ],
+        })
+        .unwrap(),
+        funds: vec![atom_info.to_coin(100)],
+    });
+
+    let res = mock.app.execute_contract(user, forwarder, &update_msg, &[atom_info.to_coin(100)]);
+    assert_err(res, ContractError::ReentrancyGuard("Reentrancy guard is active".to_string()));
+    assert_eq!(mock.query_account_kind(&account_id), AccountKind::Default);
+}
@@ -21,7 +21,14 @@ fn claiming_rewards_when_having_none() {
     let unclaimed = mock.query_unclaimed_rewards(&account_id);
     assert!(unclaimed.is_empty());
 
-    let res = mock.update_credit_account(&account_id, &user, vec![ClaimRewards {}], &[]);
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ClaimRewards {
+            recipient: None,
+        }],
+        &[],
+    );
     assert_err(res, ContractError::NoAmount);
 }
 
@@ -41,7 +48,15 @@ fn claiming_a_single_reward() {
     let unclaimed = mock.query_unclaimed_rewards(&account_id);
     assert_eq!(unclaimed.len(), 1);
 
-    mock.update_credit_account(&account_id, &user, vec![ClaimRewards {}], &[]).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ClaimRewards {
+            recipient: None,
+        }],
+        &[],
+    )
+    .unwrap();
 
     // Check account id deposit balance
     let positions = mock.query_positions(&account_id);
@@ -76,7 +91,15 @@ fn claiming_multiple_rewards() {
     let unclaimed = mock.query_unclaimed_rewards(&account_id);
     assert_eq!(unclaimed.len(), 3);
 
-    mock.update_credit_account(&account_id, &user, vec![ClaimRewards {}], &[]).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ClaimRewards {
+            recipient: None,
+        }],
+        &[],
+    )
+    .unwrap();
 
     // Check account id deposit balance
     let positions = mock.query_positions(&account_id);
@@ -157,7 +180,15 @@ fn claiming_by_hls_account() {
     let unclaimed = mock.query_unclaimed_rewards(&account_id);
     assert_eq!(unclaimed.len(), 2);
 
-    mock.update_credit_account(&account_id, &user, vec![ClaimRewards {}], &[]).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ClaimRewards {
+            recipient: None,
+        }],
+        &[],
+    )
+    .unwrap();
 
     // Check account id deposit balance
     let positions = mock.query_positions(&account_id);
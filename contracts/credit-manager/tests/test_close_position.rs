@@ -0,0 +1,257 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use mars_rover::{
+    error::ContractError,
+    msg::execute::Action::{Borrow, ClosePosition, Deposit, EnterVault, Lend, ProvideLiquidity},
+};
+use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
+
+use crate::helpers::{
+    assert_err, blacklisted_coin, get_coin, locked_vault_info, lp_token_info, uatom_info,
+    unlocked_vault_info, uosmo_info, withdraw, AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn target_denom_must_be_whitelisted() {
+    let blacklisted_coin = blacklisted_coin();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[blacklisted_coin.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ClosePosition {
+            target_denom: blacklisted_coin.denom.clone(),
+            slippage: Decimal::percent(1),
+        }],
+        &[],
+    );
+
+    assert_err(res, ContractError::NotWhitelisted(blacklisted_coin.denom))
+}
+
+#[test]
+fn slippage_must_not_exceed_max() {
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let max_slippage = Decimal::percent(5);
+    let mut mock =
+        MockEnv::new().set_params(&[osmo_info.clone()]).max_slippage(max_slippage).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let slippage = max_slippage + Decimal::from_str("0.000001").unwrap();
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ClosePosition {
+            target_denom: osmo_info.denom,
+            slippage,
+        }],
+        &[],
+    );
+
+    assert_err(
+        res,
+        ContractError::SlippageExceeded {
+            slippage,
+            max_slippage,
+        },
+    )
+}
+
+#[test]
+fn debt_covered_by_balance_only_swaps_remainder() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom_info.to_coin(10_000), osmo_info.to_coin(100)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            Deposit(osmo_info.to_coin(100)),
+            Borrow(osmo_info.to_coin(1_000)),
+            ClosePosition {
+                target_denom: osmo_info.denom.clone(),
+                slippage: Decimal::percent(1),
+            },
+        ],
+        &[atom_info.to_coin(10_000), osmo_info.to_coin(100)],
+    )
+    .unwrap();
+
+    // Debt of 1_001 is repaid from the osmo balance (1_100), atom is swapped into osmo afterwards
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 0);
+    assert_eq!(position.deposits.len(), 1);
+    let osmo = get_coin(&osmo_info.denom, &position.deposits);
+    assert_eq!(osmo.amount, Uint128::new(99) + MOCK_SWAP_RESULT);
+}
+
+#[test]
+fn unwinds_all_positions_into_target_denom() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+    let lp_token = lp_token_info();
+    let unlocked_vault = unlocked_vault_info();
+    let locked_vault = locked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone(), lp_token.clone()])
+        .vault_configs(&[unlocked_vault.clone(), locked_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom_info.to_coin(10_000), lp_token.to_coin(400)],
+        })
+        .build()
+        .unwrap();
+    let unlocked = mock.get_vault(&unlocked_vault);
+    let locked = mock.get_vault(&locked_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            Deposit(lp_token.to_coin(400)),
+            Lend(atom_info.to_action_coin(2_000)),
+            EnterVault {
+                vault: unlocked,
+                coin: lp_token.to_action_coin(200),
+            },
+            EnterVault {
+                vault: locked.clone(),
+                coin: lp_token.to_action_coin(200),
+            },
+            Borrow(osmo_info.to_coin(2_000)),
+            withdraw(osmo_info.to_action_coin(2_000)),
+        ],
+        &[atom_info.to_coin(10_000), lp_token.to_coin(400)],
+    )
+    .unwrap();
+
+    let res = mock
+        .update_credit_account(
+            &account_id,
+            &user,
+            vec![ClosePosition {
+                target_denom: osmo_info.denom.clone(),
+                slippage: Decimal::percent(1),
+            }],
+            &[],
+        )
+        .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 0);
+    assert_eq!(position.lends.len(), 0);
+
+    // Only the debt of 2_001 was bought with atom (deposited + reclaimed). The atom left and the
+    // lp tokens (exited from vault) were then each swapped straight into osmo.
+    let swaps_exact_out = res
+        .events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .filter(|a| a.key == "action" && a.value == "swap_exact_out")
+        .count();
+    assert_eq!(swaps_exact_out, 1);
+    assert_eq!(position.deposits.len(), 1);
+    let osmo = get_coin(&osmo_info.denom, &position.deposits);
+    assert_eq!(osmo.amount, MOCK_SWAP_RESULT + MOCK_SWAP_RESULT);
+
+    // The locked vault position is left in place and reported
+    assert_eq!(position.vaults.len(), 1);
+    let vault_position = position.vaults.first().unwrap();
+    assert_eq!(vault_position.vault.address, locked.address);
+    let locked_amount = vault_position.amount.locked();
+    let reported = res.events.iter().flat_map(|e| e.attributes.iter()).any(|a| {
+        a.key == "locked_vault_position"
+            && a.value == format!("{}:{}", locked.address, locked_amount)
+    });
+    assert!(reported);
+}
+
+#[test]
+fn lp_tokens_are_withdrawn_before_repaying_debts() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+    let lp_token = lp_token_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone(), lp_token.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom_info.to_coin(10_000), osmo_info.to_coin(2_000)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            Deposit(osmo_info.to_coin(2_000)),
+            ProvideLiquidity {
+                coins_in: vec![atom_info.to_action_coin(1_000), osmo_info.to_action_coin(2_000)],
+                lp_token_out: lp_token.denom.clone(),
+                slippage: Decimal::percent(1),
+            },
+            Borrow(osmo_info.to_coin(1_000)),
+            withdraw(osmo_info.to_action_coin(1_000)),
+        ],
+        &[atom_info.to_coin(10_000), osmo_info.to_coin(2_000)],
+    )
+    .unwrap();
+
+    let res = mock
+        .update_credit_account(
+            &account_id,
+            &user,
+            vec![ClosePosition {
+                target_denom: osmo_info.denom.clone(),
+                slippage: Decimal::percent(1),
+            }],
+            &[],
+        )
+        .unwrap();
+
+    // The lp tokens are withdrawn into 1_000 atom and 2_000 osmo. The osmo covers the debt of
+    // 1_001 without any exact-out swap, and the atom is swapped into osmo afterwards.
+    let actions = res
+        .events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .filter(|a| a.key == "action")
+        .map(|a| a.value.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(actions.iter().filter(|a| *a == "withdraw_liquidity").count(), 1);
+    assert_eq!(actions.iter().filter(|a| *a == "swap_exact_out").count(), 0);
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 0);
+    assert_eq!(position.deposits.len(), 1);
+    let osmo = get_coin(&osmo_info.denom, &position.deposits);
+    assert_eq!(osmo.amount, Uint128::new(999) + MOCK_SWAP_RESULT);
+}
@@ -0,0 +1,184 @@
+use cosmwasm_std::{Addr, Decimal};
+use mars_owner::OwnerError::{NotEmergencyOwner, NotOwner};
+use mars_rover::{
+    adapters::swap::SwapperBase,
+    error::{ContractError, ContractError::Owner},
+    msg::{instantiate::ConfigUpdates, query::ConfigProposal},
+};
+
+use crate::helpers::{assert_err, MockEnv};
+
+pub mod helpers;
+
+const DELAY: u64 = 86400;
+
+#[test]
+fn timelocked_fields_can_not_be_updated_directly() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = owner(&mock);
+    set_delay(&mut mock);
+
+    let res = mock.update_config(&owner, swapper_update());
+    assert_err(
+        res,
+        ContractError::ConfigUpdateTimelocked {
+            field: "swapper".to_string(),
+        },
+    );
+
+    let res = mock.update_config(
+        &owner,
+        ConfigUpdates {
+            config_update_delay: Some(0),
+            ..Default::default()
+        },
+    );
+    assert_err(
+        res,
+        ContractError::ConfigUpdateTimelocked {
+            field: "config_update_delay".to_string(),
+        },
+    );
+
+    // Parameters are not timelocked
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            max_slippage: Some(Decimal::percent(10)),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(mock.query_config().max_slippage, Decimal::percent(10));
+}
+
+#[test]
+fn proposal_executable_after_delay() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = owner(&mock);
+    set_delay(&mut mock);
+
+    let original_swapper = mock.query_config().swapper;
+
+    mock.propose_config_update(&owner, swapper_update()).unwrap();
+    let executable_at = mock.app.block_info().time.seconds() + DELAY;
+    assert_eq!(
+        mock.query_config_proposals(None, None),
+        vec![ConfigProposal {
+            proposal_id: 1,
+            updates: swapper_update(),
+            executable_at,
+        }]
+    );
+
+    let res = mock.execute_config_update(&owner, 1);
+    assert_err(
+        res,
+        ContractError::ConfigProposalNotReady {
+            proposal_id: 1,
+            executable_at,
+        },
+    );
+    assert_eq!(mock.query_config().swapper, original_swapper);
+
+    mock.app.update_block(|block| block.time = block.time.plus_seconds(DELAY));
+    mock.execute_config_update(&owner, 1).unwrap();
+
+    assert_eq!(mock.query_config().swapper, "new_swapper".to_string());
+    assert!(mock.query_config_proposals(None, None).is_empty());
+
+    // Already executed
+    let res = mock.execute_config_update(&owner, 1);
+    assert!(res.is_err());
+}
+
+#[test]
+fn only_authorized_can_propose_and_execute() {
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let mut mock = MockEnv::new().emergency_owner(&emergency_owner).build().unwrap();
+    let owner = owner(&mock);
+    set_delay(&mut mock);
+
+    let res = mock.propose_config_update(&emergency_owner, swapper_update());
+    assert_err(res, Owner(NotOwner {}));
+
+    mock.propose_config_update(&owner, swapper_update()).unwrap();
+    mock.app.update_block(|block| block.time = block.time.plus_seconds(DELAY));
+
+    let res = mock.execute_config_update(&emergency_owner, 1);
+    assert_err(res, Owner(NotOwner {}));
+
+    let res = mock.execute_config_update(&Addr::unchecked("bad_guy"), 1);
+    assert_err(res, Owner(NotOwner {}));
+
+    mock.execute_config_update(&owner, 1).unwrap();
+}
+
+#[test]
+fn owner_and_emergency_owner_can_cancel() {
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let mut mock = MockEnv::new().emergency_owner(&emergency_owner).build().unwrap();
+    let owner = owner(&mock);
+    set_delay(&mut mock);
+
+    mock.propose_config_update(&owner, swapper_update()).unwrap();
+    mock.propose_config_update(&owner, swapper_update()).unwrap();
+    assert_eq!(mock.query_config_proposals(None, None).len(), 2);
+
+    let res = mock.cancel_config_update(&Addr::unchecked("bad_guy"), 1);
+    assert_err(res, Owner(NotEmergencyOwner {}));
+
+    mock.cancel_config_update(&emergency_owner, 1).unwrap();
+    mock.cancel_config_update(&owner, 2).unwrap();
+    assert!(mock.query_config_proposals(None, None).is_empty());
+
+    mock.app.update_block(|block| block.time = block.time.plus_seconds(DELAY));
+    let res = mock.execute_config_update(&owner, 1);
+    assert!(res.is_err());
+}
+
+#[test]
+fn delay_is_updated_through_proposal() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = owner(&mock);
+    set_delay(&mut mock);
+
+    mock.propose_config_update(
+        &owner,
+        ConfigUpdates {
+            config_update_delay: Some(0),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    mock.app.update_block(|block| block.time = block.time.plus_seconds(DELAY));
+    mock.execute_config_update(&owner, 1).unwrap();
+    assert_eq!(mock.query_config().config_update_delay, 0);
+
+    // Without a delay, addresses can be updated directly again
+    mock.update_config(&owner, swapper_update()).unwrap();
+    assert_eq!(mock.query_config().swapper, "new_swapper".to_string());
+}
+
+fn owner(mock: &MockEnv) -> Addr {
+    Addr::unchecked(mock.query_config().ownership.owner.unwrap())
+}
+
+fn set_delay(mock: &mut MockEnv) {
+    let owner = owner(mock);
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            config_update_delay: Some(DELAY),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+fn swapper_update() -> ConfigUpdates {
+    ConfigUpdates {
+        swapper: Some(SwapperBase::new("new_swapper".to_string())),
+        ..Default::default()
+    }
+}
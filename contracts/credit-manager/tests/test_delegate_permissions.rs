@@ -0,0 +1,298 @@
+use cosmwasm_std::{coins, Addr, Uint128};
+use cw_utils::Expiration;
+use mars_rover::{
+    error::{ContractError, ContractError::NotTokenOwner},
+    msg::execute::{
+        Action::{Deposit, Lend, Repay},
+        ActionPermission,
+    },
+};
+
+use crate::helpers::{assert_err, uosmo_info, withdraw, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn only_token_owner_can_grant_and_revoke() {
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.grant_permission(
+        &account_id,
+        &delegate,
+        &delegate,
+        vec![ActionPermission::Deposit],
+        None,
+    );
+    assert_err(
+        res,
+        NotTokenOwner {
+            user: delegate.to_string(),
+            account_id: account_id.clone(),
+        },
+    );
+
+    let res = mock.revoke_permission(&account_id, &delegate, &delegate);
+    assert_err(
+        res,
+        NotTokenOwner {
+            user: delegate.to_string(),
+            account_id,
+        },
+    );
+}
+
+#[test]
+fn delegate_can_only_perform_permitted_actions() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: delegate.clone(),
+            funds: coins(300, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.grant_permission(
+        &account_id,
+        &user,
+        &delegate,
+        vec![ActionPermission::Deposit, ActionPermission::Repay],
+        None,
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(coin_info.to_coin(300))],
+        &[coin_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 1);
+    assert_eq!(position.deposits[0].amount, Uint128::new(300));
+
+    let lend = Lend(coin_info.to_action_coin(50));
+    let res = mock.update_credit_account(&account_id, &delegate, vec![lend.clone()], &[]);
+    assert_err(
+        res,
+        ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("{lend:?}"),
+        },
+    );
+
+    let withdraw = withdraw(coin_info.to_action_coin(50));
+    let res = mock.update_credit_account(&account_id, &delegate, vec![withdraw.clone()], &[]);
+    assert_err(
+        res,
+        ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("{withdraw:?}"),
+        },
+    );
+
+    // Repaying another account moves funds out of the account
+    let repay = Repay {
+        recipient_account_id: Some("123".to_string()),
+        coin: coin_info.to_action_coin(50),
+    };
+    let res = mock.update_credit_account(&account_id, &delegate, vec![repay.clone()], &[]);
+    assert_err(
+        res,
+        ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("{repay:?}"),
+        },
+    );
+}
+
+#[test]
+fn expired_permissions_can_not_be_used() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: delegate.clone(),
+            funds: coins(300, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let expires_at = mock.app.block_info().height + 10;
+    mock.grant_permission(
+        &account_id,
+        &user,
+        &delegate,
+        vec![ActionPermission::Deposit],
+        Some(Expiration::AtHeight(expires_at)),
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(coin_info.to_coin(100))],
+        &[coin_info.to_coin(100)],
+    )
+    .unwrap();
+
+    mock.app.update_block(|block| block.height = expires_at);
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(coin_info.to_coin(100))],
+        &[coin_info.to_coin(100)],
+    );
+    assert_err(
+        res,
+        ContractError::PermissionExpired {
+            delegate: delegate.to_string(),
+            account_id,
+        },
+    );
+}
+
+#[test]
+fn revoked_and_transferred_accounts_lose_permissions() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: delegate.clone(),
+            funds: coins(300, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.grant_permission(&account_id, &user, &delegate, vec![ActionPermission::Deposit], None)
+        .unwrap();
+    mock.revoke_permission(&account_id, &user, &delegate).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(coin_info.to_coin(100))],
+        &[coin_info.to_coin(100)],
+    );
+    assert_err(
+        res,
+        NotTokenOwner {
+            user: delegate.to_string(),
+            account_id: account_id.clone(),
+        },
+    );
+
+    mock.grant_permission(&account_id, &user, &delegate, vec![ActionPermission::Deposit], None)
+        .unwrap();
+    mock.transfer_account(&account_id, &user, &Addr::unchecked("new_owner")).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(coin_info.to_coin(100))],
+        &[coin_info.to_coin(100)],
+    );
+    assert_err(
+        res,
+        NotTokenOwner {
+            user: delegate.to_string(),
+            account_id,
+        },
+    );
+}
+
+#[test]
+fn permissions_stay_lost_after_account_returns_to_granter() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let new_owner = Addr::unchecked("new_owner");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: delegate.clone(),
+            funds: coins(300, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.grant_permission(&account_id, &user, &delegate, vec![ActionPermission::Deposit], None)
+        .unwrap();
+    mock.transfer_account(&account_id, &user, &new_owner).unwrap();
+    mock.transfer_account(&account_id, &new_owner, &user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(coin_info.to_coin(100))],
+        &[coin_info.to_coin(100)],
+    );
+    assert_err(
+        res,
+        NotTokenOwner {
+            user: delegate.to_string(),
+            account_id: account_id.clone(),
+        },
+    );
+
+    // A new grant after the account returned is valid
+    mock.grant_permission(&account_id, &user, &delegate, vec![ActionPermission::Deposit], None)
+        .unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(coin_info.to_coin(100))],
+        &[coin_info.to_coin(100)],
+    )
+    .unwrap();
+}
+
+#[test]
+fn query_delegates() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let delegate_a = Addr::unchecked("delegate_a");
+    let delegate_b = Addr::unchecked("delegate_b");
+    mock.grant_permission(&account_id, &user, &delegate_a, vec![ActionPermission::Deposit], None)
+        .unwrap();
+    mock.grant_permission(
+        &account_id,
+        &user,
+        &delegate_b,
+        vec![ActionPermission::SwapExactIn, ActionPermission::Lend],
+        Some(Expiration::AtHeight(1_000_000)),
+    )
+    .unwrap();
+
+    let delegates = mock.query_delegates(&account_id, None, None);
+    assert_eq!(delegates.len(), 2);
+    assert_eq!(delegates[0].delegate, delegate_a.to_string());
+    assert_eq!(delegates[0].granter, user.to_string());
+    assert_eq!(delegates[0].permissions, vec![ActionPermission::Deposit]);
+    assert_eq!(delegates[0].expires, None);
+    assert_eq!(delegates[1].delegate, delegate_b.to_string());
+    assert_eq!(delegates[1].expires, Some(Expiration::AtHeight(1_000_000)));
+
+    let delegates = mock.query_delegates(&account_id, Some(delegate_a.to_string()), None);
+    assert_eq!(delegates.len(), 1);
+    assert_eq!(delegates[0].delegate, delegate_b.to_string());
+}
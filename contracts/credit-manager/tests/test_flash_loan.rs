@@ -0,0 +1,232 @@
+use cosmwasm_std::{coins, Addr, Coin, Decimal, OverflowError, OverflowOperation::Sub, Uint128};
+use mars_rover::{
+    error::ContractError,
+    msg::{
+        execute::Action::{Deposit, FlashBorrow, FlashRepay},
+        instantiate::ConfigUpdates,
+    },
+};
+
+use crate::helpers::{assert_err, uatom_info, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+fn set_flash_loan_fee(mock: &mut MockEnv, fee: Decimal) {
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            flash_loan_fee: Some(fee),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+/// Deposits coins of another user, which the credit manager can then advance as flash loans
+fn provide_liquidity(mock: &mut MockEnv, depositor: &Addr, coin: Coin) -> String {
+    let account_id = mock.create_credit_account(depositor).unwrap();
+    mock.update_credit_account(&account_id, depositor, vec![Deposit(coin.clone())], &[coin])
+        .unwrap();
+    account_id
+}
+
+#[test]
+fn flash_loan_fee_must_be_less_than_one() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    let res = mock.update_config(
+        &owner,
+        ConfigUpdates {
+            flash_loan_fee: Some(Decimal::one()),
+            ..Default::default()
+        },
+    );
+    assert_err(
+        res,
+        ContractError::InvalidConfig {
+            reason: "Flash loan fee must be less than 1".to_string(),
+        },
+    );
+}
+
+#[test]
+fn flash_borrow_must_be_repaid_in_bundle() {
+    let coin_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let depositor = Addr::unchecked("depositor");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: depositor.clone(),
+            funds: coins(1000, coin_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(20, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    provide_liquidity(&mut mock, &depositor, coin_info.to_coin(1000));
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(20)), FlashBorrow(coin_info.to_coin(1000))],
+        &[coin_info.to_coin(20)],
+    );
+    assert_err(res, ContractError::FlashLoanNotRepaid(coin_info.to_coin(1000).to_string()));
+}
+
+#[test]
+fn flash_borrow_must_be_repaid_even_if_healthy() {
+    let coin_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(1000, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    // Well collateralized, the flash loan would pass the health check as a regular borrow
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(1000)), FlashBorrow(coin_info.to_coin(10))],
+        &[coin_info.to_coin(1000)],
+    );
+    assert_err(res, ContractError::FlashLoanNotRepaid(coin_info.to_coin(10).to_string()));
+
+    let position = mock.query_positions(&account_id);
+    assert!(position.deposits.is_empty());
+    assert!(position.debts.is_empty());
+}
+
+#[test]
+fn flash_repay_requires_flash_borrow() {
+    let coin_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[coin_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(&account_id, &user, vec![FlashRepay {}], &[]);
+    assert_err(res, ContractError::NoFlashLoan);
+}
+
+#[test]
+fn flash_loan_fee_must_be_covered_by_account() {
+    let coin_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let depositor = Addr::unchecked("depositor");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: depositor.clone(),
+            funds: coins(1000, coin_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(5, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    provide_liquidity(&mut mock, &depositor, coin_info.to_coin(1000));
+    let account_id = mock.create_credit_account(&user).unwrap();
+    set_flash_loan_fee(&mut mock, Decimal::percent(1));
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(5)), FlashBorrow(coin_info.to_coin(1000)), FlashRepay {}],
+        &[coin_info.to_coin(5)],
+    );
+    assert_err(
+        res,
+        ContractError::Overflow(OverflowError {
+            operation: Sub,
+            operand1: "5".to_string(),
+            operand2: "10".to_string(),
+        }),
+    );
+}
+
+#[test]
+fn flash_borrow_above_collateral_and_repay_with_fee() {
+    let coin_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let depositor = Addr::unchecked("depositor");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: depositor.clone(),
+            funds: coins(1000, coin_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(20, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let depositor_account_id = provide_liquidity(&mut mock, &depositor, coin_info.to_coin(1000));
+    let account_id = mock.create_credit_account(&user).unwrap();
+    set_flash_loan_fee(&mut mock, Decimal::percent(1));
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(20)), FlashBorrow(coin_info.to_coin(1000)), FlashRepay {}],
+        &[coin_info.to_coin(20)],
+    )
+    .unwrap();
+
+    // 20 deposited - 10 fee (1% of 1000)
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 1);
+    assert_eq!(position.deposits[0].amount, Uint128::new(10));
+
+    // The advance does not go through Red Bank, so no debt is left behind
+    assert!(position.debts.is_empty());
+
+    let depositor_position = mock.query_positions(&depositor_account_id);
+    assert_eq!(depositor_position.deposits, vec![coin_info.to_coin(1000)]);
+
+    let rc_account_id = mock.query_rewards_collector_account();
+    let rc_position = mock.query_positions(&rc_account_id);
+    assert_eq!(rc_position.deposits.len(), 1);
+    assert_eq!(rc_position.deposits[0].denom, coin_info.denom);
+    assert_eq!(rc_position.deposits[0].amount, Uint128::new(10));
+}
+
+#[test]
+fn flash_borrow_capped_by_credit_manager_balance() {
+    let coin_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(20, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(20)), FlashBorrow(coin_info.to_coin(1000)), FlashRepay {}],
+        &[coin_info.to_coin(20)],
+    );
+    assert_err(
+        res,
+        ContractError::FlashLoanExceedsLiquidity {
+            requested: coin_info.to_coin(1000).to_string(),
+            available: "20".to_string(),
+        },
+    );
+}
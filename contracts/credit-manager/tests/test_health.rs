@@ -12,7 +12,7 @@ use mars_rover::{
     error::ContractError,
     msg::{
         execute::{
-            Action::{Borrow, Deposit, EnterVault, Repay, Withdraw},
+            Action::{Borrow, Deposit, EnterVault, Repay},
             ActionAmount, ActionCoin,
         },
         query::DebtAmount,
@@ -21,7 +21,7 @@ use mars_rover::{
 use mars_rover_health_types::AccountKind;
 
 use crate::helpers::{
-    assert_err, lp_token_info, uatom_info, ujake_info, unlocked_vault_info, uosmo_info,
+    assert_err, lp_token_info, uatom_info, ujake_info, unlocked_vault_info, uosmo_info, withdraw,
     AccountToFund, CoinInfo, MockEnv,
 };
 
@@ -932,7 +932,7 @@ fn can_take_actions_if_ltv_does_not_weaken() {
     mock.update_credit_account(
         &account_id,
         &user,
-        vec![Deposit(uosmo_info.to_coin(1)), Withdraw(uosmo_info.to_action_coin(1))],
+        vec![Deposit(uosmo_info.to_coin(1)), withdraw(uosmo_info.to_action_coin(1))],
         &[uosmo_info.to_coin(1)],
     )
     .unwrap();
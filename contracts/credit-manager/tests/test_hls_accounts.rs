@@ -28,7 +28,7 @@ fn queries_return_the_expected_kind() {
 }
 
 #[test]
-fn more_than_one_debt_does_not_qualify() {
+fn collateral_must_be_correlated_to_every_debt() {
     let atom_info = uatom_info();
     let jake_info = ujake_info();
 
@@ -44,6 +44,7 @@ fn more_than_one_debt_does_not_qualify() {
 
     let account_id = mock.create_hls_account(&user);
 
+    // Borrowed ujake is deposited in the account, but is not correlated to the uatom debt
     let res = mock.update_credit_account(
         &account_id,
         &user,
@@ -58,11 +59,61 @@ fn more_than_one_debt_does_not_qualify() {
     assert_err(
         res,
         ContractError::HLS {
-            reason: "Account has more than one debt denom".to_string(),
+            reason: format!(
+                "{} deposit is not a correlated asset to debt {}",
+                jake_info.denom, atom_info.denom
+            ),
         },
     )
 }
 
+#[test]
+fn multiple_debts_with_correlated_collateral_qualify() {
+    let mut atom_info = uatom_info();
+    let mut jake_info = ujake_info();
+
+    let atom_correlation = HlsAssetType::Coin {
+        denom: atom_info.denom.clone(),
+    };
+    let jake_correlation = HlsAssetType::Coin {
+        denom: jake_info.denom.clone(),
+    };
+    atom_info.hls.as_mut().unwrap().correlations.push(jake_correlation.clone());
+    jake_info.hls.as_mut().unwrap().correlations = vec![atom_correlation, jake_correlation];
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[atom_info.clone(), jake_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_hls_account(&user);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(300)),
+            Borrow(atom_info.to_coin(10)),
+            Borrow(jake_info.to_coin(1)),
+        ],
+        &[atom_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 2);
+
+    let hls_health =
+        mock.query_health(&account_id, AccountKind::HighLeveredStrategy, ActionKind::Default);
+    let default_health = mock.query_health(&account_id, AccountKind::Default, ActionKind::Default);
+    assert!(hls_health.max_ltv_adjusted_collateral > default_health.max_ltv_adjusted_collateral);
+}
+
 #[test]
 fn hls_allows_zero_debts_is_ok() {
     let atom_info = uatom_info();
@@ -0,0 +1,246 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{coins, Addr, Decimal, Uint128};
+use mars_params::types::hls::{HlsAssetType, HlsParamsUnchecked};
+use mars_rover::{
+    error::ContractError,
+    msg::execute::Action::{Borrow, Deposit, Leverage},
+};
+use mars_rover_health_types::HealthError;
+use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
+
+use crate::helpers::{assert_err, uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn target_ltv_must_be_between_zero_and_one() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock =
+        MockEnv::new().set_params(&[osmo_info.clone(), atom_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    for target_ltv in [Decimal::zero(), Decimal::one()] {
+        let res = mock.update_credit_account(
+            &account_id,
+            &user,
+            vec![Leverage {
+                collateral_denom: osmo_info.denom.clone(),
+                debt_denom: atom_info.denom.clone(),
+                target_ltv,
+                slippage: Decimal::percent(1),
+            }],
+            &[],
+        );
+
+        assert_err(res, ContractError::InvalidTargetLtv(target_ltv));
+    }
+}
+
+#[test]
+fn slippage_must_not_exceed_max() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let max_slippage = Decimal::percent(5);
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .max_slippage(max_slippage)
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let slippage = max_slippage + Decimal::from_str("0.000001").unwrap();
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Leverage {
+            collateral_denom: osmo_info.denom,
+            debt_denom: atom_info.denom,
+            target_ltv: Decimal::percent(50),
+            slippage,
+        }],
+        &[],
+    );
+
+    assert_err(
+        res,
+        ContractError::SlippageExceeded {
+            slippage,
+            max_slippage,
+        },
+    )
+}
+
+#[test]
+fn no_amount_if_already_at_target_ltv() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(100_000, osmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    // Collateral value: 25_000 + 1_000 (borrowed atom), debt value: 1_001
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(osmo_info.to_coin(100_000)),
+            Borrow(atom_info.to_coin(1_000)),
+            Leverage {
+                collateral_denom: osmo_info.denom.clone(),
+                debt_denom: atom_info.denom,
+                target_ltv: Decimal::percent(1),
+                slippage: Decimal::zero(),
+            },
+        ],
+        &[osmo_info.to_coin(100_000)],
+    );
+
+    assert_err(res, ContractError::NoAmount)
+}
+
+#[test]
+fn leverage_borrows_and_swaps_into_collateral() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(100_000, osmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(osmo_info.to_coin(100_000)),
+            Leverage {
+                collateral_denom: osmo_info.denom.clone(),
+                debt_denom: atom_info.denom.clone(),
+                target_ltv: Decimal::percent(5),
+                slippage: Decimal::percent(5),
+            },
+        ],
+        &[osmo_info.to_coin(100_000)],
+    )
+    .unwrap();
+
+    // Collateral value: 100_000 * 0.25 = 25_000
+    // borrow value = (0.05 * 25_000 - 0) / (1 - 0.05 * 0.95) = 1312
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 1);
+    let debt = position.debts.first().unwrap();
+    assert_eq!(debt.denom, atom_info.denom);
+    assert_eq!(debt.amount, Uint128::new(1313)); // +1 interest of mock red bank
+
+    // All borrowed coins have been swapped
+    assert_eq!(position.deposits.len(), 1);
+    let deposit = position.deposits.first().unwrap();
+    assert_eq!(deposit.denom, osmo_info.denom);
+    assert_eq!(deposit.amount, Uint128::new(100_000) + MOCK_SWAP_RESULT);
+}
+
+#[test]
+fn leverage_works_for_hls_accounts() {
+    let mut osmo_info = uosmo_info();
+    osmo_info.hls = Some(HlsParamsUnchecked {
+        max_loan_to_value: Decimal::from_str("0.8").unwrap(),
+        liquidation_threshold: Decimal::from_str("0.85").unwrap(),
+        correlations: vec![],
+    });
+    let mut atom_info = uatom_info();
+    atom_info.hls.as_mut().unwrap().correlations.push(HlsAssetType::Coin {
+        denom: osmo_info.denom.clone(),
+    });
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(100_000, osmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_hls_account(&user);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(osmo_info.to_coin(100_000)),
+            Leverage {
+                collateral_denom: osmo_info.denom.clone(),
+                debt_denom: atom_info.denom.clone(),
+                target_ltv: Decimal::percent(5),
+                slippage: Decimal::zero(),
+            },
+        ],
+        &[osmo_info.to_coin(100_000)],
+    )
+    .unwrap();
+
+    // borrow value = (0.05 * 25_000 - 0) / (1 - 0.05) = 1315
+    let position = mock.query_positions(&account_id);
+    let debt = position.debts.first().unwrap();
+    assert_eq!(debt.denom, atom_info.denom);
+    assert_eq!(debt.amount, Uint128::new(1316));
+
+    let deposit = position.deposits.first().unwrap();
+    assert_eq!(deposit.denom, osmo_info.denom);
+    assert_eq!(deposit.amount, Uint128::new(100_000) + MOCK_SWAP_RESULT);
+}
+
+#[test]
+fn hls_collateral_must_have_hls_params() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_hls_account(&user);
+
+    // uosmo has no HLS params, so it can not be used as collateral
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(300)),
+            Leverage {
+                collateral_denom: osmo_info.denom.clone(),
+                debt_denom: atom_info.denom,
+                target_ltv: Decimal::percent(10),
+                slippage: Decimal::zero(),
+            },
+        ],
+        &[atom_info.to_coin(300)],
+    );
+
+    assert_err(res, ContractError::Health(HealthError::MissingHLSParams(osmo_info.denom)))
+}
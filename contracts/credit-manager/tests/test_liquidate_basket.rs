@@ -0,0 +1,256 @@
+use cosmwasm_std::{coins, Addr, Coin, Decimal, Uint128};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    adapters::vault::VaultPositionType,
+    error::ContractError,
+    msg::execute::{
+        Action::{Borrow, Deposit, Liquidate},
+        LiquidateRequest,
+    },
+};
+
+use crate::helpers::{
+    assert_err, get_coin, get_debt, lp_token_info, uatom_info, unlocked_vault_info, uosmo_info,
+    AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn basket_must_be_valid() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let liquidator = Addr::unchecked("liquidator");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone(), lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+    let liquidatee_account_id = mock.create_credit_account(&Addr::unchecked("liquidatee")).unwrap();
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+
+    let cases = vec![
+        (vec![], "no requests".to_string()),
+        (
+            vec![LiquidateRequest::Basket(vec![LiquidateRequest::Deposit(
+                uosmo_info.denom.clone(),
+            )])],
+            "baskets can not be nested".to_string(),
+        ),
+        (
+            vec![
+                LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+                LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+            ],
+            format!("{} deposit requested more than once", uosmo_info.denom),
+        ),
+        (
+            vec![
+                LiquidateRequest::Lend(uatom_info.denom.clone()),
+                LiquidateRequest::Lend(uatom_info.denom.clone()),
+            ],
+            format!("{} released by more than one lend or vault request", uatom_info.denom),
+        ),
+        (
+            vec![
+                LiquidateRequest::Deposit(uatom_info.denom.clone()),
+                LiquidateRequest::Lend(uatom_info.denom.clone()),
+            ],
+            format!(
+                "{} requested as a deposit and released by a lend or vault request",
+                uatom_info.denom
+            ),
+        ),
+        (
+            vec![
+                LiquidateRequest::Vault {
+                    request_vault: vault,
+                    position_type: VaultPositionType::UNLOCKED,
+                },
+                LiquidateRequest::Deposit(lp_token.denom.clone()),
+            ],
+            format!(
+                "{} requested as a deposit and released by a lend or vault request",
+                lp_token.denom
+            ),
+        ),
+    ];
+
+    for (requests, reason) in cases {
+        let res = mock.update_credit_account(
+            &liquidator_account_id,
+            &liquidator,
+            vec![Liquidate {
+                liquidatee_account_id: liquidatee_account_id.clone(),
+                debt_coin: uatom_info.to_coin(10),
+                request: LiquidateRequest::Basket(requests),
+            }],
+            &[],
+        );
+        assert_err(
+            res,
+            ContractError::InvalidLiquidationBasket {
+                reason,
+            },
+        );
+    }
+}
+
+#[test]
+fn debt_is_allocated_across_buckets_in_order() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(3000, uosmo_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: coins(3000, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![Deposit(uosmo_info.to_coin(3000)), Borrow(uatom_info.to_coin(1000))],
+        &[Coin::new(3000, uosmo_info.denom.clone())],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom.clone(),
+        price: Decimal::from_atomics(6u128, 0).unwrap(),
+    });
+
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+
+    let requests = vec![
+        LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+        LiquidateRequest::Deposit(uatom_info.denom.clone()),
+    ];
+    let preview = mock
+        .query_basket_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(200),
+            requests.clone(),
+        )
+        .unwrap();
+
+    let res = mock
+        .update_credit_account(
+            &liquidator_account_id,
+            &liquidator,
+            vec![
+                Deposit(uatom_info.to_coin(200)),
+                Liquidate {
+                    liquidatee_account_id: liquidatee_account_id.clone(),
+                    debt_coin: uatom_info.to_coin(200),
+                    request: LiquidateRequest::Basket(requests),
+                },
+            ],
+            &[uatom_info.to_coin(200)],
+        )
+        .unwrap();
+
+    let attr = |key: &str| {
+        res.events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .filter(|a| a.key == key)
+            .map(|a| a.value.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(attr("action").iter().filter(|a| *a == "liquidate_basket").count(), 1);
+    assert_eq!(attr("coin_liquidated").len(), 1);
+    assert_eq!(attr("coin_debt_repaid"), vec![uatom_info.to_coin(121).to_string()]);
+
+    // The osmo bucket is sized as the single deposit liquidation, where the debt is adjusted down
+    // to 121 by the osmo balance. The account is no longer liquidatable after it, so the atom
+    // bucket is not used.
+    let position = mock.query_positions(&liquidatee_account_id);
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(24));
+    let atom_balance = get_coin(&uatom_info.denom, &position.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(1000));
+
+    let atom_debt = get_debt(&uatom_info.denom, &position.debts);
+    assert_eq!(atom_debt.amount, Uint128::new(880));
+
+    let position = mock.query_positions(&liquidator_account_id);
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(2972));
+    let atom_balance = get_coin(&uatom_info.denom, &position.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(79));
+
+    // The preview allocates the debt the same way
+    assert_eq!(preview.debt_repaid, uatom_info.to_coin(121));
+    assert_eq!(preview.positions.len(), 1);
+    assert_eq!(preview.positions[0].liquidator_request, uosmo_info.to_coin(2972));
+    assert!(preview.profitable);
+}
+
+#[test]
+fn first_bucket_is_sized_as_a_single_liquidation() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(3000, uosmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![Deposit(uosmo_info.to_coin(3000)), Borrow(uatom_info.to_coin(1000))],
+        &[Coin::new(3000, uosmo_info.denom.clone())],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom.clone(),
+        price: Decimal::from_atomics(6u128, 0).unwrap(),
+    });
+
+    let single = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(1000),
+            LiquidateRequest::Deposit(uatom_info.denom.clone()),
+        )
+        .unwrap();
+    let basket = mock
+        .query_basket_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(1000),
+            vec![
+                LiquidateRequest::Deposit(uatom_info.denom.clone()),
+                LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+            ],
+        )
+        .unwrap();
+
+    assert!(single.debt_repaid.amount < Uint128::new(1000));
+    assert_eq!(basket.positions[0], single);
+    assert!(basket.debt_repaid.amount >= single.debt_repaid.amount);
+}
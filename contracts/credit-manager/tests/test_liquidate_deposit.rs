@@ -7,7 +7,7 @@ use mars_rover::{
         ContractError::{AboveMaxLTV, LiquidationNotProfitable, NotLiquidatable},
     },
     msg::execute::{
-        Action::{Borrow, Deposit, EnterVault, Liquidate, Withdraw},
+        Action::{Borrow, Deposit, EnterVault, Liquidate},
         LiquidateRequest,
     },
 };
@@ -15,7 +15,7 @@ use mars_rover_health_types::AccountKind;
 
 use crate::helpers::{
     assert_err, get_coin, get_debt, lp_token_info, uatom_info, ujake_info, unlocked_vault_info,
-    uosmo_info, AccountToFund, MockEnv,
+    uosmo_info, withdraw, AccountToFund, MockEnv,
 };
 
 pub mod helpers;
@@ -477,7 +477,7 @@ fn target_health_factor_reached_after_max_debt_repayed() {
         vec![
             Deposit(uosmo_info.to_coin(3000)),
             Borrow(uatom_info.to_coin(1000)),
-            Withdraw(uatom_info.to_action_coin(400)),
+            withdraw(uatom_info.to_action_coin(400)),
         ],
         &[Coin::new(3000, uosmo_info.denom.clone())],
     )
@@ -0,0 +1,288 @@
+use cosmwasm_std::{coin, coins, Addr, Coin, Decimal, Uint128};
+use cw_utils::PaymentError;
+use mars_mock_oracle::msg::CoinPrice;
+use mars_params::msg::VaultConfigUpdate;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    adapters::vault::{VaultBase, VaultPositionType},
+    error::ContractError,
+    msg::execute::{
+        Action::{Borrow, Deposit, EnterVault},
+        LiquidateRequest,
+    },
+};
+
+use crate::helpers::{
+    assert_err, get_coin, get_debt, lp_token_info, uatom_info, ujake_info, unlocked_vault_info,
+    uosmo_info, withdraw, AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn raises_when_sending_incorrect_funds() {
+    let liquidator = Addr::unchecked("liquidator");
+    let mut mock = MockEnv::new()
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: vec![coin(12, "abc"), coin(32, "xyz")],
+        })
+        .build()
+        .unwrap();
+
+    let request = LiquidateRequest::Deposit("abc".to_string());
+
+    let res = mock.liquidate_from_wallet(&liquidator, "123", request.clone(), &[]);
+    assert_err(res, ContractError::Payment(PaymentError::NoFunds {}));
+
+    let res = mock.liquidate_from_wallet(
+        &liquidator,
+        "123",
+        request,
+        &[coin(12, "abc"), coin(32, "xyz")],
+    );
+    assert_err(res, ContractError::Payment(PaymentError::MultipleDenoms {}));
+}
+
+#[test]
+fn liquidate_deposit_from_wallet() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let ujake_info = ujake_info();
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .target_health_factor(Decimal::from_atomics(12u128, 1).unwrap())
+        .set_params(&[uosmo_info.clone(), uatom_info.clone(), ujake_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(3000, uosmo_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: coins(3000, ujake_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![
+            Deposit(uosmo_info.to_coin(3000)),
+            Borrow(uatom_info.to_coin(1000)),
+            Borrow(ujake_info.to_coin(100)),
+        ],
+        &[Coin::new(3000, uosmo_info.denom.clone())],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom,
+        price: Decimal::from_atomics(5u128, 0).unwrap(),
+    });
+
+    // Sending more than the total debt for denom, the extra is refunded
+    mock.liquidate_from_wallet(
+        &liquidator,
+        &liquidatee_account_id,
+        LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+        &[ujake_info.to_coin(150)],
+    )
+    .unwrap();
+
+    // Assert liquidatee's new position
+    let position = mock.query_positions(&liquidatee_account_id);
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(2028));
+    assert_eq!(position.debts.len(), 1);
+    let atom_debt = get_debt("uatom", &position.debts);
+    assert_eq!(atom_debt.amount, Uint128::new(1001));
+
+    // Assert liquidator's wallet
+    let jake_balance = mock.query_balance(&liquidator, &ujake_info.denom);
+    assert_eq!(jake_balance.amount, Uint128::new(3000 - 101));
+    let osmo_balance = mock.query_balance(&liquidator, &uosmo_info.denom);
+    assert_eq!(osmo_balance.amount, Uint128::new(968));
+
+    // Assert rewards-collector's new position
+    let rewards_collector_acc_id = mock.query_rewards_collector_account();
+    let position = mock.query_positions(&rewards_collector_acc_id);
+    assert_eq!(position.deposits.len(), 1);
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(4));
+
+    // Rover holds exactly what is accounted for
+    let rover_balance = mock.query_balance(&mock.rover, &uosmo_info.denom);
+    assert_eq!(rover_balance.amount, Uint128::new(2028 + 4));
+}
+
+#[test]
+fn liquidate_vault_from_wallet() {
+    let lp_token = lp_token_info();
+    let ujake = ujake_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let liquidatee = Addr::unchecked("liquidatee");
+    let liquidator = Addr::unchecked("liquidator");
+
+    let mut mock = MockEnv::new()
+        .set_params(&[lp_token.clone(), ujake.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: vec![lp_token.to_coin(300)],
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: vec![ujake.to_coin(50)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(200),
+            },
+            Borrow(ujake.to_coin(175)),
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: ujake.denom.clone(),
+        price: Decimal::from_atomics(18u128, 0).unwrap(),
+    });
+
+    let res = mock
+        .liquidate_from_wallet(
+            &liquidator,
+            &liquidatee_account_id,
+            LiquidateRequest::Vault {
+                request_vault: VaultBase::new(mock.get_vault(&leverage_vault).address),
+                position_type: VaultPositionType::UNLOCKED,
+            },
+            &[ujake.to_coin(50)],
+        )
+        .unwrap();
+
+    // The vault is called under a global guard, released once the liquidation is done
+    let guard_removed = res
+        .events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .any(|a| a.key == "action" && a.value == "remove_reentrancy_guard");
+    assert!(guard_removed);
+    mock.update_credit_account(&liquidatee_account_id, &liquidatee, vec![], &[]).unwrap();
+
+    // Assert liquidatee's new position
+    let position = mock.query_positions(&liquidatee_account_id);
+    let vault_balance = position.vaults.first().unwrap().amount.unlocked();
+    assert_eq!(vault_balance, Uint128::new(525_000));
+    let jake_debt = get_debt(&ujake.denom, &position.debts);
+    assert_eq!(jake_debt.amount, Uint128::new(126));
+
+    // Underlying of the vault coins is sent to the liquidator's wallet
+    let lp_balance = mock.query_balance(&liquidator, &lp_token.denom);
+    assert_eq!(lp_balance.amount, Uint128::new(94));
+    let jake_balance = mock.query_balance(&liquidator, &ujake.denom);
+    assert_eq!(jake_balance.amount, Uint128::zero());
+
+    // Assert rewards-collector's new position
+    let rewards_collector_acc_id = mock.query_rewards_collector_account();
+    let position = mock.query_positions(&rewards_collector_acc_id);
+    let lp = get_coin(&lp_token.denom, &position.deposits);
+    assert_eq!(lp.amount, Uint128::new(1));
+}
+
+#[test]
+fn liquidate_vault_of_debt_denom_from_wallet() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let liquidatee = Addr::unchecked("liquidatee");
+    let liquidator = Addr::unchecked("liquidator");
+
+    let mut mock = MockEnv::new()
+        .set_params(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: vec![lp_token.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    // The vault's base token is also the debt denom
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(200),
+            },
+            Borrow(lp_token.to_coin(100)),
+            withdraw(lp_token.to_action_coin(100)),
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    let mut config = mock.query_vault_params(&vault.address);
+    config.max_loan_to_value = Decimal::percent(40);
+    config.liquidation_threshold = Decimal::percent(45);
+    mock.update_vault_params(VaultConfigUpdate::AddOrUpdate {
+        config: config.into(),
+    });
+
+    let request = LiquidateRequest::Vault {
+        request_vault: VaultBase::new(vault.address.clone()),
+        position_type: VaultPositionType::UNLOCKED,
+    };
+    let preview = mock
+        .query_liquidation_preview(&liquidatee_account_id, lp_token.to_coin(150), request.clone())
+        .unwrap();
+
+    // More is sent than the debt repaid, the extra is refunded in the same denom
+    mock.liquidate_from_wallet(
+        &liquidator,
+        &liquidatee_account_id,
+        request,
+        &[lp_token.to_coin(150)],
+    )
+    .unwrap();
+    assert!(preview.debt_repaid.amount < Uint128::new(150));
+
+    // The refund does not count as withdrawn from the vault
+    let lp_balance = mock.query_balance(&liquidator, &lp_token.denom);
+    assert_eq!(
+        lp_balance.amount,
+        Uint128::new(300) - preview.debt_repaid.amount + preview.liquidator_request.amount
+    );
+
+    let rewards_collector_acc_id = mock.query_rewards_collector_account();
+    let position = mock.query_positions(&rewards_collector_acc_id);
+    let lp = get_coin(&lp_token.denom, &position.deposits);
+    assert_eq!(lp, preview.protocol_fee);
+}
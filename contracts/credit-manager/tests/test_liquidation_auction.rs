@@ -0,0 +1,265 @@
+use cosmwasm_std::{coins, Addr, Coin, Decimal};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::msg::{
+    execute::{
+        Action::{Borrow, Deposit, Liquidate},
+        LiquidateRequest,
+    },
+    instantiate::{ConfigUpdates, LiquidationAuction, LiquidationAuctionUpdate},
+    query::LiquidationPreviewResponse,
+};
+
+use crate::helpers::{get_coin, uatom_info, uosmo_info, withdraw, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn bonus_rises_with_blocks_since_liquidatable() {
+    let (mut mock, liquidatee_account_id) = setup_liquidatable_account();
+
+    let full_bonus = preview(&mock, &liquidatee_account_id);
+
+    set_liquidation_auction(&mut mock, 100);
+
+    // Not yet recorded, the auction starts at the current block
+    let start = preview(&mock, &liquidatee_account_id);
+
+    mock.update_liquidatable_since(&Addr::unchecked("anyone"), &liquidatee_account_id).unwrap();
+    let recorded_at = mock.app.block_info().height;
+    assert_eq!(mock.query_liquidatable_since(&liquidatee_account_id), Some(recorded_at));
+
+    mock.app.update_block(|block| block.height += 50);
+    let halfway = preview(&mock, &liquidatee_account_id);
+
+    mock.app.update_block(|block| block.height += 50);
+    let end = preview(&mock, &liquidatee_account_id);
+
+    mock.app.update_block(|block| block.height += 50);
+    let after_end = preview(&mock, &liquidatee_account_id);
+
+    assert_eq!(start.debt_repaid, full_bonus.debt_repaid);
+    assert_eq!(halfway.debt_repaid, full_bonus.debt_repaid);
+    assert!(start.liquidatee_request.amount < halfway.liquidatee_request.amount);
+    assert!(halfway.liquidatee_request.amount < end.liquidatee_request.amount);
+    assert!(start.liquidator_request.amount < halfway.liquidator_request.amount);
+    assert!(halfway.liquidator_request.amount < end.liquidator_request.amount);
+    assert_eq!(end, full_bonus);
+    assert_eq!(after_end, full_bonus);
+
+    // Recording again does not restart the auction
+    mock.update_liquidatable_since(&Addr::unchecked("anyone"), &liquidatee_account_id).unwrap();
+    assert_eq!(mock.query_liquidatable_since(&liquidatee_account_id), Some(recorded_at));
+}
+
+#[test]
+fn zero_blocks_pays_full_bonus() {
+    let (mut mock, liquidatee_account_id) = setup_liquidatable_account();
+
+    let full_bonus = preview(&mock, &liquidatee_account_id);
+
+    set_liquidation_auction(&mut mock, 0);
+
+    assert_eq!(preview(&mock, &liquidatee_account_id), full_bonus);
+}
+
+#[test]
+fn auction_can_be_disabled() {
+    let (mut mock, liquidatee_account_id) = setup_liquidatable_account();
+
+    let full_bonus = preview(&mock, &liquidatee_account_id);
+
+    set_liquidation_auction(&mut mock, 100);
+    assert_ne!(preview(&mock, &liquidatee_account_id), full_bonus);
+
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            liquidation_auction: Some(LiquidationAuctionUpdate::Disable),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(mock.query_config().liquidation_auction, None);
+    assert_eq!(preview(&mock, &liquidatee_account_id), full_bonus);
+}
+
+#[test]
+fn liquidation_records_block_until_account_recovers() {
+    let (mut mock, liquidatee_account_id) = setup_liquidatable_account();
+    set_liquidation_auction(&mut mock, 100);
+
+    let uatom_info = uatom_info();
+    let uosmo_info = uosmo_info();
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+
+    assert_eq!(mock.query_liquidatable_since(&liquidatee_account_id), None);
+
+    let expected = preview(&mock, &liquidatee_account_id);
+
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Liquidate {
+                liquidatee_account_id: liquidatee_account_id.clone(),
+                debt_coin: uatom_info.to_coin(100),
+                request: LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+            },
+        ],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    // Liquidated at the minimum bonus, as the auction started in this block
+    let position = mock.query_positions(&liquidator_account_id);
+    assert_eq!(get_coin(&uosmo_info.denom, &position.deposits), expected.liquidator_request);
+    assert_eq!(
+        mock.query_liquidatable_since(&liquidatee_account_id),
+        Some(mock.app.block_info().height)
+    );
+
+    // Still liquidatable, the record is kept
+    mock.update_liquidatable_since(&Addr::unchecked("anyone"), &liquidatee_account_id).unwrap();
+    assert!(mock.query_liquidatable_since(&liquidatee_account_id).is_some());
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom,
+        price: Decimal::one(),
+    });
+
+    mock.update_liquidatable_since(&Addr::unchecked("anyone"), &liquidatee_account_id).unwrap();
+    assert_eq!(mock.query_liquidatable_since(&liquidatee_account_id), None);
+}
+
+#[test]
+fn liquidation_restoring_health_clears_record() {
+    let (mut mock, liquidatee_account_id) = setup_liquidatable_account();
+
+    // Barely liquidatable, a small liquidation restores the account's health
+    let uatom_info = uatom_info();
+    let uosmo_info = uosmo_info();
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom.clone(),
+        price: Decimal::from_atomics(117u128, 2).unwrap(),
+    });
+
+    mock.update_liquidatable_since(&Addr::unchecked("anyone"), &liquidatee_account_id).unwrap();
+    assert!(mock.query_liquidatable_since(&liquidatee_account_id).is_some());
+
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Liquidate {
+                liquidatee_account_id: liquidatee_account_id.clone(),
+                debt_coin: uatom_info.to_coin(100),
+                request: LiquidateRequest::Deposit(uosmo_info.denom),
+            },
+        ],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    assert_eq!(mock.query_liquidatable_since(&liquidatee_account_id), None);
+}
+
+#[test]
+fn healthy_account_after_actions_clears_record() {
+    let (mut mock, liquidatee_account_id) = setup_liquidatable_account();
+
+    mock.update_liquidatable_since(&Addr::unchecked("anyone"), &liquidatee_account_id).unwrap();
+    assert!(mock.query_liquidatable_since(&liquidatee_account_id).is_some());
+
+    let uatom_info = uatom_info();
+    let uosmo_info = uosmo_info();
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom,
+        price: Decimal::one(),
+    });
+
+    let liquidatee = Addr::unchecked("liquidatee");
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![withdraw(uosmo_info.to_action_coin(1))],
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(mock.query_liquidatable_since(&liquidatee_account_id), None);
+}
+
+/// The liquidatee's health factor falls far enough for the bonus to be at `max_lb`
+fn setup_liquidatable_account() -> (MockEnv, String) {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(3000, uosmo_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: Addr::unchecked("liquidator"),
+            funds: coins(100, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![
+            Deposit(uosmo_info.to_coin(3000)),
+            Borrow(uatom_info.to_coin(500)),
+            withdraw(uatom_info.to_action_coin(500)),
+        ],
+        &[Coin::new(3000, uosmo_info.denom)],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom,
+        price: Decimal::from_atomics(13u128, 1).unwrap(),
+    });
+
+    (mock, liquidatee_account_id)
+}
+
+fn set_liquidation_auction(mock: &mut MockEnv, blocks_to_max_bonus: u64) {
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            liquidation_auction: Some(LiquidationAuctionUpdate::Set(LiquidationAuction {
+                blocks_to_max_bonus,
+            })),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+fn preview(mock: &MockEnv, liquidatee_account_id: &str) -> LiquidationPreviewResponse {
+    mock.query_liquidation_preview(
+        liquidatee_account_id,
+        uatom_info().to_coin(100),
+        LiquidateRequest::Deposit(uosmo_info().denom),
+    )
+    .unwrap()
+}
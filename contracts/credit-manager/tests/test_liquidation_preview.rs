@@ -0,0 +1,319 @@
+use cosmwasm_std::{coins, Addr, Coin, Decimal, Uint128};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    adapters::vault::{VaultBase, VaultPositionType},
+    msg::execute::{
+        Action::{Borrow, Deposit, EnterVault, Liquidate},
+        LiquidateRequest,
+    },
+};
+
+use crate::helpers::{
+    get_coin, lp_token_info, uatom_info, ujake_info, unlocked_vault_info, uosmo_info,
+    AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn preview_errors_if_not_liquidatable() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![Deposit(uosmo_info.to_coin(300)), Borrow(uatom_info.to_coin(50))],
+        &[Coin::new(300, uosmo_info.denom.clone())],
+    )
+    .unwrap();
+
+    let err = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(10),
+            LiquidateRequest::Deposit(uosmo_info.denom),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("is not a liquidatable credit account"));
+}
+
+#[test]
+fn preview_errors_if_nothing_lent() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![Deposit(uosmo_info.to_coin(300)), Borrow(uatom_info.to_coin(50))],
+        &[Coin::new(300, uosmo_info.denom.clone())],
+    )
+    .unwrap();
+
+    let err = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(10),
+            LiquidateRequest::Lend(uosmo_info.denom),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("Nothing lent to reclaim"));
+}
+
+#[test]
+fn preview_matches_deposit_liquidation() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .target_health_factor(Decimal::from_atomics(12u128, 1).unwrap())
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(3000, uosmo_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: coins(3000, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![Deposit(uosmo_info.to_coin(3000)), Borrow(uatom_info.to_coin(1000))],
+        &[Coin::new(3000, uosmo_info.denom.clone())],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom.clone(),
+        price: Decimal::from_atomics(59u128, 1).unwrap(),
+    });
+
+    let preview = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(100),
+            LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+        )
+        .unwrap();
+    assert_eq!(preview.debt_repaid, uatom_info.to_coin(100));
+    assert_eq!(preview.liquidator_request, uosmo_info.to_coin(2388));
+    assert_eq!(preview.liquidatee_request, uosmo_info.to_coin(2392));
+    assert_eq!(preview.protocol_fee, uosmo_info.to_coin(4));
+    assert!(preview.profitable);
+
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Liquidate {
+                liquidatee_account_id: liquidatee_account_id.clone(),
+                debt_coin: uatom_info.to_coin(100),
+                request: LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+            },
+        ],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&liquidatee_account_id);
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(3000) - preview.liquidatee_request.amount);
+
+    let position = mock.query_positions(&liquidator_account_id);
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    assert_eq!(osmo_balance, preview.liquidator_request);
+
+    let rewards_collector_acc_id = mock.query_rewards_collector_account();
+    let position = mock.query_positions(&rewards_collector_acc_id);
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    assert_eq!(osmo_balance, preview.protocol_fee);
+}
+
+#[test]
+fn preview_reports_unprofitable_liquidation() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let ujake_info = ujake_info();
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone(), ujake_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Borrow(uatom_info.to_coin(100)),
+            Borrow(ujake_info.to_coin(25)),
+        ],
+        &[Coin::new(300, uosmo_info.denom.clone())],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: ujake_info.denom,
+        price: Decimal::from_atomics(100u128, 0).unwrap(),
+    });
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uosmo_info.denom.clone(),
+        price: Decimal::from_atomics(2u128, 0).unwrap(),
+    });
+
+    // Liquidating this would fail with LiquidationNotProfitable, the preview still succeeds
+    let preview = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(5),
+            LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+        )
+        .unwrap();
+    assert_eq!(preview.debt_repaid, uatom_info.to_coin(5));
+    assert_eq!(preview.liquidator_request, uosmo_info.to_coin(2));
+    assert!(!preview.profitable);
+}
+
+#[test]
+fn preview_matches_vault_liquidation() {
+    let lp_token = lp_token_info();
+    let ujake = ujake_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let liquidatee = Addr::unchecked("liquidatee");
+    let liquidator = Addr::unchecked("liquidator");
+
+    let mut mock = MockEnv::new()
+        .set_params(&[lp_token.clone(), ujake.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: vec![lp_token.to_coin(300)],
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: vec![ujake.to_coin(50)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(200),
+            },
+            Borrow(ujake.to_coin(175)),
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: ujake.denom.clone(),
+        price: Decimal::from_atomics(18u128, 0).unwrap(),
+    });
+
+    let position = mock.query_positions(&liquidatee_account_id);
+    let prev_vault_balance = position.vaults.first().unwrap().amount.unlocked();
+
+    let request = LiquidateRequest::Vault {
+        request_vault: VaultBase::new(mock.get_vault(&leverage_vault).address),
+        position_type: VaultPositionType::UNLOCKED,
+    };
+    let preview = mock
+        .query_liquidation_preview(&liquidatee_account_id, ujake.to_coin(50), request.clone())
+        .unwrap();
+    assert_eq!(preview.debt_repaid, ujake.to_coin(50));
+    assert!(preview.profitable);
+
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![
+            Deposit(ujake.to_coin(50)),
+            Liquidate {
+                liquidatee_account_id: liquidatee_account_id.clone(),
+                debt_coin: ujake.to_coin(50),
+                request,
+            },
+        ],
+        &[ujake.to_coin(50)],
+    )
+    .unwrap();
+
+    // The preview is in the underlying coins the vault coins taken redeem for
+    let position = mock.query_positions(&liquidatee_account_id);
+    let vault_balance = position.vaults.first().unwrap().amount.unlocked();
+    let vault = mock.get_vault(&leverage_vault);
+    assert_eq!(
+        preview.liquidatee_request,
+        lp_token
+            .to_coin(mock.query_preview_redeem(&vault, prev_vault_balance - vault_balance).u128())
+    );
+    assert_eq!(
+        preview.protocol_fee.amount,
+        preview.liquidatee_request.amount - preview.liquidator_request.amount
+    );
+
+    // Liquidator receives the underlying coins less the protocol fee
+    let position = mock.query_positions(&liquidator_account_id);
+    let lp_balance = get_coin(&lp_token.denom, &position.deposits);
+    assert_eq!(lp_balance, preview.liquidator_request);
+
+    let rewards_collector_acc_id = mock.query_rewards_collector_account();
+    let position = mock.query_positions(&rewards_collector_acc_id);
+    let lp_balance = get_coin(&lp_token.denom, &position.deposits);
+    assert_eq!(lp_balance, preview.protocol_fee);
+}
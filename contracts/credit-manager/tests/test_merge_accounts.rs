@@ -0,0 +1,420 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{coins, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use mars_account_nft_types::{msg::ExecuteMsg as NftExecuteMsg, nft_config::NftConfigUpdates};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    error::ContractError,
+    msg::execute::{
+        Action::{Borrow, Deposit, EnterVault, Lend, RequestVaultUnlock},
+        ActionPermission, Comparison, TriggerCondition,
+    },
+};
+use mars_rover_health_types::AccountKind;
+
+use crate::helpers::{
+    assert_err, get_coin, locked_vault_info, lp_token_info, uatom_info, ujake_info, uosmo_info,
+    withdraw, AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn sender_must_own_both_accounts() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let user = Addr::unchecked("user");
+    let from_account_id = mock.create_credit_account(&user).unwrap();
+    let other_account_id = mock.create_credit_account(&Addr::unchecked("other")).unwrap();
+
+    let res = mock.merge_accounts(&user, &from_account_id, &other_account_id, false);
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: user.to_string(),
+            account_id: other_account_id.clone(),
+        },
+    );
+
+    let res = mock.merge_accounts(&user, &other_account_id, &from_account_id, false);
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: user.to_string(),
+            account_id: other_account_id,
+        },
+    );
+}
+
+#[test]
+fn can_not_merge_into_self() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let user = Addr::unchecked("user");
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.merge_accounts(&user, &account_id, &account_id, false);
+    assert_err(
+        res,
+        ContractError::MergeIntoSelf {
+            account_id,
+        },
+    );
+}
+
+#[test]
+fn all_positions_are_moved() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = locked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone(), lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(400), uatom_info.to_coin(100), lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+
+    let from_account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &from_account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(400)),
+            Deposit(lp_token.to_coin(200)),
+            Borrow(uatom_info.to_coin(50)),
+            Lend(uosmo_info.to_action_coin(100)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(23),
+            },
+            RequestVaultUnlock {
+                vault,
+                amount: Uint128::new(100),
+            },
+        ],
+        &[uosmo_info.to_coin(400), lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    let into_account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &into_account_id,
+        &user,
+        vec![Deposit(uatom_info.to_coin(100))],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    let from_before = mock.query_positions(&from_account_id);
+
+    mock.merge_accounts(&user, &from_account_id, &into_account_id, false).unwrap();
+
+    let from_after = mock.query_positions(&from_account_id);
+    assert!(from_after.deposits.is_empty());
+    assert!(from_after.debts.is_empty());
+    assert!(from_after.lends.is_empty());
+    assert!(from_after.vaults.is_empty());
+
+    let into_after = mock.query_positions(&into_account_id);
+    assert_eq!(into_after.deposits.len(), 3);
+    assert_eq!(get_coin(&uosmo_info.denom, &into_after.deposits), uosmo_info.to_coin(300));
+    assert_eq!(get_coin(&uatom_info.denom, &into_after.deposits), uatom_info.to_coin(150));
+    assert_eq!(get_coin(&lp_token.denom, &into_after.deposits), lp_token.to_coin(177));
+    assert_eq!(into_after.debts, from_before.debts);
+    assert_eq!(into_after.lends, from_before.lends);
+    assert_eq!(into_after.vaults.len(), 1);
+    assert_eq!(
+        into_after.vaults.first().unwrap().amount,
+        from_before.vaults.first().unwrap().amount
+    );
+}
+
+#[test]
+fn unlocking_entries_count_towards_max() {
+    let lp_token = lp_token_info();
+    let leverage_vault = locked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .max_unlocking_positions(1)
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+
+    let from_account_id = mock.create_credit_account(&user).unwrap();
+    let into_account_id = mock.create_credit_account(&user).unwrap();
+    for account_id in [&from_account_id, &into_account_id] {
+        mock.update_credit_account(
+            account_id,
+            &user,
+            vec![
+                Deposit(lp_token.to_coin(100)),
+                EnterVault {
+                    vault: vault.clone(),
+                    coin: lp_token.to_action_coin(23),
+                },
+                RequestVaultUnlock {
+                    vault: vault.clone(),
+                    amount: Uint128::new(100),
+                },
+            ],
+            &[lp_token.to_coin(100)],
+        )
+        .unwrap();
+    }
+
+    let res = mock.merge_accounts(&user, &from_account_id, &into_account_id, false);
+    assert_err(
+        res,
+        ContractError::ExceedsMaxUnlockingPositions {
+            new_amount: Uint128::new(2),
+            maximum: Uint128::one(),
+        },
+    );
+}
+
+#[test]
+fn merged_hls_account_must_follow_hls_rules() {
+    let uatom_info = uatom_info();
+    let ujake_info = ujake_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone(), ujake_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(100), ujake_info.to_coin(100)],
+        })
+        .build()
+        .unwrap();
+
+    let from_account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &from_account_id,
+        &user,
+        vec![Deposit(ujake_info.to_coin(100))],
+        &[ujake_info.to_coin(100)],
+    )
+    .unwrap();
+
+    let into_account_id = mock.create_hls_account(&user);
+    mock.update_credit_account(
+        &into_account_id,
+        &user,
+        vec![Deposit(uatom_info.to_coin(100)), Borrow(uatom_info.to_coin(50))],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    let res = mock.merge_accounts(&user, &from_account_id, &into_account_id, false);
+    assert_err(
+        res,
+        ContractError::HLS {
+            reason: format!(
+                "{} deposit is not a correlated asset to debt {}",
+                ujake_info.denom, uatom_info.denom
+            ),
+        },
+    );
+}
+
+#[test]
+fn merged_account_must_be_healthy() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300), uatom_info.to_coin(10)],
+        })
+        .build()
+        .unwrap();
+
+    let from_account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &from_account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Borrow(uatom_info.to_coin(50)),
+            withdraw(uatom_info.to_action_coin(50)),
+        ],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let into_account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &into_account_id,
+        &user,
+        vec![Deposit(uatom_info.to_coin(10))],
+        &[uatom_info.to_coin(10)],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Default,
+        denom: uatom_info.denom,
+        price: Decimal::from_atomics(13u128, 1).unwrap(),
+    });
+
+    let err: ContractError = mock
+        .merge_accounts(&user, &from_account_id, &into_account_id, false)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AboveMaxLTV { .. }));
+}
+
+#[test]
+fn emptied_account_can_be_burned() {
+    let uosmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_nft_config(
+        &owner,
+        Some(NftConfigUpdates {
+            max_value_for_burn: None,
+            health_contract_addr: Some(mock.health_contract.clone().address().to_string()),
+            credit_manager_contract_addr: Some(mock.rover.to_string()),
+        }),
+        None,
+    )
+    .unwrap();
+
+    let from_account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &from_account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300))],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+    let into_account_id = mock.create_credit_account(&user).unwrap();
+
+    let nft_contract = Addr::unchecked(mock.query_config().account_nft.unwrap());
+    mock.app
+        .execute_contract(
+            user.clone(),
+            nft_contract,
+            &NftExecuteMsg::Approve {
+                spender: mock.rover.to_string(),
+                token_id: from_account_id.clone(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    mock.merge_accounts(&user, &from_account_id, &into_account_id, true).unwrap();
+
+    let accounts = mock.query_accounts(user.as_str(), None, None);
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts.first().unwrap().id, into_account_id);
+    assert_eq!(accounts.first().unwrap().kind, AccountKind::Default);
+
+    let position = mock.query_positions(&into_account_id);
+    assert_eq!(position.deposits, vec![uosmo_info.to_coin(300)]);
+}
+
+#[test]
+fn burned_account_records_are_removed() {
+    let uosmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_nft_config(
+        &owner,
+        Some(NftConfigUpdates {
+            max_value_for_burn: None,
+            health_contract_addr: Some(mock.health_contract.clone().address().to_string()),
+            credit_manager_contract_addr: Some(mock.rover.to_string()),
+        }),
+        None,
+    )
+    .unwrap();
+
+    let from_account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &from_account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300))],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+    let into_account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.create_trigger_order(
+        &from_account_id,
+        &user,
+        vec![withdraw(uosmo_info.to_action_coin(10))],
+        TriggerCondition::OraclePrice {
+            denom: uosmo_info.denom.clone(),
+            comparison: Comparison::LessThan,
+            price: Decimal::from_str("0.2").unwrap(),
+        },
+    )
+    .unwrap();
+    mock.grant_permission(
+        &from_account_id,
+        &user,
+        &Addr::unchecked("delegate"),
+        vec![ActionPermission::Deposit],
+        None,
+    )
+    .unwrap();
+
+    let nft_contract = Addr::unchecked(mock.query_config().account_nft.unwrap());
+    mock.app
+        .execute_contract(
+            user.clone(),
+            nft_contract,
+            &NftExecuteMsg::Approve {
+                spender: mock.rover.to_string(),
+                token_id: from_account_id.clone(),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    mock.merge_accounts(&user, &from_account_id, &into_account_id, true).unwrap();
+
+    assert!(mock.query_trigger_orders(&from_account_id, None, None).is_empty());
+    assert!(mock.query_delegates(&from_account_id, None, None).is_empty());
+}
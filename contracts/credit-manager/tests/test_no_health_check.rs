@@ -3,12 +3,14 @@ use mars_red_bank_types::oracle::ActionKind;
 use mars_rover::{
     error::ContractError,
     msg::execute::{
-        Action::{Borrow, Deposit, Repay, Withdraw},
+        Action::{Borrow, Deposit, Repay},
         ActionAmount, ActionCoin,
     },
 };
 
-use crate::helpers::{assert_err, get_coin, get_debt, uosmo_info, AccountToFund, MockEnv};
+use crate::helpers::{
+    assert_err, get_coin, get_debt, uosmo_info, withdraw, AccountToFund, MockEnv,
+};
 
 pub mod helpers;
 
@@ -115,7 +117,7 @@ fn deposit_and_repay_works_without_hf_check() {
                 recipient_account_id: None,
                 coin: coin_info.to_action_coin(12),
             },
-            Withdraw(ActionCoin {
+            withdraw(ActionCoin {
                 denom: coin_info.denom.clone(),
                 amount: ActionAmount::AccountBalance,
             }),
@@ -183,7 +185,7 @@ fn withdraw_works_without_hf_check_if_no_debt() {
     let res = mock.update_credit_account(
         &account_id,
         &user,
-        vec![Withdraw(ActionCoin {
+        vec![withdraw(ActionCoin {
             denom: coin_info.denom.clone(),
             amount: ActionAmount::AccountBalance,
         })],
@@ -209,7 +211,7 @@ fn withdraw_works_without_hf_check_if_no_debt() {
     mock.update_credit_account(
         &account_id,
         &user,
-        vec![Withdraw(ActionCoin {
+        vec![withdraw(ActionCoin {
             denom: coin_info.denom.clone(),
             amount: ActionAmount::AccountBalance,
         })],
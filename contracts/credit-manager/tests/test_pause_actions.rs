@@ -0,0 +1,411 @@
+use cosmwasm_std::{Addr, Decimal};
+use mars_owner::OwnerError::{NotEmergencyOwner, NotOwner};
+use mars_rover::{
+    error::{ContractError, ContractError::Owner},
+    msg::execute::{
+        Action::{
+            Borrow, ClosePosition, Deposit, EnterVault, Lend, Leverage, Repay, RepayFromCollateral,
+        },
+        ActionPause, PausableAction,
+    },
+};
+
+use crate::helpers::{
+    assert_err, lp_token_info, uatom_info, unlocked_vault_info, uosmo_info, withdraw,
+    AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn only_emergency_owner_can_pause() {
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let mut mock = MockEnv::new().emergency_owner(&emergency_owner).build().unwrap();
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+
+    let res = mock.pause_action(&owner, borrow_pause(None));
+    assert_err(res, Owner(NotEmergencyOwner {}));
+
+    mock.pause_action(&emergency_owner, borrow_pause(None)).unwrap();
+    mock.pause_action(&emergency_owner, borrow_pause(None)).unwrap();
+    assert_eq!(mock.query_paused_actions(), vec![borrow_pause(None)]);
+}
+
+#[test]
+fn only_owner_can_unpause() {
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let mut mock = MockEnv::new().emergency_owner(&emergency_owner).build().unwrap();
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+
+    mock.pause_action(&emergency_owner, borrow_pause(None)).unwrap();
+
+    let res = mock.unpause_action(&emergency_owner, borrow_pause(None));
+    assert_err(res, Owner(NotOwner {}));
+
+    mock.unpause_action(&owner, borrow_pause(None)).unwrap();
+    assert_eq!(mock.query_paused_actions(), vec![]);
+}
+
+#[test]
+fn paused_denom_only_rejects_that_denom() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .emergency_owner(&emergency_owner)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.pause_action(&emergency_owner, borrow_pause(Some(uatom_info.denom.clone()))).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300)), Borrow(uatom_info.to_coin(10))],
+        &[uosmo_info.to_coin(300)],
+    );
+    assert_err(
+        res,
+        ContractError::ActionPaused {
+            action: PausableAction::Borrow,
+            target: Some(uatom_info.denom),
+        },
+    );
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300)), Borrow(uosmo_info.to_coin(10))],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+}
+
+#[test]
+fn paused_vault_only_rejects_that_vault() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .emergency_owner(&emergency_owner)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.pause_action(
+        &emergency_owner,
+        ActionPause {
+            action: PausableAction::EnterVault,
+            target: Some(vault.address.clone()),
+        },
+    )
+    .unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(100),
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    );
+    assert_err(
+        res,
+        ContractError::ActionPaused {
+            action: PausableAction::EnterVault,
+            target: Some(vault.address),
+        },
+    );
+}
+
+#[test]
+fn deposit_and_repay_stay_available() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .emergency_owner(&emergency_owner)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300), uatom_info.to_coin(10)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Borrow(uatom_info.to_coin(50)),
+            withdraw(uatom_info.to_action_coin(50)),
+        ],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.pause_action(&emergency_owner, borrow_pause(None)).unwrap();
+    mock.pause_action(
+        &emergency_owner,
+        ActionPause {
+            action: PausableAction::Withdraw,
+            target: None,
+        },
+    )
+    .unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![withdraw(uosmo_info.to_action_coin(10))],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::ActionPaused {
+            action: PausableAction::Withdraw,
+            target: None,
+        },
+    );
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(10)),
+            Repay {
+                recipient_account_id: None,
+                coin: uatom_info.to_action_coin(10),
+            },
+        ],
+        &[uatom_info.to_coin(10)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.first().unwrap().amount.u128(), 41);
+}
+
+#[test]
+fn leverage_rejected_if_borrow_paused() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .emergency_owner(&emergency_owner)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.pause_action(&emergency_owner, borrow_pause(Some(uatom_info.denom.clone()))).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Leverage {
+                collateral_denom: uosmo_info.denom,
+                debt_denom: uatom_info.denom.clone(),
+                target_ltv: Decimal::percent(50),
+                slippage: Decimal::percent(1),
+            },
+        ],
+        &[uosmo_info.to_coin(300)],
+    );
+    assert_err(
+        res,
+        ContractError::ActionPaused {
+            action: PausableAction::Borrow,
+            target: Some(uatom_info.denom),
+        },
+    );
+}
+
+#[test]
+fn repay_from_collateral_rejected_if_swap_paused() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .emergency_owner(&emergency_owner)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.pause_action(
+        &emergency_owner,
+        ActionPause {
+            action: PausableAction::SwapExactIn,
+            target: None,
+        },
+    )
+    .unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Borrow(uatom_info.to_coin(10)),
+            RepayFromCollateral {
+                collateral: uosmo_info.to_action_coin(100),
+                debt_denom: uatom_info.denom,
+                slippage: Decimal::percent(1),
+            },
+        ],
+        &[uosmo_info.to_coin(300)],
+    );
+    assert_err(
+        res,
+        ContractError::ActionPaused {
+            action: PausableAction::SwapExactIn,
+            target: None,
+        },
+    );
+}
+
+#[test]
+fn close_position_rejected_if_reclaim_paused() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .emergency_owner(&emergency_owner)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uatom_info.to_coin(300)), Lend(uatom_info.to_action_coin(100))],
+        &[uatom_info.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.pause_action(
+        &emergency_owner,
+        ActionPause {
+            action: PausableAction::Reclaim,
+            target: Some(uatom_info.denom.clone()),
+        },
+    )
+    .unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ClosePosition {
+            target_denom: uosmo_info.denom,
+            slippage: Decimal::percent(1),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::ActionPaused {
+            action: PausableAction::Reclaim,
+            target: Some(uatom_info.denom),
+        },
+    );
+}
+
+#[test]
+fn merge_rejected_if_transfer_paused() {
+    let uosmo_info = uosmo_info();
+
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .emergency_owner(&emergency_owner)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let from_account_id = mock.create_credit_account(&user).unwrap();
+    let into_account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &from_account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300))],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.pause_action(
+        &emergency_owner,
+        ActionPause {
+            action: PausableAction::TransferToAccount,
+            target: Some(uosmo_info.denom.clone()),
+        },
+    )
+    .unwrap();
+
+    let res = mock.merge_accounts(&user, &from_account_id, &into_account_id, false);
+    assert_err(
+        res,
+        ContractError::ActionPaused {
+            action: PausableAction::TransferToAccount,
+            target: Some(uosmo_info.denom.clone()),
+        },
+    );
+    assert_eq!(mock.query_positions(&from_account_id).deposits, vec![uosmo_info.to_coin(300)]);
+}
+
+fn borrow_pause(target: Option<String>) -> ActionPause {
+    ActionPause {
+        action: PausableAction::Borrow,
+        target,
+    }
+}
@@ -5,6 +5,7 @@ use mars_rover::{
         Action::{Deposit, EnterVault},
         CallbackMsg,
     },
+    reentrancy_guard::GuardScope,
 };
 
 use crate::helpers::{assert_err, lp_token_info, unlocked_vault_info, AccountToFund, MockEnv};
@@ -54,14 +55,96 @@ fn only_credit_manager_can_remove_guard() {
     let mut mock = MockEnv::new().build().unwrap();
     let external_user = Addr::unchecked("external_user");
 
-    let res = mock.execute_callback(&external_user, CallbackMsg::RemoveReentrancyGuard {});
+    let res = mock.execute_callback(
+        &external_user,
+        CallbackMsg::RemoveReentrancyGuard {
+            scope: GuardScope::Global,
+        },
+    );
     assert_err(res, ContractError::ExternalInvocation);
 }
 
 #[test]
 fn removing_while_inactive() {
     let mut mock = MockEnv::new().build().unwrap();
-    let res = mock.execute_callback(&mock.rover.clone(), CallbackMsg::RemoveReentrancyGuard {});
+    let res = mock.execute_callback(
+        &mock.rover.clone(),
+        CallbackMsg::RemoveReentrancyGuard {
+            scope: GuardScope::Global,
+        },
+    );
+    assert_err(
+        res,
+        ContractError::ReentrancyGuard("Invalid reentrancy guard state transition".to_string()),
+    );
+
+    let res = mock.execute_callback(
+        &mock.rover.clone(),
+        CallbackMsg::RemoveReentrancyGuard {
+            scope: GuardScope::Account("1".to_string()),
+        },
+    );
+    assert_err(
+        res,
+        ContractError::ReentrancyGuard("Invalid reentrancy guard state transition".to_string()),
+    );
+}
+
+#[test]
+fn account_and_global_locks_are_released() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    // Locks account A alone
+    mock.update_credit_account(
+        &account_id_a,
+        &user,
+        vec![Deposit(lp_token.to_coin(100))],
+        &[lp_token.to_coin(100)],
+    )
+    .unwrap();
+
+    // Calls a vault, so locks globally
+    mock.update_credit_account(
+        &account_id_a,
+        &user,
+        vec![EnterVault {
+            vault,
+            coin: lp_token.to_action_coin(50),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id_b,
+        &user,
+        vec![Deposit(lp_token.to_coin(100))],
+        &[lp_token.to_coin(100)],
+    )
+    .unwrap();
+
+    let res = mock.execute_callback(
+        &mock.rover.clone(),
+        CallbackMsg::RemoveReentrancyGuard {
+            scope: GuardScope::Account(account_id_a),
+        },
+    );
     assert_err(
         res,
         ContractError::ReentrancyGuard("Invalid reentrancy guard state transition".to_string()),
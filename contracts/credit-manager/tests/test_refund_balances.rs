@@ -29,7 +29,9 @@ fn refund_coin_balances_when_balances() {
         vec![
             Deposit(uosmo_info.to_coin(234)),
             Deposit(uatom_info.to_coin(25)),
-            RefundAllCoinBalances {},
+            RefundAllCoinBalances {
+                recipient: None,
+            },
         ],
         &[uosmo_info.to_coin(234), uatom_info.to_coin(25)],
     )
@@ -75,7 +77,9 @@ fn refund_coin_balances_when_no_balances() {
                 vault,
                 coin: lp_token.to_action_coin(200),
             },
-            RefundAllCoinBalances {},
+            RefundAllCoinBalances {
+                recipient: None,
+            },
         ],
         &[lp_token.to_coin(200)],
     )
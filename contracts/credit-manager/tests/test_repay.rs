@@ -5,11 +5,12 @@ use mars_credit_manager::borrow::DEFAULT_DEBT_SHARES_PER_COIN_BORROWED;
 use mars_params::types::asset::LiquidationBonus;
 use mars_rover::{
     error::ContractError,
-    msg::execute::Action::{Borrow, Deposit, Repay, Withdraw},
+    msg::execute::Action::{Borrow, Deposit, Repay},
 };
 
 use crate::helpers::{
-    assert_err, uosmo_info, AccountToFund, CoinInfo, MockEnv, DEFAULT_RED_BANK_COIN_BALANCE,
+    assert_err, uosmo_info, withdraw, AccountToFund, CoinInfo, MockEnv,
+    DEFAULT_RED_BANK_COIN_BALANCE,
 };
 
 pub mod helpers;
@@ -179,7 +180,7 @@ fn raises_when_not_enough_assets_to_repay() {
         vec![
             Deposit(uatom_info.to_coin(300)),
             Borrow(uosmo_info.to_coin(50)),
-            Withdraw(uosmo_info.to_action_coin(10)),
+            withdraw(uosmo_info.to_action_coin(10)),
             Repay {
                 recipient_account_id: None,
                 coin: uosmo_info.to_action_coin(50),
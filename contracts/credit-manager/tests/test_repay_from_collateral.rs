@@ -0,0 +1,290 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{coins, Addr, Decimal, Uint128};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    error::ContractError,
+    msg::execute::{
+        Action::{Borrow, Deposit, RepayFromCollateral},
+        ActionAmount, ActionCoin,
+    },
+};
+use mars_rover_health_types::AccountKind;
+use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
+
+use crate::helpers::{
+    assert_err, get_coin, get_debt, uatom_info, uosmo_info, AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn no_debt_to_repay() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(10_000, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            RepayFromCollateral {
+                collateral: atom_info.to_action_coin(100),
+                debt_denom: osmo_info.denom,
+                slippage: Decimal::percent(1),
+            },
+        ],
+        &[atom_info.to_coin(10_000)],
+    );
+
+    assert_err(res, ContractError::NoDebt)
+}
+
+#[test]
+fn slippage_must_not_exceed_max() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let max_slippage = Decimal::percent(5);
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .max_slippage(max_slippage)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(10_000, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let slippage = max_slippage + Decimal::from_str("0.000001").unwrap();
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            Borrow(osmo_info.to_coin(1_000)),
+            RepayFromCollateral {
+                collateral: atom_info.to_action_coin(100),
+                debt_denom: osmo_info.denom,
+                slippage,
+            },
+        ],
+        &[atom_info.to_coin(10_000)],
+    );
+
+    assert_err(
+        res,
+        ContractError::SlippageExceeded {
+            slippage,
+            max_slippage,
+        },
+    )
+}
+
+#[test]
+fn only_swap_output_is_used_to_repay() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(10_000, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(atom_info.to_coin(10_000)), Borrow(osmo_info.to_coin(2_000))],
+        &[atom_info.to_coin(10_000)],
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![RepayFromCollateral {
+            collateral: atom_info.to_action_coin(100),
+            debt_denom: osmo_info.denom.clone(),
+            slippage: Decimal::percent(1),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 1);
+    let debt = get_debt(&osmo_info.denom, &position.debts);
+    assert_eq!(debt.amount, Uint128::new(2_001) - MOCK_SWAP_RESULT); // +1 interest of mock red bank
+
+    // The borrowed coins are left untouched
+    assert_eq!(position.deposits.len(), 2);
+    assert_eq!(get_coin(&osmo_info.denom, &position.deposits).amount, Uint128::new(2_000));
+    assert_eq!(get_coin(&atom_info.denom, &position.deposits).amount, Uint128::new(9_900));
+}
+
+#[test]
+fn surplus_stays_in_account() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(10_000, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            Borrow(osmo_info.to_coin(1_000)),
+            RepayFromCollateral {
+                collateral: ActionCoin {
+                    denom: atom_info.denom.clone(),
+                    amount: ActionAmount::AccountBalance,
+                },
+                debt_denom: osmo_info.denom.clone(),
+                slippage: Decimal::percent(1),
+            },
+        ],
+        &[atom_info.to_coin(10_000)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 0);
+
+    // 1_000 borrowed + 1_337 swapped - 1_001 repaid
+    assert_eq!(position.deposits.len(), 1);
+    let deposit = get_coin(&osmo_info.denom, &position.deposits);
+    assert_eq!(deposit.amount, Uint128::new(1_000) + MOCK_SWAP_RESULT - Uint128::new(1_001));
+
+    let osmo_balance = mock.query_balance(&mock.rover, &osmo_info.denom);
+    assert_eq!(osmo_balance.amount, deposit.amount);
+}
+
+#[test]
+fn swap_output_must_cover_min_received() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(10_000, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(atom_info.to_coin(10_000)), Borrow(osmo_info.to_coin(2_000))],
+        &[atom_info.to_coin(10_000)],
+    )
+    .unwrap();
+
+    // 10_000 uatom is worth 40_000 uosmo, the mock swapper only returns MOCK_SWAP_RESULT
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![RepayFromCollateral {
+            collateral: atom_info.to_action_coin(10_000),
+            debt_denom: osmo_info.denom.clone(),
+            slippage: Decimal::percent(1),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::SwapOutputTooLow {
+            denom: osmo_info.denom.clone(),
+            received: MOCK_SWAP_RESULT,
+            minimum: Uint128::new(39_600),
+        },
+    );
+
+    let position = mock.query_positions(&account_id);
+    let debt = get_debt(&osmo_info.denom, &position.debts);
+    assert_eq!(debt.amount, Uint128::new(2_001));
+}
+
+#[test]
+fn works_without_hf_check() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(10_000, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(atom_info.to_coin(10_000)), Borrow(osmo_info.to_coin(2_000))],
+        &[atom_info.to_coin(10_000)],
+    )
+    .unwrap();
+
+    // Collateral drops in value, the account is now unhealthy
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Default,
+        denom: atom_info.denom.clone(),
+        price: Decimal::from_str("0.05").unwrap(),
+    });
+    let health = mock.query_health(&account_id, AccountKind::Default, ActionKind::Default);
+    assert!(health.above_max_ltv);
+
+    // Repaying from collateral is still allowed, it is how the account recovers
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![RepayFromCollateral {
+            collateral: atom_info.to_action_coin(100),
+            debt_denom: osmo_info.denom.clone(),
+            slippage: Decimal::percent(1),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    let debt = get_debt(&osmo_info.denom, &position.debts);
+    assert_eq!(debt.amount, Uint128::new(2_001) - MOCK_SWAP_RESULT);
+}
@@ -0,0 +1,176 @@
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use mars_owner::OwnerError::{NotEmergencyOwner, NotOwner};
+use mars_rover::{
+    adapters::oracle::OracleUnchecked,
+    error::{ContractError, ContractError::Owner},
+    msg::{
+        execute::{ActionPause, PausableAction},
+        instantiate::{ConfigUpdates, Role},
+        query::RolesResponseItem,
+    },
+};
+
+use crate::helpers::{assert_err, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn only_owner_can_grant_and_revoke_roles() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let bad_guy = Addr::unchecked("bad_guy");
+
+    let res = mock.grant_role(&bad_guy, bad_guy.as_str(), Role::RiskManager);
+    assert_err(res, Owner(NotOwner {}));
+
+    let res = mock.revoke_role(&bad_guy, bad_guy.as_str(), Role::RiskManager);
+    assert_err(res, Owner(NotOwner {}));
+}
+
+#[test]
+fn roles_can_be_granted_and_revoked() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+
+    mock.grant_role(&owner, "admin", Role::RiskManager).unwrap();
+    mock.grant_role(&owner, "admin", Role::ConfigAdmin).unwrap();
+    mock.grant_role(&owner, "admin", Role::RiskManager).unwrap();
+    mock.grant_role(&owner, "pauser", Role::Pauser).unwrap();
+
+    assert_eq!(
+        mock.query_roles(None, None),
+        vec![
+            RolesResponseItem {
+                address: "admin".to_string(),
+                roles: vec![Role::RiskManager, Role::ConfigAdmin],
+            },
+            RolesResponseItem {
+                address: "pauser".to_string(),
+                roles: vec![Role::Pauser],
+            },
+        ]
+    );
+
+    mock.revoke_role(&owner, "admin", Role::RiskManager).unwrap();
+    mock.revoke_role(&owner, "pauser", Role::Pauser).unwrap();
+
+    assert_eq!(
+        mock.query_roles(None, None),
+        vec![RolesResponseItem {
+            address: "admin".to_string(),
+            roles: vec![Role::ConfigAdmin],
+        }]
+    );
+}
+
+#[test]
+fn config_fields_gated_by_role() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    let risk_manager = Addr::unchecked("risk_manager");
+    mock.grant_role(&owner, risk_manager.as_str(), Role::RiskManager).unwrap();
+
+    mock.update_config(
+        &risk_manager,
+        ConfigUpdates {
+            max_slippage: Some(Decimal::percent(10)),
+            max_unlocking_positions: Some(Uint128::new(7)),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let config = mock.query_config();
+    assert_eq!(config.max_slippage, Decimal::percent(10));
+    assert_eq!(config.max_unlocking_positions, Uint128::new(7));
+
+    let res = mock.update_config(
+        &risk_manager,
+        ConfigUpdates {
+            max_slippage: Some(Decimal::percent(11)),
+            keeper_fee: Some(coin(10, "uosmo")),
+            ..Default::default()
+        },
+    );
+    assert_err(
+        res,
+        ContractError::MissingRole {
+            user: risk_manager.to_string(),
+            role: Role::ConfigAdmin,
+            field: "keeper_fee".to_string(),
+        },
+    );
+
+    // Contract addresses remain owner-only
+    let res = mock.update_config(
+        &risk_manager,
+        ConfigUpdates {
+            oracle: Some(OracleUnchecked::new("new_oracle".to_string())),
+            ..Default::default()
+        },
+    );
+    assert_err(res, Owner(NotOwner {}));
+
+    assert_eq!(mock.query_config().max_slippage, Decimal::percent(10));
+}
+
+#[test]
+fn config_admin_updates_fees() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    let config_admin = Addr::unchecked("config_admin");
+    mock.grant_role(&owner, config_admin.as_str(), Role::ConfigAdmin).unwrap();
+
+    mock.update_config(
+        &config_admin,
+        ConfigUpdates {
+            keeper_fee: Some(coin(10, "uosmo")),
+            flash_loan_fee: Some(Decimal::permille(1)),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let config = mock.query_config();
+    assert_eq!(config.keeper_fee, Some(coin(10, "uosmo")));
+    assert_eq!(config.flash_loan_fee, Decimal::permille(1));
+
+    let res = mock.update_config(
+        &config_admin,
+        ConfigUpdates {
+            max_slippage: Some(Decimal::percent(11)),
+            ..Default::default()
+        },
+    );
+    assert_err(
+        res,
+        ContractError::MissingRole {
+            user: config_admin.to_string(),
+            role: Role::RiskManager,
+            field: "max_slippage".to_string(),
+        },
+    );
+}
+
+#[test]
+fn pauser_can_pause_until_revoked() {
+    let mut mock =
+        MockEnv::new().emergency_owner(&Addr::unchecked("emergency_owner")).build().unwrap();
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    let pauser = Addr::unchecked("pauser");
+    let pause = ActionPause {
+        action: PausableAction::Borrow,
+        target: None,
+    };
+
+    mock.grant_role(&owner, pauser.as_str(), Role::Pauser).unwrap();
+    mock.pause_action(&pauser, pause.clone()).unwrap();
+    assert_eq!(mock.query_paused_actions(), vec![pause.clone()]);
+
+    // Pausers can not unpause
+    let res = mock.unpause_action(&pauser, pause.clone());
+    assert_err(res, Owner(NotOwner {}));
+
+    mock.revoke_role(&owner, pauser.as_str(), Role::Pauser).unwrap();
+    let res = mock.pause_action(&pauser, pause);
+    assert_err(res, Owner(NotEmergencyOwner {}));
+}
@@ -0,0 +1,287 @@
+use cosmwasm_std::{coin, Addr, Coin, Decimal, Uint128};
+use mars_params::{msg::AssetParamsUpdate, types::asset::AssetParams};
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    error::ContractError,
+    msg::{
+        execute::{
+            Action::{Borrow, ClaimRewards, Deposit, Liquidate, SwapExactIn},
+            ActionAmount, ActionCoin, ActionPause, ActionPermission, LiquidateRequest,
+            PausableAction,
+        },
+        query::SimulationRule,
+    },
+};
+use mars_rover_health_types::AccountKind;
+use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
+
+use crate::helpers::{uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn simulation_does_not_change_positions() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[coin_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            &user,
+            vec![Deposit(coin_info.to_coin(300)), Borrow(coin_info.to_coin(42))],
+        )
+        .unwrap();
+
+    assert_eq!(res.rejected_by, None);
+    assert_eq!(res.positions.deposits, vec![coin_info.to_coin(342)]);
+    assert_eq!(res.positions.debts.len(), 1);
+    assert_eq!(res.positions.debts.first().unwrap().amount, Uint128::new(42));
+    assert_eq!(res.health_before.total_debt_value, Uint128::zero());
+    assert!(!res.health_after.total_debt_value.is_zero());
+    assert!(!res.health_after.above_max_ltv);
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 0);
+    assert_eq!(position.debts.len(), 0);
+}
+
+#[test]
+fn claimed_rewards_are_not_added_to_deposits() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[coin_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.add_incentive_reward(&account_id, coin_info.to_coin(123));
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            &user,
+            vec![
+                Deposit(coin_info.to_coin(300)),
+                ClaimRewards {
+                    recipient: None,
+                },
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(res.rejected_by, None);
+    assert_eq!(res.positions.deposits, vec![coin_info.to_coin(300)]);
+}
+
+#[test]
+fn health_before_matches_health_contract() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![coin_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(300)), Borrow(coin_info.to_coin(42))],
+        &[coin_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = mock.simulate_actions(&account_id, &user, vec![]).unwrap();
+
+    let health = mock.query_health(&account_id, AccountKind::Default, ActionKind::Default);
+    assert_eq!(res.health_before, health);
+    assert_eq!(res.health_after, health);
+    assert_eq!(res.positions, mock.query_positions(&account_id));
+    assert_eq!(res.rejected_by, None);
+}
+
+#[test]
+fn reports_max_ltv_rejection() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[coin_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            &user,
+            vec![Deposit(coin_info.to_coin(300)), Borrow(coin_info.to_coin(800))],
+        )
+        .unwrap();
+
+    let rejection = res.rejected_by.unwrap();
+    assert_eq!(rejection.rule, SimulationRule::MaxLtv);
+    assert!(rejection
+        .reason
+        .starts_with("Actions resulted in exceeding maximum allowed loan-to-value"));
+    assert!(res.health_after.above_max_ltv);
+}
+
+#[test]
+fn no_health_check_bundles_are_not_rejected_by_max_ltv() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[coin_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res =
+        mock.simulate_actions(&account_id, &user, vec![Deposit(coin_info.to_coin(300))]).unwrap();
+
+    assert_eq!(res.rejected_by, None);
+    assert_eq!(res.health_after.max_ltv_health_factor, None);
+}
+
+#[test]
+fn reports_deposit_cap_rejection() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock =
+        MockEnv::new().set_params(&[atom_info.clone(), osmo_info.clone()]).build().unwrap();
+
+    let mut params: AssetParams = mock.query_asset_params(&osmo_info.denom);
+    params.deposit_cap = Uint128::new(1_000);
+    mock.update_asset_params(AssetParamsUpdate::AddOrUpdate {
+        params: params.into(),
+    });
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            &user,
+            vec![
+                Deposit(atom_info.to_coin(123)),
+                SwapExactIn {
+                    coin_in: ActionCoin {
+                        denom: atom_info.denom.clone(),
+                        amount: ActionAmount::AccountBalance,
+                    },
+                    denom_out: osmo_info.denom.clone(),
+                    slippage: Decimal::percent(5),
+                },
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(res.positions.deposits, vec![coin(MOCK_SWAP_RESULT.u128(), &osmo_info.denom)]);
+    let rejection = res.rejected_by.unwrap();
+    assert_eq!(rejection.rule, SimulationRule::DepositCaps);
+    assert_eq!(
+        rejection.reason,
+        ContractError::AboveAssetDepositCap {
+            new_value: Coin {
+                denom: osmo_info.denom,
+                amount: MOCK_SWAP_RESULT,
+            },
+            maximum: Uint128::new(1_000),
+        }
+        .to_string()
+    );
+}
+
+#[test]
+fn liquidations_can_not_be_simulated() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[coin_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let err = mock
+        .simulate_actions(
+            &account_id,
+            &user,
+            vec![Liquidate {
+                liquidatee_account_id: "2".to_string(),
+                debt_coin: coin_info.to_coin(10),
+                request: LiquidateRequest::Deposit(coin_info.denom.clone()),
+            }],
+        )
+        .unwrap_err();
+
+    assert!(err
+        .to_string()
+        .contains(&ContractError::SimulationNotSupported("Liquidate".to_string()).to_string()));
+}
+
+#[test]
+fn sender_must_be_owner_or_permitted_delegate() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[coin_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let delegate = Addr::unchecked("delegate");
+    let err = mock
+        .simulate_actions(&account_id, &delegate, vec![Deposit(coin_info.to_coin(300))])
+        .unwrap_err();
+    assert!(err.to_string().contains(
+        &ContractError::NotTokenOwner {
+            user: delegate.to_string(),
+            account_id: account_id.clone(),
+        }
+        .to_string()
+    ));
+
+    mock.grant_permission(&account_id, &user, &delegate, vec![ActionPermission::Deposit], None)
+        .unwrap();
+    mock.simulate_actions(&account_id, &delegate, vec![Deposit(coin_info.to_coin(300))]).unwrap();
+
+    let err = mock
+        .simulate_actions(&account_id, &delegate, vec![Borrow(coin_info.to_coin(10))])
+        .unwrap_err();
+    assert!(err.to_string().contains(
+        &ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("{:?}", Borrow(coin_info.to_coin(10))),
+        }
+        .to_string()
+    ));
+}
+
+#[test]
+fn paused_actions_can_not_be_simulated() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let emergency_owner = Addr::unchecked("emergency_owner");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .emergency_owner(&emergency_owner)
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.pause_action(
+        &emergency_owner,
+        ActionPause {
+            action: PausableAction::Borrow,
+            target: None,
+        },
+    )
+    .unwrap();
+
+    let err = mock
+        .simulate_actions(
+            &account_id,
+            &user,
+            vec![Deposit(coin_info.to_coin(300)), Borrow(coin_info.to_coin(10))],
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains(
+        &ContractError::ActionPaused {
+            action: PausableAction::Borrow,
+            target: None,
+        }
+        .to_string()
+    ));
+}
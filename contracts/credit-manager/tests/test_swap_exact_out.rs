@@ -0,0 +1,298 @@
+use cosmwasm_std::{Addr, Coin, OverflowError, OverflowOperation::Sub, Uint128};
+use mars_rover::{
+    error::ContractError,
+    msg::execute::{
+        Action::{Deposit, SwapExactOut},
+        ActionAmount,
+    },
+};
+use mars_swapper_mock::contract::{
+    MOCK_SWAP_EXACT_OUT_AMOUNT_IN, MOCK_SWAP_EXACT_OUT_MAX_AMOUNT_OUT,
+};
+
+use crate::helpers::{
+    assert_err, blacklisted_coin, uatom_info, uosmo_info, AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn only_token_owner_can_swap_exact_out_for_account() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let another_user = Addr::unchecked("another_user");
+    let res = mock.update_credit_account(
+        &account_id,
+        &another_user,
+        vec![SwapExactOut {
+            denom_in: "mars".to_string(),
+            coin_out: Coin::new(12, "osmo"),
+            max_amount_in: ActionAmount::Exact(Uint128::new(12)),
+        }],
+        &[],
+    );
+
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: another_user.into(),
+            account_id,
+        },
+    )
+}
+
+#[test]
+fn denom_out_must_be_whitelisted() {
+    let blacklisted_coin = blacklisted_coin();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[blacklisted_coin.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SwapExactOut {
+            denom_in: blacklisted_coin.denom,
+            coin_out: Coin::new(100, "ujake"),
+            max_amount_in: ActionAmount::Exact(Uint128::new(10_000)),
+        }],
+        &[],
+    );
+
+    assert_err(res, ContractError::NotWhitelisted("ujake".to_string()))
+}
+
+#[test]
+fn no_amount_out_requested() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock =
+        MockEnv::new().set_params(&[osmo_info.clone(), atom_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SwapExactOut {
+            denom_in: atom_info.denom,
+            coin_out: osmo_info.to_coin(0),
+            max_amount_in: ActionAmount::Exact(Uint128::new(10_000)),
+        }],
+        &[],
+    );
+
+    assert_err(res, ContractError::NoAmount)
+}
+
+#[test]
+fn user_does_not_have_enough_balance_for_max_amount_in() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(100)),
+            SwapExactOut {
+                denom_in: atom_info.denom.clone(),
+                coin_out: osmo_info.to_coin(1_000),
+                max_amount_in: ActionAmount::Exact(Uint128::new(10_000)),
+            },
+        ],
+        &[atom_info.to_coin(100)],
+    );
+
+    assert_err(
+        res,
+        ContractError::Overflow(OverflowError {
+            operation: Sub,
+            operand1: "100".to_string(),
+            operand2: "10000".to_string(),
+        }),
+    )
+}
+
+#[test]
+fn unspent_input_is_refunded_to_account() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom_info.to_coin(10_000)],
+        })
+        .build()
+        .unwrap();
+
+    let coin_out = osmo_info.to_coin(2_500);
+    let res = mock.query_swap_exact_out_estimate(&atom_info.denom, &coin_out);
+    assert_eq!(res.amount, MOCK_SWAP_EXACT_OUT_AMOUNT_IN);
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            SwapExactOut {
+                denom_in: atom_info.denom.clone(),
+                coin_out: coin_out.clone(),
+                max_amount_in: ActionAmount::Exact(Uint128::new(1_000)),
+            },
+        ],
+        &[atom_info.to_coin(10_000)],
+    )
+    .unwrap();
+
+    let expected_atom = Uint128::new(10_000) - MOCK_SWAP_EXACT_OUT_AMOUNT_IN;
+
+    // assert rover balance
+    let atom_balance = mock.query_balance(&mock.rover, &atom_info.denom).amount;
+    let osmo_balance = mock.query_balance(&mock.rover, &osmo_info.denom).amount;
+    assert_eq!(atom_balance, expected_atom);
+    assert_eq!(osmo_balance, coin_out.amount);
+
+    // assert account position
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 2);
+    let atom_deposit = position.deposits.iter().find(|c| c.denom == atom_info.denom).unwrap();
+    assert_eq!(atom_deposit.amount, expected_atom);
+    let osmo_deposit = position.deposits.iter().find(|c| c.denom == osmo_info.denom).unwrap();
+    assert_eq!(osmo_deposit.amount, coin_out.amount);
+}
+
+#[test]
+fn swap_exact_out_with_full_balance() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom_info.to_coin(MOCK_SWAP_EXACT_OUT_AMOUNT_IN.u128())],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(MOCK_SWAP_EXACT_OUT_AMOUNT_IN.u128())),
+            SwapExactOut {
+                denom_in: atom_info.denom.clone(),
+                coin_out: osmo_info.to_coin(5_000),
+                max_amount_in: ActionAmount::AccountBalance,
+            },
+        ],
+        &[atom_info.to_coin(MOCK_SWAP_EXACT_OUT_AMOUNT_IN.u128())],
+    )
+    .unwrap();
+
+    // assert rover balance
+    let atom_balance = mock.query_balance(&mock.rover, &atom_info.denom).amount;
+    assert_eq!(atom_balance, Uint128::zero());
+
+    // assert account position
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 1);
+    assert_eq!(position.deposits.first().unwrap().denom, osmo_info.denom);
+    assert_eq!(position.deposits.first().unwrap().amount, Uint128::new(5_000));
+}
+
+#[test]
+fn can_not_swap_into_same_denom() {
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![osmo_info.to_coin(1_000)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(osmo_info.to_coin(1_000)),
+            SwapExactOut {
+                denom_in: osmo_info.denom.clone(),
+                coin_out: osmo_info.to_coin(500),
+                max_amount_in: ActionAmount::Exact(Uint128::new(1_000)),
+            },
+        ],
+        &[osmo_info.to_coin(1_000)],
+    );
+
+    assert_err(res, ContractError::SwapSameDenom(osmo_info.denom))
+}
+
+#[test]
+fn swap_must_return_requested_amount_out() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom_info.to_coin(10_000)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    // The mock swapper falls short of any request above its max amount out
+    let coin_out = osmo_info.to_coin(MOCK_SWAP_EXACT_OUT_MAX_AMOUNT_OUT.u128() + 1);
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            SwapExactOut {
+                denom_in: atom_info.denom.clone(),
+                coin_out: coin_out.clone(),
+                max_amount_in: ActionAmount::Exact(Uint128::new(1_000)),
+            },
+        ],
+        &[atom_info.to_coin(10_000)],
+    );
+
+    assert_err(
+        res,
+        ContractError::SwapOutputTooLow {
+            denom: coin_out.denom,
+            received: MOCK_SWAP_EXACT_OUT_MAX_AMOUNT_OUT,
+            minimum: coin_out.amount,
+        },
+    )
+}
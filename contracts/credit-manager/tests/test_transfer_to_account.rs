@@ -0,0 +1,253 @@
+use cosmwasm_std::{Addr, Uint128};
+use mars_rover::{
+    error::ContractError,
+    msg::execute::{
+        Action::{Borrow, Deposit, EnterVault, TransferToAccount},
+        ActionAmount, ActionCoin,
+    },
+};
+
+use crate::helpers::{
+    assert_err, get_coin, lp_token_info, uatom_info, ujake_info, unlocked_vault_info,
+    AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn recipient_must_have_same_owner() {
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(100)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let other_account_id = mock.create_credit_account(&Addr::unchecked("other")).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            TransferToAccount {
+                recipient_account_id: other_account_id.clone(),
+                coins: vec![uatom_info.to_action_coin(100)],
+                debt: vec![],
+                vault_positions: vec![],
+            },
+        ],
+        &[uatom_info.to_coin(100)],
+    );
+
+    assert_err(
+        res,
+        ContractError::InvalidTransfer {
+            reason: format!("{other_account_id} is not owned by the owner of {account_id}"),
+        },
+    );
+}
+
+#[test]
+fn vault_position_and_debt_ring_fenced() {
+    let uatom_info = uatom_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone(), lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(100), lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let isolated_account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Deposit(lp_token.to_coin(200)),
+            Borrow(uatom_info.to_coin(50)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(100),
+            },
+        ],
+        &[uatom_info.to_coin(100), lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    let before = mock.query_positions(&account_id);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![TransferToAccount {
+            recipient_account_id: isolated_account_id.clone(),
+            coins: vec![uatom_info.to_action_coin(60)],
+            debt: vec![ActionCoin {
+                denom: uatom_info.denom.clone(),
+                amount: ActionAmount::AccountBalance,
+            }],
+            vault_positions: vec![vault],
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 2);
+    assert_eq!(get_coin(&uatom_info.denom, &position.deposits), uatom_info.to_coin(90));
+    assert_eq!(get_coin(&lp_token.denom, &position.deposits), lp_token.to_coin(100));
+    assert!(position.debts.is_empty());
+    assert!(position.vaults.is_empty());
+
+    let position = mock.query_positions(&isolated_account_id);
+    assert_eq!(position.deposits, vec![uatom_info.to_coin(60)]);
+    assert_eq!(position.debts, before.debts);
+    assert_eq!(position.vaults, before.vaults);
+}
+
+#[test]
+fn partial_debt_moves_proportional_shares() {
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(100)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let recipient_account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Borrow(uatom_info.to_coin(50)),
+            TransferToAccount {
+                recipient_account_id: recipient_account_id.clone(),
+                coins: vec![uatom_info.to_action_coin(50)],
+                debt: vec![uatom_info.to_action_coin(20)],
+                vault_positions: vec![],
+            },
+        ],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    let sender_debt = mock.query_positions(&account_id).debts.first().unwrap().clone();
+    let recipient_debt = mock.query_positions(&recipient_account_id).debts.first().unwrap().clone();
+    assert_eq!(recipient_debt.amount, Uint128::new(20));
+
+    let total_shares = mock.query_total_debt_shares(&uatom_info.denom).shares;
+    assert_eq!(sender_debt.shares + recipient_debt.shares, total_shares);
+}
+
+#[test]
+fn recipient_must_be_healthy() {
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(100)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let recipient_account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Borrow(uatom_info.to_coin(50)),
+            TransferToAccount {
+                recipient_account_id: recipient_account_id.clone(),
+                coins: vec![],
+                debt: vec![uatom_info.to_action_coin(50)],
+                vault_positions: vec![],
+            },
+        ],
+        &[uatom_info.to_coin(100)],
+    );
+
+    let err: ContractError = res.unwrap_err().downcast().unwrap();
+    match err {
+        ContractError::AboveMaxLTV {
+            account_id,
+            ..
+        } => assert_eq!(account_id, recipient_account_id),
+        _ => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn hls_recipient_must_follow_hls_rules() {
+    let uatom_info = uatom_info();
+    let ujake_info = ujake_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone(), ujake_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(100), ujake_info.to_coin(100)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let recipient_account_id = mock.create_hls_account(&user);
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Deposit(ujake_info.to_coin(100)),
+            Borrow(uatom_info.to_coin(10)),
+            TransferToAccount {
+                recipient_account_id,
+                coins: vec![uatom_info.to_action_coin(100), ujake_info.to_action_coin(100)],
+                debt: vec![uatom_info.to_action_coin(10)],
+                vault_positions: vec![],
+            },
+        ],
+        &[uatom_info.to_coin(100), ujake_info.to_coin(100)],
+    );
+
+    assert_err(
+        res,
+        ContractError::HLS {
+            reason: format!(
+                "{} deposit is not a correlated asset to debt {}",
+                ujake_info.denom, uatom_info.denom
+            ),
+        },
+    );
+}
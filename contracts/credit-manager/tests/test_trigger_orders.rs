@@ -0,0 +1,335 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::{
+    error::{ContractError, ContractError::NotTokenOwner},
+    msg::{
+        execute::{
+            Action::{Borrow, Deposit, Repay},
+            Comparison, TriggerCondition,
+        },
+        instantiate::ConfigUpdates,
+    },
+};
+
+use crate::helpers::{assert_err, uatom_info, uosmo_info, withdraw, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+fn osmo_price_below(price: &str) -> TriggerCondition {
+    TriggerCondition::OraclePrice {
+        denom: "uosmo".to_string(),
+        comparison: Comparison::LessThan,
+        price: Decimal::from_str(price).unwrap(),
+    }
+}
+
+fn set_keeper_fee(mock: &mut MockEnv, amount: u128) {
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            keeper_fee: Some(coin(amount, "uosmo")),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn only_token_owner_can_create_trigger_order() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let another_user = Addr::unchecked("another_user");
+    let res = mock.create_trigger_order(
+        &account_id,
+        &another_user,
+        vec![withdraw(uosmo_info().to_action_coin(10))],
+        osmo_price_below("0.2"),
+    );
+
+    assert_err(
+        res,
+        NotTokenOwner {
+            user: another_user.into(),
+            account_id,
+        },
+    );
+}
+
+#[test]
+fn deposits_not_allowed_in_trigger_orders() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.create_trigger_order(
+        &account_id,
+        &user,
+        vec![Deposit(uosmo_info().to_coin(10))],
+        osmo_price_below("0.2"),
+    );
+
+    assert_err(res, ContractError::TriggerActionNotAllowed("Deposit".to_string()));
+}
+
+#[test]
+fn create_list_and_cancel_trigger_orders() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    mock.create_trigger_order(
+        &account_id_a,
+        &user,
+        vec![withdraw(uosmo_info().to_action_coin(10))],
+        osmo_price_below("0.2"),
+    )
+    .unwrap();
+    mock.create_trigger_order(
+        &account_id_a,
+        &user,
+        vec![withdraw(uosmo_info().to_action_coin(20))],
+        osmo_price_below("0.1"),
+    )
+    .unwrap();
+    mock.create_trigger_order(
+        &account_id_b,
+        &user,
+        vec![withdraw(uosmo_info().to_action_coin(30))],
+        osmo_price_below("0.1"),
+    )
+    .unwrap();
+
+    let orders = mock.query_trigger_orders(&account_id_a, None, None);
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders[0].trigger_order_id, 1);
+    assert_eq!(orders[0].owner, user.to_string());
+    assert_eq!(orders[0].condition, osmo_price_below("0.2"));
+    assert_eq!(orders[1].trigger_order_id, 2);
+
+    let orders = mock.query_trigger_orders(&account_id_a, Some(1), None);
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].trigger_order_id, 2);
+
+    let orders = mock.query_all_trigger_orders(None, None);
+    assert_eq!(orders.len(), 3);
+    let orders = mock.query_all_trigger_orders(Some((account_id_a.clone(), 2)), None);
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].account_id, account_id_b);
+
+    let another_user = Addr::unchecked("another_user");
+    let res = mock.cancel_trigger_order(&account_id_a, &another_user, 1);
+    assert_err(
+        res,
+        NotTokenOwner {
+            user: another_user.into(),
+            account_id: account_id_a.clone(),
+        },
+    );
+
+    mock.cancel_trigger_order(&account_id_a, &user, 1).unwrap();
+    let orders = mock.query_trigger_orders(&account_id_a, None, None);
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].trigger_order_id, 2);
+}
+
+#[test]
+fn keeper_executes_order_when_price_condition_met() {
+    let osmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let keeper = Addr::unchecked("keeper");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, osmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+    set_keeper_fee(&mut mock, 5);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(osmo_info.to_coin(300))],
+        &[osmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.create_trigger_order(
+        &account_id,
+        &user,
+        vec![withdraw(osmo_info.to_action_coin(100))],
+        osmo_price_below("0.2"),
+    )
+    .unwrap();
+
+    let res = mock.execute_trigger_order(&account_id, &keeper, 1);
+    assert_err(res, ContractError::TriggerConditionNotMet(1));
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Default,
+        denom: osmo_info.denom.clone(),
+        price: Decimal::from_str("0.1").unwrap(),
+    });
+
+    mock.execute_trigger_order(&account_id, &keeper, 1).unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 1);
+    assert_eq!(position.deposits[0].amount, Uint128::new(195));
+
+    let user_balance = mock.query_balance(&user, &osmo_info.denom);
+    assert_eq!(user_balance.amount, Uint128::new(100));
+    let keeper_balance = mock.query_balance(&keeper, &osmo_info.denom);
+    assert_eq!(keeper_balance.amount, Uint128::new(5));
+
+    // Order is consumed on execution
+    assert!(mock.query_trigger_orders(&account_id, None, None).is_empty());
+}
+
+#[test]
+fn keeper_executes_order_when_health_factor_condition_met() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let keeper = Addr::unchecked("keeper");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, osmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+    set_keeper_fee(&mut mock, 5);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(osmo_info.to_coin(300)), Borrow(atom_info.to_coin(40))],
+        &[osmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.create_trigger_order(
+        &account_id,
+        &user,
+        vec![Repay {
+            recipient_account_id: None,
+            coin: atom_info.to_action_coin_full_balance(),
+        }],
+        TriggerCondition::HealthFactor {
+            threshold: Decimal::from_str("2").unwrap(),
+        },
+    )
+    .unwrap();
+
+    let res = mock.execute_trigger_order(&account_id, &keeper, 1);
+    assert_err(res, ContractError::TriggerConditionNotMet(1));
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Default,
+        denom: osmo_info.denom.clone(),
+        price: Decimal::from_str("0.2").unwrap(),
+    });
+
+    mock.execute_trigger_order(&account_id, &keeper, 1).unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 1);
+    assert_eq!(position.deposits[0].denom, osmo_info.denom);
+    assert_eq!(position.deposits[0].amount, Uint128::new(295));
+
+    let keeper_balance = mock.query_balance(&keeper, &osmo_info.denom);
+    assert_eq!(keeper_balance.amount, Uint128::new(5));
+}
+
+#[test]
+fn orders_invalid_after_account_transfer() {
+    let osmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let keeper = Addr::unchecked("keeper");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, osmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(osmo_info.to_coin(300))],
+        &[osmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.create_trigger_order(
+        &account_id,
+        &user,
+        vec![withdraw(osmo_info.to_action_coin(100))],
+        osmo_price_below("0.5"),
+    )
+    .unwrap();
+
+    let new_owner = Addr::unchecked("new_owner");
+    mock.transfer_account(&account_id, &user, &new_owner).unwrap();
+
+    let res = mock.execute_trigger_order(&account_id, &keeper, 1);
+    assert_err(res, ContractError::TriggerOrderInvalidated(1));
+
+    // New owner can clean up the stale order
+    mock.cancel_trigger_order(&account_id, &new_owner, 1).unwrap();
+    assert!(mock.query_trigger_orders(&account_id, None, None).is_empty());
+}
+
+#[test]
+fn orders_stay_invalid_after_account_returns_to_owner() {
+    let osmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let keeper = Addr::unchecked("keeper");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, osmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(osmo_info.to_coin(300))],
+        &[osmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.create_trigger_order(
+        &account_id,
+        &user,
+        vec![withdraw(osmo_info.to_action_coin(100))],
+        osmo_price_below("0.5"),
+    )
+    .unwrap();
+
+    let new_owner = Addr::unchecked("new_owner");
+    mock.transfer_account(&account_id, &user, &new_owner).unwrap();
+    mock.transfer_account(&account_id, &new_owner, &user).unwrap();
+
+    let res = mock.execute_trigger_order(&account_id, &keeper, 1);
+    assert_err(res, ContractError::TriggerOrderInvalidated(1));
+}
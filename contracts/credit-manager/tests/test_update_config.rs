@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cosmwasm_std::{coin, Addr, Decimal, Empty, Uint128};
 use cw_multi_test::{BasicApp, Executor};
 use helpers::assert_err;
 use mars_mock_oracle::msg::{CoinPrice, InstantiateMsg as OracleInstantiateMsg};
@@ -10,7 +10,7 @@ use mars_rover::{
         zapper::ZapperBase,
     },
     error::ContractError,
-    msg::instantiate::ConfigUpdates,
+    msg::instantiate::{ConfigUpdates, LiquidationAuction, LiquidationAuctionUpdate},
 };
 use mars_rover_health_types::AccountKind;
 
@@ -36,6 +36,11 @@ fn only_owner_can_update_config() {
             zapper: None,
             health_contract: None,
             rewards_collector: None,
+            keeper_fee: None,
+            flash_loan_fee: None,
+            liquidation_auction: None,
+            bad_debt_dust_threshold: None,
+            config_update_delay: None,
         },
     );
 
@@ -93,6 +98,11 @@ fn update_config_works_with_full_config() {
     let new_swapper = SwapperBase::new("new_swapper".to_string());
     let new_health_contract = HealthContractUnchecked::new("new_health_contract".to_string());
     let new_rewards_collector = "rewards_collector_contract_new".to_string();
+    let new_keeper_fee = coin(100, "uosmo");
+    let new_flash_loan_fee = Decimal::percent(1);
+    let new_liquidation_auction = LiquidationAuction {
+        blocks_to_max_bonus: 100,
+    };
 
     mock.update_config(
         &Addr::unchecked(original_config.ownership.owner.clone().unwrap()),
@@ -107,6 +117,13 @@ fn update_config_works_with_full_config() {
             zapper: Some(new_zapper.clone()),
             health_contract: Some(new_health_contract.clone()),
             rewards_collector: Some(new_rewards_collector.clone()),
+            keeper_fee: Some(new_keeper_fee.clone()),
+            flash_loan_fee: Some(new_flash_loan_fee),
+            liquidation_auction: Some(LiquidationAuctionUpdate::Set(
+                new_liquidation_auction.clone(),
+            )),
+            bad_debt_dust_threshold: Some(Uint128::new(10)),
+            config_update_delay: Some(3600),
         },
     )
     .unwrap();
@@ -156,6 +173,21 @@ fn update_config_works_with_full_config() {
 
     assert_eq!(&new_config.incentives, new_incentives.address());
     assert_ne!(new_config.incentives, original_config.incentives);
+
+    assert_eq!(new_config.keeper_fee, Some(new_keeper_fee));
+    assert_ne!(new_config.keeper_fee, original_config.keeper_fee);
+
+    assert_eq!(new_config.flash_loan_fee, new_flash_loan_fee);
+    assert_ne!(new_config.flash_loan_fee, original_config.flash_loan_fee);
+
+    assert_eq!(new_config.bad_debt_dust_threshold, Uint128::new(10));
+    assert_ne!(new_config.bad_debt_dust_threshold, original_config.bad_debt_dust_threshold);
+
+    assert_eq!(new_config.liquidation_auction, Some(new_liquidation_auction));
+    assert_ne!(new_config.liquidation_auction, original_config.liquidation_auction);
+
+    assert_eq!(new_config.config_update_delay, 3600);
+    assert_ne!(new_config.config_update_delay, original_config.config_update_delay);
 }
 
 #[test]
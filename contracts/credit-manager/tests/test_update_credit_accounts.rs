@@ -0,0 +1,254 @@
+use cosmwasm_std::{coin, Addr, Uint128};
+use mars_rover::{
+    coins::Coins,
+    error::ContractError,
+    msg::execute::{
+        AccountUpdate,
+        Action::{Borrow, Deposit, EnterVault},
+    },
+};
+
+use crate::helpers::{
+    assert_err, get_coin, lp_token_info, uatom_info, unlocked_vault_info, uosmo_info,
+    AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn funds_allocated_to_each_account() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300), uatom_info.to_coin(100)],
+        })
+        .build()
+        .unwrap();
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_accounts(
+        &user,
+        vec![
+            AccountUpdate {
+                account_id: account_id_a.clone(),
+                actions: vec![Deposit(uosmo_info.to_coin(200))],
+            },
+            AccountUpdate {
+                account_id: account_id_b.clone(),
+                actions: vec![
+                    Deposit(uosmo_info.to_coin(100)),
+                    Deposit(uatom_info.to_coin(40)),
+                    Borrow(uatom_info.to_coin(10)),
+                ],
+            },
+        ],
+        &[uosmo_info.to_coin(300), uatom_info.to_coin(40)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id_a);
+    assert_eq!(position.deposits, vec![uosmo_info.to_coin(200)]);
+    assert!(position.debts.is_empty());
+
+    let position = mock.query_positions(&account_id_b);
+    assert_eq!(get_coin(&uosmo_info.denom, &position.deposits), uosmo_info.to_coin(100));
+    assert_eq!(get_coin(&uatom_info.denom, &position.deposits), uatom_info.to_coin(50));
+    assert_eq!(position.debts.len(), 1);
+}
+
+#[test]
+fn funds_must_match_deposits() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    let updates = vec![
+        AccountUpdate {
+            account_id: account_id_a,
+            actions: vec![Deposit(uosmo_info.to_coin(100))],
+        },
+        AccountUpdate {
+            account_id: account_id_b,
+            actions: vec![Deposit(uosmo_info.to_coin(100))],
+        },
+    ];
+
+    let res = mock.update_credit_accounts(&user, updates.clone(), &[uosmo_info.to_coin(150)]);
+    assert_err(
+        res,
+        ContractError::FundsMismatch {
+            expected: Uint128::new(100),
+            received: Uint128::new(50),
+        },
+    );
+
+    let res = mock.update_credit_accounts(&user, updates, &[uosmo_info.to_coin(250)]);
+    assert_err(
+        res,
+        ContractError::ExtraFundsReceived(Coins::try_from(vec![uosmo_info.to_coin(50)]).unwrap()),
+    );
+}
+
+#[test]
+fn each_account_must_be_authorized() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let other_user = Addr::unchecked("other_user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let other_account_id = mock.create_credit_account(&other_user).unwrap();
+
+    let res = mock.update_credit_accounts(
+        &user,
+        vec![
+            AccountUpdate {
+                account_id,
+                actions: vec![Deposit(uosmo_info.to_coin(100))],
+            },
+            AccountUpdate {
+                account_id: other_account_id.clone(),
+                actions: vec![Deposit(uosmo_info.to_coin(100))],
+            },
+        ],
+        &[uosmo_info.to_coin(200)],
+    );
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: user.to_string(),
+            account_id: other_account_id,
+        },
+    );
+}
+
+#[test]
+fn account_can_not_be_updated_twice() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let update = AccountUpdate {
+        account_id: account_id.clone(),
+        actions: vec![],
+    };
+    let res = mock.update_credit_accounts(&user, vec![update.clone(), update], &[]);
+    assert_err(
+        res,
+        ContractError::DuplicateAccountUpdate {
+            account_id,
+        },
+    );
+}
+
+#[test]
+fn batch_is_reverted_if_any_account_is_unhealthy() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_accounts(
+        &user,
+        vec![
+            AccountUpdate {
+                account_id: account_id_a.clone(),
+                actions: vec![Deposit(uosmo_info.to_coin(200)), Borrow(uatom_info.to_coin(10))],
+            },
+            AccountUpdate {
+                account_id: account_id_b.clone(),
+                actions: vec![Deposit(uosmo_info.to_coin(100)), Borrow(uatom_info.to_coin(100))],
+            },
+        ],
+        &[uosmo_info.to_coin(300)],
+    );
+    assert!(matches!(
+        res.unwrap_err().downcast().unwrap(),
+        ContractError::AboveMaxLTV { account_id, .. } if account_id == account_id_b
+    ));
+
+    assert!(mock.query_positions(&account_id_a).deposits.is_empty());
+    assert!(mock.query_positions(&account_id_a).debts.is_empty());
+    assert!(mock.query_positions(&account_id_b).deposits.is_empty());
+    assert_eq!(mock.query_balance(&user, &uosmo_info.denom), coin(300, uosmo_info.denom));
+}
+
+#[test]
+fn batch_with_external_calls_locks_globally() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_accounts(
+        &user,
+        vec![
+            AccountUpdate {
+                account_id: account_id_a.clone(),
+                actions: vec![
+                    Deposit(lp_token.to_coin(200)),
+                    EnterVault {
+                        vault,
+                        coin: lp_token.to_action_coin(50),
+                    },
+                ],
+            },
+            AccountUpdate {
+                account_id: account_id_b.clone(),
+                actions: vec![Deposit(lp_token.to_coin(100))],
+            },
+        ],
+        &[lp_token.to_coin(300)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id_a);
+    assert_eq!(position.deposits, vec![lp_token.to_coin(150)]);
+    assert_eq!(position.vaults.len(), 1);
+
+    let position = mock.query_positions(&account_id_b);
+    assert_eq!(position.deposits, vec![lp_token.to_coin(100)]);
+
+    // Guards were released
+    mock.update_credit_account(&account_id_b, &user, vec![], &[]).unwrap();
+}
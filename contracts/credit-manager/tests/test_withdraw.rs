@@ -4,7 +4,7 @@ use mars_rover::{
     msg::execute::Action,
 };
 
-use crate::helpers::{assert_err, uatom_info, uosmo_info, AccountToFund, MockEnv};
+use crate::helpers::{assert_err, uatom_info, uosmo_info, withdraw, AccountToFund, MockEnv};
 
 pub mod helpers;
 
@@ -19,7 +19,7 @@ fn only_owner_of_token_can_withdraw() {
     let res = mock.update_credit_account(
         &account_id,
         &another_user,
-        vec![Action::Withdraw(coin_info.to_action_coin(382))],
+        vec![withdraw(coin_info.to_action_coin(382))],
         &[],
     );
 
@@ -45,7 +45,7 @@ fn withdraw_nothing() {
     let res = mock.update_credit_account(
         &account_id,
         &user,
-        vec![Action::Withdraw(coin_info.to_action_coin(0))],
+        vec![withdraw(coin_info.to_action_coin(0))],
         &[],
     );
 
@@ -64,7 +64,7 @@ fn withdraw_but_no_funds() {
     let res = mock.update_credit_account(
         &account_id,
         &user,
-        vec![Action::Withdraw(coin_info.to_action_coin(234))],
+        vec![withdraw(coin_info.to_action_coin(234))],
         &[],
     );
 
@@ -98,10 +98,7 @@ fn withdraw_but_not_enough_funds() {
     let res = mock.update_credit_account(
         &account_id,
         &user,
-        vec![
-            Action::Deposit(coin_info.to_coin(300)),
-            Action::Withdraw(coin_info.to_action_coin(400)),
-        ],
+        vec![Action::Deposit(coin_info.to_coin(300)), withdraw(coin_info.to_action_coin(400))],
         &[coin(300, coin_info.denom)],
     );
 
@@ -138,7 +135,7 @@ fn cannot_withdraw_more_than_healthy() {
         vec![
             Action::Deposit(coin_info.to_coin(200)),
             Action::Borrow(coin_info.to_coin(400)),
-            Action::Withdraw(coin_info.to_action_coin(50)),
+            withdraw(coin_info.to_action_coin(50)),
         ],
         &[coin(200, coin_info.denom)],
     );
@@ -175,7 +172,7 @@ fn withdraw_success() {
         &user,
         vec![
             Action::Deposit(coin_info.to_coin(deposit_amount)),
-            Action::Withdraw(coin_info.to_action_coin(deposit_amount)),
+            withdraw(coin_info.to_action_coin(deposit_amount)),
         ],
         &[Coin::new(deposit_amount, coin_info.denom.clone())],
     )
@@ -208,7 +205,7 @@ fn withdraw_account_balance() {
         &user,
         vec![
             Action::Deposit(coin_info.to_coin(deposit_amount)),
-            Action::Withdraw(coin_info.to_action_coin_full_balance()),
+            withdraw(coin_info.to_action_coin_full_balance()),
         ],
         &[Coin::new(deposit_amount, coin_info.denom.clone())],
     )
@@ -263,7 +260,7 @@ fn multiple_withdraw_actions() {
     mock.update_credit_account(
         &account_id,
         &user,
-        vec![Action::Withdraw(uosmo_info.to_action_coin(uosmo_amount.u128()))],
+        vec![withdraw(uosmo_info.to_action_coin(uosmo_amount.u128()))],
         &[],
     )
     .unwrap();
@@ -280,7 +277,7 @@ fn multiple_withdraw_actions() {
     mock.update_credit_account(
         &account_id,
         &user,
-        vec![Action::Withdraw(uatom_info.to_action_coin(20))],
+        vec![withdraw(uatom_info.to_action_coin(20))],
         &[],
     )
     .unwrap();
@@ -297,7 +294,7 @@ fn multiple_withdraw_actions() {
     mock.update_credit_account(
         &account_id,
         &user,
-        vec![Action::Withdraw(uatom_info.to_action_coin(5))],
+        vec![withdraw(uatom_info.to_action_coin(5))],
         &[],
     )
     .unwrap();
@@ -0,0 +1,223 @@
+use cosmwasm_std::{coin, from_slice, to_binary, Addr, Uint128};
+use mars_red_bank_types::red_bank;
+use mars_rover::msg::execute::{
+    Action,
+    Action::{ClaimRewards, Deposit, RefundAllCoinBalances, Withdraw, WithdrawToRecipient},
+    ActionAmount, ActionCoin, RecipientBase,
+};
+
+use crate::helpers::{uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn actions_without_recipient_deserialize() {
+    let action: Action =
+        from_slice(br#"{"withdraw":{"denom":"uosmo","amount":{"exact":"120"}}}"#).unwrap();
+    assert_eq!(
+        action,
+        Withdraw(ActionCoin {
+            denom: "uosmo".to_string(),
+            amount: ActionAmount::Exact(Uint128::new(120)),
+        })
+    );
+
+    let action: Action = from_slice(br#"{"claim_rewards":{}}"#).unwrap();
+    assert_eq!(
+        action,
+        ClaimRewards {
+            recipient: None
+        }
+    );
+
+    let action: Action = from_slice(br#"{"refund_all_coin_balances":{}}"#).unwrap();
+    assert_eq!(
+        action,
+        RefundAllCoinBalances {
+            recipient: None
+        }
+    );
+}
+
+#[test]
+fn withdraw_to_wallet() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let treasury = Addr::unchecked("treasury");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock
+        .update_credit_account(
+            &account_id,
+            &user,
+            vec![
+                Deposit(uosmo_info.to_coin(300)),
+                WithdrawToRecipient {
+                    coin: ActionCoin {
+                        denom: uosmo_info.denom.clone(),
+                        amount: ActionAmount::Exact(Uint128::new(120)),
+                    },
+                    recipient: RecipientBase::Wallet(treasury.to_string()),
+                },
+            ],
+            &[uosmo_info.to_coin(300)],
+        )
+        .unwrap();
+
+    let attr =
+        res.events.iter().flat_map(|e| e.attributes.iter()).find(|a| a.key == "recipient").unwrap();
+    assert_eq!(attr.value, treasury.to_string());
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits, vec![uosmo_info.to_coin(180)]);
+    assert_eq!(mock.query_balance(&treasury, &uosmo_info.denom), uosmo_info.to_coin(120));
+    assert_eq!(mock.query_balance(&user, &uosmo_info.denom).amount, Uint128::zero());
+}
+
+#[test]
+fn invalid_recipient_rejected() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            WithdrawToRecipient {
+                coin: ActionCoin {
+                    denom: uosmo_info.denom.clone(),
+                    amount: ActionAmount::Exact(Uint128::new(120)),
+                },
+                recipient: RecipientBase::Wallet("".to_string()),
+            },
+        ],
+        &[uosmo_info.to_coin(300)],
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn withdraw_to_contract_with_msg() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let red_bank = Addr::unchecked(mock.query_config().red_bank);
+    let red_bank_balance = mock.query_balance(&red_bank, &uosmo_info.denom);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            WithdrawToRecipient {
+                coin: ActionCoin {
+                    denom: uosmo_info.denom.clone(),
+                    amount: ActionAmount::Exact(Uint128::new(100)),
+                },
+                recipient: RecipientBase::Contract {
+                    contract: red_bank.to_string(),
+                    msg: to_binary(&red_bank::ExecuteMsg::Deposit {
+                        account_id: None,
+                        on_behalf_of: None,
+                    })
+                    .unwrap(),
+                },
+            },
+        ],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits, vec![uosmo_info.to_coin(200)]);
+    assert_eq!(
+        mock.query_balance(&red_bank, &uosmo_info.denom).amount,
+        red_bank_balance.amount + Uint128::new(100)
+    );
+}
+
+#[test]
+fn refund_to_recipient() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let treasury = Addr::unchecked("treasury");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(234), uatom_info.to_coin(25)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(234)),
+            Deposit(uatom_info.to_coin(25)),
+            RefundAllCoinBalances {
+                recipient: Some(RecipientBase::Wallet(treasury.to_string())),
+            },
+        ],
+        &[uosmo_info.to_coin(234), uatom_info.to_coin(25)],
+    )
+    .unwrap();
+
+    assert!(mock.query_positions(&account_id).deposits.is_empty());
+    assert_eq!(mock.query_balance(&treasury, &uosmo_info.denom), uosmo_info.to_coin(234));
+    assert_eq!(mock.query_balance(&treasury, &uatom_info.denom), uatom_info.to_coin(25));
+    assert_eq!(mock.query_balance(&user, &uosmo_info.denom).amount, Uint128::zero());
+}
+
+#[test]
+fn claim_rewards_to_recipient() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let treasury = Addr::unchecked("treasury");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.add_incentive_reward(&account_id, uosmo_info.to_coin(123));
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ClaimRewards {
+            recipient: Some(RecipientBase::Wallet(treasury.to_string())),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(mock.query_balance(&treasury, &uosmo_info.denom), coin(123, uosmo_info.denom));
+    assert_eq!(mock.query_balance(&user, &uosmo_info.denom).amount, Uint128::zero());
+}
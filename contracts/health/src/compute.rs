@@ -1,23 +1,34 @@
 use std::collections::HashMap;
 
-use cosmwasm_std::{Deps, StdResult};
+use cosmwasm_std::{Deps, StdResult, Uint128};
 use mars_red_bank_types::oracle::ActionKind;
-use mars_rover::msg::query::Positions;
+use mars_rover::{adapters::vault::Vault, msg::query::Positions};
 use mars_rover_health_computer::{DenomsData, HealthComputer, VaultsData};
-use mars_rover_health_types::{AccountKind, HealthResult, HealthState, HealthValuesResponse};
+use mars_rover_health_types::{
+    AccountKind, BorrowTarget, FailedAccount, HealthResult, HealthState, HealthValuesResponse,
+    LiquidatableAccount, LiquidatableAccountsResponse, SwapKind,
+};
 
 use crate::querier::HealthQuerier;
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+/// Upper bound on the queries one page of `liquidatable_accounts` makes, see `estimated_queries`
+const MAX_QUERIES_PER_PAGE: u32 = 300;
+
 /// Uses `mars-rover-health-computer` which is a data agnostic package given
 /// it's compiled to .wasm and shared with the frontend.
 /// This function queries all necessary data to pass to `HealthComputer`.
-pub fn compute_health(
+/// `extra_denoms` are denoms outside of the account's positions that also need
+/// prices + params (e.g. the denom to borrow or swap to in max estimates).
+pub fn health_computer(
     deps: Deps,
     kind: AccountKind,
-    q: HealthQuerier,
+    q: &HealthQuerier,
     positions: Positions,
     action: ActionKind,
-) -> HealthResult<HealthValuesResponse> {
+    extra_denoms: &[&str],
+) -> HealthResult<HealthComputer> {
     // Get the denoms that need prices + markets
     let deposit_denoms = positions.deposits.iter().map(|d| &d.denom).collect::<Vec<_>>();
     let debt_denoms = positions.debts.iter().map(|d| &d.denom).collect::<Vec<_>>();
@@ -39,11 +50,16 @@ pub fn compute_health(
         .chain(debt_denoms)
         .chain(lend_denoms)
         .chain(vault_base_token_denoms)
+        .map(|denom| denom.as_str())
+        .chain(extra_denoms.iter().copied())
         .try_for_each(|denom| -> StdResult<()> {
+            if denoms_data.prices.contains_key(denom) {
+                return Ok(());
+            }
             let price = q.oracle.query_price(&deps.querier, denom, action.clone())?.price;
-            denoms_data.prices.insert(denom.clone(), price);
+            denoms_data.prices.insert(denom.to_string(), price);
             let params = q.params.query_asset_params(&deps.querier, denom)?;
-            denoms_data.params.insert(denom.clone(), params);
+            denoms_data.params.insert(denom.to_string(), params);
             Ok(())
         })?;
 
@@ -57,13 +73,22 @@ pub fn compute_health(
         Ok(())
     })?;
 
-    let computer = HealthComputer {
+    Ok(HealthComputer {
         kind,
         positions,
         denoms_data,
         vaults_data,
-    };
+    })
+}
 
+pub fn compute_health(
+    deps: Deps,
+    kind: AccountKind,
+    q: HealthQuerier,
+    positions: Positions,
+    action: ActionKind,
+) -> HealthResult<HealthValuesResponse> {
+    let computer = health_computer(deps, kind, &q, positions, action, &[])?;
     Ok(computer.compute_health()?.into())
 }
 
@@ -94,11 +119,139 @@ pub fn health_state(
     }
 
     let health = compute_health(deps, kind, q, positions, action)?;
-    if !health.above_max_ltv {
-        Ok(HealthState::Healthy)
+    Ok((&health).into())
+}
+
+/// Each account examined costs a positions query plus price, params and vault queries per
+/// position, so `limit` alone does not bound the work of a page. The page also ends early, before
+/// the account that would take the estimated queries past `MAX_QUERIES_PER_PAGE`. At least one
+/// account is always examined so that paging makes progress.
+pub fn liquidatable_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> HealthResult<LiquidatableAccountsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let q = HealthQuerier::new(&deps)?;
+    let accounts_with_debt = q.query_accounts_with_debt(start_after, limit)?;
+
+    let mut next_start_after = if accounts_with_debt.len() == limit as usize {
+        accounts_with_debt.last().map(|account| account.id.clone())
     } else {
-        Ok(HealthState::Unhealthy {
-            max_ltv_health_factor: health.max_ltv_health_factor.unwrap(),
-        })
+        None
+    };
+
+    let mut accounts = vec![];
+    let mut failed = vec![];
+    let mut queries = 0;
+    let mut last_examined: Option<String> = None;
+    for account in accounts_with_debt {
+        let health = match q.query_positions(&account.id) {
+            Ok(positions) => {
+                queries += estimated_queries(&positions);
+                if queries > MAX_QUERIES_PER_PAGE && last_examined.is_some() {
+                    next_start_after = last_examined;
+                    break;
+                }
+                liquidation_health(deps, &q, positions, account.kind.clone())
+            }
+            Err(err) => {
+                queries += 1;
+                Err(err)
+            }
+        };
+
+        match health {
+            Ok(health) if health.liquidatable => accounts.push(LiquidatableAccount {
+                account_id: account.id.clone(),
+                kind: account.kind,
+                health,
+            }),
+            Ok(_) => {}
+            // One broken account must not prevent the others from being found
+            Err(err) => failed.push(FailedAccount {
+                account_id: account.id.clone(),
+                error: err.to_string(),
+            }),
+        }
+        last_examined = Some(account.id);
     }
+
+    Ok(LiquidatableAccountsResponse {
+        accounts,
+        failed,
+        next_start_after,
+    })
+}
+
+/// Queries made to compute the health of an account: its positions, a price and params per coin
+/// position, and info, value, config and the base token's price and params per vault position.
+/// Denoms shared by positions are only queried once, so this is an upper bound.
+fn estimated_queries(positions: &Positions) -> u32 {
+    let coins = positions.deposits.len() + positions.debts.len() + positions.lends.len();
+    (1 + 2 * coins + 5 * positions.vaults.len()) as u32
+}
+
+fn liquidation_health(
+    deps: Deps,
+    q: &HealthQuerier,
+    positions: Positions,
+    kind: AccountKind,
+) -> HealthResult<HealthValuesResponse> {
+    let computer = health_computer(deps, kind, q, positions, ActionKind::Liquidation, &[])?;
+    Ok(computer.compute_health()?.into())
+}
+
+pub fn max_withdraw_estimate(
+    deps: Deps,
+    account_id: &str,
+    kind: AccountKind,
+    action: ActionKind,
+    denom: &str,
+) -> HealthResult<Uint128> {
+    let q = HealthQuerier::new(&deps)?;
+    let positions = q.query_positions(account_id)?;
+    let computer = health_computer(deps, kind, &q, positions, action, &[denom])?;
+    computer.max_withdraw_amount_estimate(denom)
+}
+
+pub fn max_borrow_estimate(
+    deps: Deps,
+    account_id: &str,
+    kind: AccountKind,
+    action: ActionKind,
+    denom: &str,
+    target: BorrowTarget,
+) -> HealthResult<Uint128> {
+    let q = HealthQuerier::new(&deps)?;
+    let positions = q.query_positions(account_id)?;
+    let mut computer = health_computer(deps, kind, &q, positions, action, &[denom])?;
+
+    // The vault borrowed into may not yet be part of the account's positions
+    if let BorrowTarget::Vault {
+        address,
+    } = &target
+    {
+        if !computer.vaults_data.vault_configs.contains_key(address) {
+            let config = q.query_vault_config(&Vault::new(address.clone()))?;
+            computer.vaults_data.vault_configs.insert(address.clone(), config);
+        }
+    }
+
+    computer.max_borrow_amount_estimate(denom, &target)
+}
+
+pub fn max_swap_estimate(
+    deps: Deps,
+    account_id: &str,
+    kind: AccountKind,
+    action: ActionKind,
+    from_denom: &str,
+    to_denom: &str,
+    swap_kind: SwapKind,
+) -> HealthResult<Uint128> {
+    let q = HealthQuerier::new(&deps)?;
+    let positions = q.query_positions(account_id)?;
+    let computer = health_computer(deps, kind, &q, positions, action, &[from_denom, to_denom])?;
+    computer.max_swap_amount_estimate(from_denom, to_denom, &swap_kind)
 }
@@ -6,7 +6,10 @@ use mars_owner::OwnerInit::SetInitialOwner;
 use mars_rover_health_types::{ConfigResponse, ExecuteMsg, HealthResult, InstantiateMsg, QueryMsg};
 
 use crate::{
-    compute::{health_state, health_values},
+    compute::{
+        health_state, health_values, liquidatable_accounts, max_borrow_estimate, max_swap_estimate,
+        max_withdraw_estimate,
+    },
     state::{CREDIT_MANAGER, OWNER},
     update_config::update_config,
 };
@@ -67,6 +70,39 @@ pub fn query(deps: Deps, _: Env, msg: QueryMsg) -> HealthResult<Binary> {
             kind,
             action,
         } => to_binary(&health_state(deps, &account_id, kind, action)?),
+        QueryMsg::MaxWithdrawEstimate {
+            account_id,
+            kind,
+            action,
+            denom,
+        } => to_binary(&max_withdraw_estimate(deps, &account_id, kind, action, &denom)?),
+        QueryMsg::MaxBorrowEstimate {
+            account_id,
+            kind,
+            action,
+            denom,
+            target,
+        } => to_binary(&max_borrow_estimate(deps, &account_id, kind, action, &denom, target)?),
+        QueryMsg::MaxSwapEstimate {
+            account_id,
+            kind,
+            action,
+            from_denom,
+            to_denom,
+            swap_kind,
+        } => to_binary(&max_swap_estimate(
+            deps,
+            &account_id,
+            kind,
+            action,
+            &from_denom,
+            &to_denom,
+            swap_kind,
+        )?),
+        QueryMsg::LiquidatableAccounts {
+            start_after,
+            limit,
+        } => to_binary(&liquidatable_accounts(deps, start_after, limit)?),
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
     };
     res.map_err(Into::into)
@@ -2,7 +2,7 @@ use cosmwasm_std::{Addr, Deps, QuerierWrapper, StdError, StdResult};
 use mars_params::types::vault::VaultConfig;
 use mars_rover::{
     adapters::{oracle::Oracle, params::Params, vault::Vault},
-    msg::query::{ConfigResponse, Positions, QueryMsg as CmQueryMsg},
+    msg::query::{Account, ConfigResponse, Positions, QueryMsg as CmQueryMsg},
 };
 use mars_rover_health_types::HealthResult;
 
@@ -42,6 +42,20 @@ impl<'a> HealthQuerier<'a> {
         )?)
     }
 
+    pub fn query_accounts_with_debt(
+        &self,
+        start_after: Option<String>,
+        limit: u32,
+    ) -> HealthResult<Vec<Account>> {
+        Ok(self.querier.query_wasm_smart(
+            self.credit_manager.to_string(),
+            &CmQueryMsg::AccountsWithDebt {
+                start_after,
+                limit: Some(limit),
+            },
+        )?)
+    }
+
     pub fn query_vault_config(&self, vault: &Vault) -> HealthResult<VaultConfig> {
         Ok(self
             .params
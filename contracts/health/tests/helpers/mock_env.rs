@@ -18,8 +18,8 @@ use mars_params::{
 use mars_red_bank_types::oracle::ActionKind;
 use mars_rover::{adapters::vault::VaultUnchecked, msg::query::Positions};
 use mars_rover_health_types::{
-    AccountKind, ConfigResponse, ExecuteMsg::UpdateConfig, HealthState, HealthValuesResponse,
-    QueryMsg,
+    AccountKind, BorrowTarget, ConfigResponse, ExecuteMsg::UpdateConfig, HealthState,
+    HealthValuesResponse, LiquidatableAccountsResponse, QueryMsg, SwapKind,
 };
 
 use crate::helpers::MockEnvBuilder;
@@ -81,6 +81,77 @@ impl MockEnv {
         )
     }
 
+    pub fn query_max_withdraw_estimate(
+        &self,
+        account_id: &str,
+        kind: AccountKind,
+        denom: &str,
+    ) -> StdResult<Uint128> {
+        self.app.wrap().query_wasm_smart(
+            self.health_contract.clone(),
+            &QueryMsg::MaxWithdrawEstimate {
+                account_id: account_id.to_string(),
+                kind,
+                action: ActionKind::Default,
+                denom: denom.to_string(),
+            },
+        )
+    }
+
+    pub fn query_max_borrow_estimate(
+        &self,
+        account_id: &str,
+        kind: AccountKind,
+        denom: &str,
+        target: BorrowTarget,
+    ) -> StdResult<Uint128> {
+        self.app.wrap().query_wasm_smart(
+            self.health_contract.clone(),
+            &QueryMsg::MaxBorrowEstimate {
+                account_id: account_id.to_string(),
+                kind,
+                action: ActionKind::Default,
+                denom: denom.to_string(),
+                target,
+            },
+        )
+    }
+
+    pub fn query_max_swap_estimate(
+        &self,
+        account_id: &str,
+        kind: AccountKind,
+        from_denom: &str,
+        to_denom: &str,
+        swap_kind: SwapKind,
+    ) -> StdResult<Uint128> {
+        self.app.wrap().query_wasm_smart(
+            self.health_contract.clone(),
+            &QueryMsg::MaxSwapEstimate {
+                account_id: account_id.to_string(),
+                kind,
+                action: ActionKind::Default,
+                from_denom: from_denom.to_string(),
+                to_denom: to_denom.to_string(),
+                swap_kind,
+            },
+        )
+    }
+
+    pub fn query_liquidatable_accounts(
+        &self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<LiquidatableAccountsResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.health_contract.clone(),
+            &QueryMsg::LiquidatableAccounts {
+                start_after,
+                limit,
+            },
+        )
+    }
+
     pub fn query_config(&self) -> ConfigResponse {
         self.app
             .wrap()
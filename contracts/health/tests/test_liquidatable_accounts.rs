@@ -0,0 +1,171 @@
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use mars_params::{
+    msg::AssetParamsUpdate::AddOrUpdate,
+    types::asset::{AssetParamsUnchecked, CmSettings, LiquidationBonus, RedBankSettings},
+};
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::msg::query::{DebtAmount, Positions};
+use mars_rover_health_types::AccountKind;
+
+use crate::helpers::MockEnv;
+
+pub mod helpers;
+
+#[test]
+fn pages_through_accounts_with_debt() {
+    let mut mock = MockEnv::new().build().unwrap();
+
+    let umars = "umars";
+    set_umars_params(&mut mock, umars);
+
+    // (account id, deposit, debt)
+    let accounts = [("1", 30, 2), ("2", 30, 20), ("3", 30, 0), ("4", 10, 9)];
+    for (account_id, deposit, debt) in accounts {
+        mock.set_positions_response(
+            account_id,
+            &Positions {
+                account_id: account_id.to_string(),
+                deposits: vec![Coin {
+                    denom: umars.to_string(),
+                    amount: Uint128::new(deposit),
+                }],
+                debts: if debt == 0 {
+                    vec![]
+                } else {
+                    vec![DebtAmount {
+                        denom: umars.to_string(),
+                        shares: Default::default(),
+                        amount: Uint128::new(debt),
+                    }]
+                },
+                lends: vec![],
+                vaults: vec![],
+            },
+        );
+    }
+
+    // Accounts 1 and 2 are examined, only 2 is liquidatable
+    let res = mock.query_liquidatable_accounts(None, Some(2)).unwrap();
+    assert_eq!(res.accounts.len(), 1);
+    let account = res.accounts.first().unwrap();
+    assert_eq!(account.account_id, "2");
+    assert_eq!(account.kind, AccountKind::Default);
+    assert_eq!(
+        account.health,
+        mock.query_health_values("2", AccountKind::Default, ActionKind::Liquidation).unwrap()
+    );
+    assert!(account.health.liquidation_health_factor.unwrap() < Decimal::one());
+    assert_eq!(res.next_start_after, Some("2".to_string()));
+
+    // Account 3 has no debt and is not examined
+    let res = mock.query_liquidatable_accounts(res.next_start_after, Some(2)).unwrap();
+    assert_eq!(res.accounts.iter().map(|a| a.account_id.as_str()).collect::<Vec<_>>(), vec!["4"]);
+    assert!(res.failed.is_empty());
+    assert_eq!(res.next_start_after, None);
+}
+
+#[test]
+fn failing_accounts_are_reported_without_stopping_the_page() {
+    let mut mock = MockEnv::new().build().unwrap();
+
+    let umars = "umars";
+    set_umars_params(&mut mock, umars);
+
+    // Account 2 holds a coin without a price, its health can not be computed
+    let accounts = [("1", umars), ("2", "unpriced"), ("3", umars)];
+    for (account_id, deposit_denom) in accounts {
+        mock.set_positions_response(
+            account_id,
+            &Positions {
+                account_id: account_id.to_string(),
+                deposits: vec![Coin {
+                    denom: deposit_denom.to_string(),
+                    amount: Uint128::new(10),
+                }],
+                debts: vec![DebtAmount {
+                    denom: umars.to_string(),
+                    shares: Default::default(),
+                    amount: Uint128::new(9),
+                }],
+                lends: vec![],
+                vaults: vec![],
+            },
+        );
+    }
+
+    let res = mock.query_liquidatable_accounts(None, Some(3)).unwrap();
+    assert_eq!(
+        res.accounts.iter().map(|a| a.account_id.as_str()).collect::<Vec<_>>(),
+        vec!["1", "3"]
+    );
+    assert_eq!(res.failed.len(), 1);
+    assert_eq!(res.failed.first().unwrap().account_id, "2");
+    assert_eq!(res.next_start_after, Some("3".to_string()));
+}
+
+#[test]
+fn page_ends_early_once_query_bound_is_reached() {
+    let mut mock = MockEnv::new().build().unwrap();
+
+    let umars = "umars";
+    set_umars_params(&mut mock, umars);
+
+    // 1 + 2 * 101 = 203 estimated queries per account, two of them exceed the bound of 300
+    for account_id in ["1", "2"] {
+        mock.set_positions_response(
+            account_id,
+            &Positions {
+                account_id: account_id.to_string(),
+                deposits: vec![
+                    Coin {
+                        denom: umars.to_string(),
+                        amount: Uint128::new(1),
+                    };
+                    100
+                ],
+                debts: vec![DebtAmount {
+                    denom: umars.to_string(),
+                    shares: Default::default(),
+                    amount: Uint128::new(90),
+                }],
+                lends: vec![],
+                vaults: vec![],
+            },
+        );
+    }
+
+    let res = mock.query_liquidatable_accounts(None, Some(10)).unwrap();
+    assert_eq!(res.accounts.iter().map(|a| a.account_id.as_str()).collect::<Vec<_>>(), vec!["1"]);
+    assert_eq!(res.next_start_after, Some("1".to_string()));
+
+    let res = mock.query_liquidatable_accounts(res.next_start_after, Some(10)).unwrap();
+    assert_eq!(res.accounts.iter().map(|a| a.account_id.as_str()).collect::<Vec<_>>(), vec!["2"]);
+    assert_eq!(res.next_start_after, None);
+}
+
+fn set_umars_params(mock: &mut MockEnv, umars: &str) {
+    mock.set_price(umars, Decimal::one(), ActionKind::Liquidation);
+    mock.update_asset_params(AddOrUpdate {
+        params: AssetParamsUnchecked {
+            denom: umars.to_string(),
+            credit_manager: CmSettings {
+                whitelisted: true,
+                hls: None,
+            },
+            red_bank: RedBankSettings {
+                deposit_enabled: true,
+                borrow_enabled: true,
+            },
+            max_loan_to_value: Decimal::percent(40),
+            liquidation_threshold: Decimal::percent(50),
+            liquidation_bonus: LiquidationBonus {
+                starting_lb: Decimal::percent(1u64),
+                slope: Decimal::from_atomics(2u128, 0).unwrap(),
+                min_lb: Decimal::percent(2u64),
+                max_lb: Decimal::percent(10u64),
+            },
+            protocol_liquidation_fee: Decimal::percent(2u64),
+            deposit_cap: Default::default(),
+        },
+    });
+}
@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use mars_params::msg::AssetParamsUpdate::AddOrUpdate;
+use mars_red_bank_types::oracle::ActionKind;
+use mars_rover::msg::query::{DebtAmount, Positions};
+use mars_rover_health_types::{AccountKind, BorrowTarget, SwapKind};
+
+use crate::helpers::{default_asset_params, MockEnv};
+
+pub mod helpers;
+
+const ACCOUNT_ID: &str = "123";
+
+/// Account with 1000 umars deposited (max ltv 0.5) and 200 uusdc of debt (max ltv 0.8).
+/// Max LTV adjusted collateral is 500 and the debt value is 200.
+fn setup() -> MockEnv {
+    let mut mock = MockEnv::new().build().unwrap();
+
+    set_asset(&mut mock, "umars", Decimal::one(), "0.5", "0.55");
+    set_asset(&mut mock, "uusdc", Decimal::one(), "0.8", "0.85");
+    set_asset(&mut mock, "uatom", Decimal::one(), "0.1", "0.15");
+    set_asset(&mut mock, "ujuno", Decimal::from_str("2").unwrap(), "0.5", "0.55");
+
+    mock.set_positions_response(
+        ACCOUNT_ID,
+        &Positions {
+            account_id: ACCOUNT_ID.to_string(),
+            deposits: vec![Coin {
+                denom: "umars".to_string(),
+                amount: Uint128::new(1000),
+            }],
+            debts: vec![DebtAmount {
+                denom: "uusdc".to_string(),
+                shares: Default::default(),
+                amount: Uint128::new(200),
+            }],
+            lends: vec![],
+            vaults: vec![],
+        },
+    );
+
+    mock
+}
+
+fn set_asset(mock: &mut MockEnv, denom: &str, price: Decimal, max_ltv: &str, lt: &str) {
+    mock.set_price(denom, price, ActionKind::Default);
+    let mut params = default_asset_params(denom);
+    params.max_loan_to_value = Decimal::from_str(max_ltv).unwrap();
+    params.liquidation_threshold = Decimal::from_str(lt).unwrap();
+    mock.update_asset_params(AddOrUpdate {
+        params,
+    });
+}
+
+#[test]
+fn max_withdraw_estimate() {
+    let mock = setup();
+
+    // (500 - 200 - 1) / (1 * 0.5)
+    let amount =
+        mock.query_max_withdraw_estimate(ACCOUNT_ID, AccountKind::Default, "umars").unwrap();
+    assert_eq!(amount, Uint128::new(598));
+
+    // Nothing deposited of denom
+    let amount =
+        mock.query_max_withdraw_estimate(ACCOUNT_ID, AccountKind::Default, "uatom").unwrap();
+    assert_eq!(amount, Uint128::zero());
+}
+
+#[test]
+fn max_borrow_estimate() {
+    let mock = setup();
+
+    // (500 - 200 - 1) / (1 * (1 - 0.8))
+    let amount = mock
+        .query_max_borrow_estimate(ACCOUNT_ID, AccountKind::Default, "uusdc", BorrowTarget::Deposit)
+        .unwrap();
+    assert_eq!(amount, Uint128::new(1495));
+
+    // (500 - 200 - 1) / 1
+    let amount = mock
+        .query_max_borrow_estimate(ACCOUNT_ID, AccountKind::Default, "uusdc", BorrowTarget::Wallet)
+        .unwrap();
+    assert_eq!(amount, Uint128::new(299));
+
+    // Denom not part of positions is priced as well: (500 - 200 - 1) / 2
+    let amount = mock
+        .query_max_borrow_estimate(ACCOUNT_ID, AccountKind::Default, "ujuno", BorrowTarget::Wallet)
+        .unwrap();
+    assert_eq!(amount, Uint128::new(149));
+}
+
+#[test]
+fn max_swap_estimate() {
+    let mock = setup();
+
+    // Swapping to a higher max ltv denom allows the full balance
+    let amount = mock
+        .query_max_swap_estimate(
+            ACCOUNT_ID,
+            AccountKind::Default,
+            "umars",
+            "uusdc",
+            SwapKind::Default,
+        )
+        .unwrap();
+    assert_eq!(amount, Uint128::new(1000));
+
+    // (500 - 200 - 1) / (1 * (0.5 - 0.1))
+    let amount = mock
+        .query_max_swap_estimate(
+            ACCOUNT_ID,
+            AccountKind::Default,
+            "umars",
+            "uatom",
+            SwapKind::Default,
+        )
+        .unwrap();
+    assert_eq!(amount, Uint128::new(747));
+
+    // Full balance + (800 - 200 - 1) / ((1 - 0.8) * 1)
+    let amount = mock
+        .query_max_swap_estimate(
+            ACCOUNT_ID,
+            AccountKind::Default,
+            "umars",
+            "uusdc",
+            SwapKind::Margin,
+        )
+        .unwrap();
+    assert_eq!(amount, Uint128::new(3995));
+}
@@ -6,7 +6,7 @@ use mars_rover::msg::QueryMsg;
 use crate::{
     execute::{set_account_kind_response, set_position_response},
     msg::{ExecuteMsg, InstantiateMsg},
-    query::{query_account_kind, query_config, query_positions},
+    query::{query_account_kind, query_accounts_with_debt, query_config, query_positions},
     state::CONFIG,
 };
 
@@ -50,6 +50,10 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::AccountKind {
             account_id,
         } => to_binary(&query_account_kind(deps, account_id)?),
+        QueryMsg::AccountsWithDebt {
+            start_after,
+            limit,
+        } => to_binary(&query_accounts_with_debt(deps, start_after, limit)?),
         _ => unimplemented!("query msg not supported"),
     }
 }
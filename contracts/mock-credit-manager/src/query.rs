@@ -1,5 +1,6 @@
-use cosmwasm_std::{Deps, StdResult};
-use mars_rover::msg::query::{ConfigResponse, Positions};
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+use mars_rover::msg::query::{Account, ConfigResponse, Positions};
 use mars_rover_health_types::AccountKind;
 
 use crate::state::{ACCOUNT_KINDS, CONFIG, POSITION_RESPONSES};
@@ -12,6 +13,27 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     CONFIG.load(deps.storage)
 }
 
+pub fn query_accounts_with_debt(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Account>> {
+    let start = start_after.as_deref().map(Bound::exclusive);
+    POSITION_RESPONSES
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, positions)| !positions.debts.is_empty()))
+        .take(limit.unwrap_or(10) as usize)
+        .map(|item| {
+            let (id, _) = item?;
+            let kind = query_account_kind(deps, id.clone())?;
+            Ok(Account {
+                id,
+                kind,
+            })
+        })
+        .collect()
+}
+
 pub fn query_account_kind(deps: Deps, account_id: String) -> StdResult<AccountKind> {
     Ok(ACCOUNT_KINDS.may_load(deps.storage, &account_id)?.unwrap_or(AccountKind::Default))
 }
@@ -15,8 +15,9 @@ use crate::{
     error::ContractResult,
     msg::InstantiateMsg,
     query::{
-        query_lockup_duration, query_unlocking_position, query_unlocking_positions,
-        query_vault_info, query_vault_token_supply, shares_to_base_denom_amount,
+        base_denom_amount_to_shares, query_lockup_duration, query_unlocking_position,
+        query_unlocking_positions, query_vault_info, query_vault_token_supply,
+        shares_to_base_denom_amount,
     },
     state::{
         CHAIN_BANK, COIN_BALANCE, IS_EVIL, LOCKUP_TIME, NEXT_LOCKUP_ID, ORACLE, TOTAL_VAULT_SHARES,
@@ -97,6 +98,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     let res = match msg {
         QueryMsg::TotalVaultTokenSupply {} => to_binary(&query_vault_token_supply(deps.storage)?),
         QueryMsg::Info {} => to_binary(&query_vault_info(deps)?),
+        QueryMsg::PreviewDeposit {
+            amount,
+        } => to_binary(&base_denom_amount_to_shares(deps, amount)?),
         QueryMsg::PreviewRedeem {
             amount,
         } => to_binary(&shares_to_base_denom_amount(deps.storage, amount)?),
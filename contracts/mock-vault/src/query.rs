@@ -1,14 +1,37 @@
-use cosmwasm_std::{Deps, Order, StdError, StdResult, Storage, Uint128};
+use cosmwasm_std::{Coin, Deps, Order, StdError, StdResult, Storage, Uint128};
 use cw_utils::Duration;
 use cw_vault_standard::{extensions::lockup::UnlockingPosition, msg::VaultInfoResponse};
+use mars_red_bank_types::oracle::ActionKind;
 
 use crate::{
+    contract::STARTING_VAULT_SHARES,
     error::{ContractError::NotLockingVault, ContractResult},
     state::{
-        COIN_BALANCE, LOCKUP_TIME, TOTAL_VAULT_SHARES, UNLOCKING_POSITIONS, VAULT_TOKEN_DENOM,
+        COIN_BALANCE, LOCKUP_TIME, ORACLE, TOTAL_VAULT_SHARES, UNLOCKING_POSITIONS,
+        VAULT_TOKEN_DENOM,
     },
 };
 
+pub fn base_denom_amount_to_shares(deps: Deps, amount: Uint128) -> ContractResult<Uint128> {
+    let total_shares = TOTAL_VAULT_SHARES.load(deps.storage)?;
+    if total_shares.is_zero() {
+        return Ok(STARTING_VAULT_SHARES);
+    }
+
+    let oracle = ORACLE.load(deps.storage)?;
+    let balance = COIN_BALANCE.load(deps.storage)?;
+    let total_vault_value = oracle.query_value(&deps.querier, &balance, ActionKind::Default)?;
+    let assets_value = oracle.query_value(
+        &deps.querier,
+        &Coin {
+            denom: balance.denom.clone(),
+            amount,
+        },
+        ActionKind::Default,
+    )?;
+    Ok(total_shares.checked_multiply_ratio(assets_value, total_vault_value)?)
+}
+
 pub fn shares_to_base_denom_amount(
     storage: &dyn Storage,
     shares: Uint128,
@@ -2,14 +2,14 @@ use std::marker::PhantomData;
 
 use cosmwasm_std::{
     to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, CustomMsg, CustomQuery, Decimal, Deps,
-    DepsMut, Env, MessageInfo, Response, WasmMsg,
+    DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg,
 };
 use cw_paginate::paginate_map;
 use cw_storage_plus::{Bound, Map};
 use mars_owner::{Owner, OwnerInit::SetInitialOwner, OwnerUpdate};
-use mars_red_bank_types::swapper::{
-    EstimateExactInSwapResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RouteResponse,
-    RoutesResponse,
+use mars_rover::adapters::swap::{
+    EstimateExactInSwapResponse, EstimateExactOutSwapResponse, ExecuteMsg, InstantiateMsg,
+    QueryMsg, RouteResponse, RoutesResponse,
 };
 
 use crate::{ContractError, ContractResult, Route};
@@ -85,6 +85,11 @@ where
                 denom_out,
                 slippage,
             } => self.swap_exact_in(deps, env, info, coin_in, denom_out, slippage),
+            ExecuteMsg::SwapExactOut {
+                denom_in,
+                coin_out,
+                max_amount_in,
+            } => self.swap_exact_out(deps, env, info, denom_in, coin_out, max_amount_in),
             ExecuteMsg::TransferResult {
                 recipient,
                 denom_in,
@@ -100,6 +105,10 @@ where
                 coin_in,
                 denom_out,
             } => to_binary(&self.estimate_exact_in_swap(deps, env, coin_in, denom_out)?),
+            QueryMsg::EstimateExactOutSwap {
+                denom_in,
+                coin_out,
+            } => to_binary(&self.estimate_exact_out_swap(deps, env, denom_in, coin_out)?),
             QueryMsg::Route {
                 denom_in,
                 denom_out,
@@ -152,6 +161,17 @@ where
         route.estimate_exact_in_swap(&deps.querier, &env, &coin_in)
     }
 
+    fn estimate_exact_out_swap(
+        &self,
+        deps: Deps<Q>,
+        env: Env,
+        denom_in: String,
+        coin_out: Coin,
+    ) -> ContractResult<EstimateExactOutSwapResponse> {
+        let route = self.get_route(deps, &denom_in, &coin_out.denom)?;
+        route.estimate_exact_out_swap(&deps.querier, &env, &denom_in, &coin_out)
+    }
+
     fn swap_exact_in(
         &self,
         deps: DepsMut<Q>,
@@ -186,6 +206,40 @@ where
             .add_attribute("slippage", slippage.to_string()))
     }
 
+    fn swap_exact_out(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        info: MessageInfo,
+        denom_in: String,
+        coin_out: Coin,
+        max_amount_in: Uint128,
+    ) -> ContractResult<Response<M>> {
+        let swap_msg = self
+            .get_route(deps.as_ref(), &denom_in, &coin_out.denom)?
+            .build_exact_out_swap_msg(&deps.querier, &env, &denom_in, &coin_out, max_amount_in)?;
+
+        // Send back result of swapper along with the unspent input to sender
+        let transfer_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::<R>::TransferResult {
+                recipient: info.sender,
+                denom_in: denom_in.clone(),
+                denom_out: coin_out.denom.clone(),
+            })?,
+        });
+
+        Ok(Response::new()
+            .add_message(swap_msg)
+            .add_message(transfer_msg)
+            .add_attribute("action", "swap_exact_out")
+            .add_attribute("denom_in", denom_in)
+            .add_attribute("max_amount_in", max_amount_in)
+            .add_attribute("denom_out", coin_out.denom)
+            .add_attribute("amount_out", coin_out.amount))
+    }
+
     fn transfer_result(
         &self,
         deps: DepsMut<Q>,
@@ -1,7 +1,9 @@
 use std::fmt::{Debug, Display};
 
-use cosmwasm_std::{Coin, CosmosMsg, CustomMsg, CustomQuery, Decimal, Env, QuerierWrapper};
-use mars_red_bank_types::swapper::EstimateExactInSwapResponse;
+use cosmwasm_std::{
+    Coin, CosmosMsg, CustomMsg, CustomQuery, Decimal, Env, QuerierWrapper, Uint128,
+};
+use mars_rover::adapters::swap::{EstimateExactInSwapResponse, EstimateExactOutSwapResponse};
 use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -37,4 +39,24 @@ where
         env: &Env,
         coin_in: &Coin,
     ) -> ContractResult<EstimateExactInSwapResponse>;
+
+    /// Build a message for executing the trade, given the exact output wanted and the maximum
+    /// amount of the input denom that may be spent
+    fn build_exact_out_swap_msg(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        env: &Env,
+        denom_in: &str,
+        coin_out: &Coin,
+        max_amount_in: Uint128,
+    ) -> ContractResult<CosmosMsg<M>>;
+
+    /// Query to get the estimated amount of the input denom needed to receive an exact output
+    fn estimate_exact_out_swap(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        env: &Env,
+        denom_in: &str,
+        coin_out: &Coin,
+    ) -> ContractResult<EstimateExactOutSwapResponse>;
 }
@@ -2,11 +2,14 @@ use cosmwasm_std::{
     coins, to_binary, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env,
     MessageInfo, Response, StdError, StdResult, Uint128,
 };
-use mars_red_bank_types::swapper::{
-    EstimateExactInSwapResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+use mars_rover::adapters::swap::{
+    EstimateExactInSwapResponse, EstimateExactOutSwapResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
 };
 
 pub const MOCK_SWAP_RESULT: Uint128 = Uint128::new(1337);
+pub const MOCK_SWAP_EXACT_OUT_AMOUNT_IN: Uint128 = Uint128::new(420);
+/// Exact-out swaps deliver at most this amount, simulating a swap that falls short of the request
+pub const MOCK_SWAP_EXACT_OUT_MAX_AMOUNT_OUT: Uint128 = Uint128::new(100_000);
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
@@ -38,6 +41,11 @@ pub fn execute(
             denom_out,
             slippage,
         } => swap_exact_in(deps, env, info, coin_in, denom_out, slippage),
+        ExecuteMsg::SwapExactOut {
+            denom_in,
+            coin_out,
+            max_amount_in,
+        } => swap_exact_out(deps, env, info, denom_in, coin_out, max_amount_in),
     }
 }
 
@@ -56,6 +64,9 @@ pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::EstimateExactInSwap {
             ..
         } => to_binary(&estimate_exact_in_swap()),
+        QueryMsg::EstimateExactOutSwap {
+            ..
+        } => to_binary(&estimate_exact_out_swap()),
     }
 }
 
@@ -65,6 +76,12 @@ pub fn estimate_exact_in_swap() -> EstimateExactInSwapResponse {
     }
 }
 
+pub fn estimate_exact_out_swap() -> EstimateExactOutSwapResponse {
+    EstimateExactOutSwapResponse {
+        amount: MOCK_SWAP_EXACT_OUT_AMOUNT_IN,
+    }
+}
+
 pub fn swap_exact_in(
     deps: DepsMut,
     env: Env,
@@ -91,3 +108,43 @@ pub fn swap_exact_in(
 
     Ok(Response::new().add_attribute("action", "transfer_result").add_message(transfer_msg))
 }
+
+pub fn swap_exact_out(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom_in: String,
+    coin_out: Coin,
+    max_amount_in: Uint128,
+) -> StdResult<Response> {
+    let denom_in_balance = deps.querier.query_balance(env.contract.address, &denom_in)?;
+    if denom_in_balance.amount < max_amount_in {
+        return Err(StdError::generic_err("Did not send funds"));
+    }
+
+    if coin_out.denom != "uosmo" {
+        return Err(StdError::generic_err("Mock swapper can only have uosmo as denom out"));
+    }
+
+    if max_amount_in < MOCK_SWAP_EXACT_OUT_AMOUNT_IN {
+        return Err(StdError::generic_err("Max amount in exceeded"));
+    }
+
+    // This is dependent on the mock env to pre-fund this contract with uosmo coins
+    // simulating a swap has taken place. The unspent input is refunded.
+    let amount_out = coin_out.amount.min(MOCK_SWAP_EXACT_OUT_MAX_AMOUNT_OUT);
+    let mut amount = coins(amount_out.u128(), coin_out.denom);
+    let refund = max_amount_in - MOCK_SWAP_EXACT_OUT_AMOUNT_IN;
+    if !refund.is_zero() {
+        amount.push(Coin {
+            denom: denom_in,
+            amount: refund,
+        });
+    }
+    let transfer_msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount,
+    });
+
+    Ok(Response::new().add_attribute("action", "transfer_result").add_message(transfer_msg))
+}
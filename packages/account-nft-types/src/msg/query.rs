@@ -16,6 +16,13 @@ pub enum QueryMsg {
     #[returns(String)]
     NextId {},
 
+    /// Number of times the token has been transferred with `TransferNft` or `SendNft`.
+    /// Lets grants made by an earlier holding of the account be told apart.
+    #[returns(u64)]
+    TransferNonce {
+        token_id: String,
+    },
+
     //--------------------------------------------------------------------------------------------------
     // Base cw721 messages
     //--------------------------------------------------------------------------------------------------
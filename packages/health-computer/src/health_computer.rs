@@ -365,6 +365,77 @@ impl HealthComputer {
         Ok(max_borrow_amount)
     }
 
+    /// The amount of `debt_denom` to borrow and swap into `collateral_denom` for the account to
+    /// reach `target_ltv` (debt value / collateral value). Capped to the max amount that keeps
+    /// max_ltv >= 1. The swap output is assumed to be reduced by the full `slippage`.
+    /// Note: This is an estimate. Guarantees to leave account healthy, but in edge cases,
+    /// due to rounding, it may be slightly too conservative.
+    pub fn leverage_amount_estimate(
+        &self,
+        collateral_denom: &str,
+        debt_denom: &str,
+        target_ltv: Decimal,
+        slippage: Decimal,
+    ) -> HealthResult<Uint128> {
+        let CollateralValue {
+            total_collateral_value,
+            max_ltv_adjusted_collateral,
+            ..
+        } = self.total_collateral_value()?;
+        let debt_value = self.total_debt_value()?;
+
+        let debt_params =
+            self.denoms_data.params.get(debt_denom).ok_or(MissingParams(debt_denom.to_string()))?;
+
+        let target_debt_value = total_collateral_value.checked_mul_floor(target_ltv)?;
+
+        // Zero leverage if already at (or above) the target, unhealthy or not whitelisted
+        if debt_value >= target_debt_value
+            || debt_value >= max_ltv_adjusted_collateral
+            || !debt_params.credit_manager.whitelisted
+        {
+            return Ok(Uint128::zero());
+        }
+
+        let collateral_denom_max_ltv = self.get_coin_max_ltv(collateral_denom)?;
+
+        let debt_denom_price = self
+            .denoms_data
+            .prices
+            .get(debt_denom)
+            .cloned()
+            .ok_or(MissingPrice(debt_denom.to_string()))?;
+
+        // Share of the borrowed value that is received as collateral in the worst case
+        let swap_out_ratio = Decimal::one().checked_sub(slippage)?;
+
+        // The borrowed value to reach the target LTV can be calculated as:
+        //      target ltv = (debt value + borrow value) / (collateral value + (borrow value * swap out ratio))
+        // Re-arranging this to isolate borrow value renders:
+        //      borrow value = (target ltv * collateral value - debt value) / (1 - target ltv * swap out ratio)
+        let target_borrow_value = target_debt_value.checked_sub(debt_value)?.checked_div_floor(
+            Decimal::one().checked_sub(target_ltv.checked_mul(swap_out_ratio)?)?,
+        )?;
+
+        // The max borrowed value that keeps the account healthy can be calculated as:
+        //      1 = (max ltv adjusted value + (borrow value * swap out ratio * collateral denom max ltv)) / (debt value + borrow value)
+        // Re-arranging this to isolate borrow value renders:
+        //      max borrow value = (max ltv adjusted value - debt value) / (1 - swap out ratio * collateral denom max ltv)
+        // The - 1 errs on the side of being more conservative, as in `max_borrow_amount_estimate`.
+        let max_borrow_value = max_ltv_adjusted_collateral
+            .checked_sub(debt_value)?
+            .checked_sub(Uint128::one())?
+            .checked_div_floor(
+                Decimal::one()
+                    .checked_sub(swap_out_ratio.checked_mul(collateral_denom_max_ltv)?)?,
+            )?;
+
+        let borrow_amount =
+            min(target_borrow_value, max_borrow_value).checked_div_floor(debt_denom_price)?;
+
+        Ok(borrow_amount)
+    }
+
     fn total_debt_value(&self) -> HealthResult<Uint128> {
         let mut total = Uint128::zero();
         for debt in &self.positions.debts {
@@ -68,6 +68,73 @@ fn hls_deposit() {
     assert!(!health.is_above_max_ltv());
 }
 
+#[test]
+fn hls_with_multiple_debts() {
+    let ustars = ustars_info();
+    let udai = udai_info();
+
+    let denoms_data = DenomsData {
+        prices: HashMap::from([
+            (ustars.denom.clone(), ustars.price),
+            (udai.denom.clone(), udai.price),
+        ]),
+        params: HashMap::from([
+            (ustars.denom.clone(), ustars.params.clone()),
+            (udai.denom.clone(), udai.params.clone()),
+        ]),
+    };
+
+    let vaults_data = VaultsData {
+        vault_values: Default::default(),
+        vault_configs: Default::default(),
+    };
+
+    let deposit_amount = Uint128::new(300);
+    let ustars_debt = Uint128::new(10);
+    let udai_debt = Uint128::new(1000);
+    let h = HealthComputer {
+        kind: AccountKind::HighLeveredStrategy,
+        positions: Positions {
+            account_id: "123".to_string(),
+            deposits: vec![coin(deposit_amount.u128(), &ustars.denom)],
+            debts: vec![
+                DebtAmount {
+                    denom: ustars.denom.clone(),
+                    shares: Default::default(),
+                    amount: ustars_debt,
+                },
+                DebtAmount {
+                    denom: udai.denom.clone(),
+                    shares: Default::default(),
+                    amount: udai_debt,
+                },
+            ],
+            lends: vec![],
+            vaults: vec![],
+        },
+        denoms_data,
+        vaults_data,
+    };
+
+    let health = h.compute_health().unwrap();
+    let collateral_value = deposit_amount.checked_mul_floor(ustars.price).unwrap();
+    let hls = ustars.params.credit_manager.hls.unwrap();
+    assert_eq!(health.total_collateral_value, collateral_value);
+    assert_eq!(
+        health.max_ltv_adjusted_collateral,
+        collateral_value.checked_mul_floor(hls.max_loan_to_value).unwrap()
+    );
+    assert_eq!(
+        health.liquidation_threshold_adjusted_collateral,
+        collateral_value.checked_mul_floor(hls.liquidation_threshold).unwrap()
+    );
+    assert_eq!(
+        health.total_debt_value,
+        ustars_debt.checked_mul_ceil(ustars.price).unwrap()
+            + udai_debt.checked_mul_ceil(udai.price).unwrap()
+    );
+}
+
 #[test]
 fn hls_vault() {
     let ustars = ustars_info();
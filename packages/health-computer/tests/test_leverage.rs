@@ -0,0 +1,142 @@
+use std::{collections::HashMap, str::FromStr};
+
+use cosmwasm_std::{coin, Decimal, Uint128};
+use mars_rover::msg::query::{DebtAmount, Positions};
+use mars_rover_health_computer::{DenomsData, HealthComputer, VaultsData};
+use mars_rover_health_types::{AccountKind, HealthError::MissingHLSParams};
+
+use crate::helpers::{uatom_info, udai_info, umars_info, CoinInfo};
+
+pub mod helpers;
+
+fn computer(kind: AccountKind, collateral: &CoinInfo, debt: &CoinInfo) -> HealthComputer {
+    let denoms_data = DenomsData {
+        prices: HashMap::from([
+            (collateral.denom.clone(), collateral.price),
+            (debt.denom.clone(), debt.price),
+        ]),
+        params: HashMap::from([
+            (collateral.denom.clone(), collateral.params.clone()),
+            (debt.denom.clone(), debt.params.clone()),
+        ]),
+    };
+
+    HealthComputer {
+        kind,
+        positions: Positions {
+            account_id: "123".to_string(),
+            // Collateral value: 9412, max ltv adjusted: 6117 (default), 6682 (hls)
+            deposits: vec![coin(10_000, &collateral.denom)],
+            debts: vec![],
+            lends: vec![],
+            vaults: vec![],
+        },
+        denoms_data,
+        vaults_data: VaultsData {
+            vault_values: Default::default(),
+            vault_configs: Default::default(),
+        },
+    }
+}
+
+#[test]
+fn leverage_to_target_ltv() {
+    let uatom = uatom_info();
+    let umars = umars_info();
+    let h = computer(AccountKind::Default, &uatom, &umars);
+
+    // (0.5 * 9412 - 0) / (1 - 0.5)
+    let amount = h
+        .leverage_amount_estimate(&uatom.denom, &umars.denom, Decimal::percent(50), Decimal::zero())
+        .unwrap();
+    assert_eq!(amount, Uint128::new(9412));
+}
+
+#[test]
+fn leverage_accounts_for_slippage() {
+    let uatom = uatom_info();
+    let umars = umars_info();
+    let h = computer(AccountKind::Default, &uatom, &umars);
+
+    // (0.5 * 9412 - 0) / (1 - 0.5 * 0.9)
+    let amount = h
+        .leverage_amount_estimate(
+            &uatom.denom,
+            &umars.denom,
+            Decimal::percent(50),
+            Decimal::percent(10),
+        )
+        .unwrap();
+    assert_eq!(amount, Uint128::new(8556));
+}
+
+#[test]
+fn leverage_amount_is_in_debt_denom() {
+    let uatom = uatom_info();
+    let udai = udai_info();
+    let h = computer(AccountKind::Default, &uatom, &udai);
+
+    let amount = h
+        .leverage_amount_estimate(&uatom.denom, &udai.denom, Decimal::percent(50), Decimal::zero())
+        .unwrap();
+    assert_eq!(amount, Uint128::new(30027));
+}
+
+#[test]
+fn leverage_capped_by_max_ltv_of_account_kind() {
+    let mut uatom = uatom_info();
+    let umars = umars_info();
+    let target_ltv = Decimal::from_str("0.7").unwrap();
+
+    // Target: (0.7 * 9412 - 0) / (1 - 0.7) = 21960
+    // Default max: (6117 - 0 - 1) / (1 - 0.65) = 17474
+    let h = computer(AccountKind::Default, &uatom, &umars);
+    let amount = h
+        .leverage_amount_estimate(&uatom.denom, &umars.denom, target_ltv, Decimal::zero())
+        .unwrap();
+    assert_eq!(amount, Uint128::new(17474));
+
+    // HLS max: (6682 - 0 - 1) / (1 - 0.71) = 23037
+    let h = computer(AccountKind::HighLeveredStrategy, &uatom, &umars);
+    let amount = h
+        .leverage_amount_estimate(&uatom.denom, &umars.denom, target_ltv, Decimal::zero())
+        .unwrap();
+    assert_eq!(amount, Uint128::new(21960));
+
+    uatom.params.credit_manager.hls = None;
+    let h = computer(AccountKind::HighLeveredStrategy, &uatom, &umars);
+    let err = h
+        .leverage_amount_estimate(&uatom.denom, &umars.denom, target_ltv, Decimal::zero())
+        .unwrap_err();
+    assert_eq!(err, MissingHLSParams(uatom.denom));
+}
+
+#[test]
+fn zero_leverage_if_above_target_ltv() {
+    let uatom = uatom_info();
+    let umars = umars_info();
+    let mut h = computer(AccountKind::Default, &uatom, &umars);
+    h.positions.debts.push(DebtAmount {
+        denom: umars.denom.clone(),
+        shares: Uint128::new(5_000_000),
+        amount: Uint128::new(5_000),
+    });
+
+    let amount = h
+        .leverage_amount_estimate(&uatom.denom, &umars.denom, Decimal::percent(50), Decimal::zero())
+        .unwrap();
+    assert_eq!(amount, Uint128::zero());
+}
+
+#[test]
+fn zero_leverage_if_debt_denom_not_whitelisted() {
+    let uatom = uatom_info();
+    let mut umars = umars_info();
+    umars.params.credit_manager.whitelisted = false;
+    let h = computer(AccountKind::Default, &uatom, &umars);
+
+    let amount = h
+        .leverage_amount_estimate(&uatom.denom, &umars.denom, Decimal::percent(50), Decimal::zero())
+        .unwrap();
+    assert_eq!(amount, Uint128::zero());
+}
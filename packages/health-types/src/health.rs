@@ -103,6 +103,17 @@ pub enum HealthState {
     },
 }
 
+impl From<&HealthValuesResponse> for HealthState {
+    fn from(h: &HealthValuesResponse) -> Self {
+        match h.max_ltv_health_factor {
+            Some(max_ltv_health_factor) if h.above_max_ltv => HealthState::Unhealthy {
+                max_ltv_health_factor,
+            },
+            _ => HealthState::Healthy,
+        }
+    }
+}
+
 impl fmt::Display for HealthState {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
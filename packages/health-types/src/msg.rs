@@ -2,7 +2,7 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use mars_owner::{OwnerResponse, OwnerUpdate};
 use mars_red_bank_types::oracle::ActionKind;
 
-use crate::AccountKind;
+use crate::{AccountKind, BorrowTarget, HealthValuesResponse, SwapKind};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -41,10 +41,73 @@ pub enum QueryMsg {
         kind: AccountKind,
         action: ActionKind,
     },
+    /// Estimates the max amount of `denom` that can be withdrawn while keeping the account healthy
+    #[returns(cosmwasm_std::Uint128)]
+    MaxWithdrawEstimate {
+        account_id: String,
+        kind: AccountKind,
+        action: ActionKind,
+        denom: String,
+    },
+    /// Estimates the max amount of `denom` that can be borrowed into `target` while keeping the account healthy
+    #[returns(cosmwasm_std::Uint128)]
+    MaxBorrowEstimate {
+        account_id: String,
+        kind: AccountKind,
+        action: ActionKind,
+        denom: String,
+        target: BorrowTarget,
+    },
+    /// Estimates the max amount of `from_denom` that can be swapped to `to_denom` while keeping the account healthy
+    #[returns(cosmwasm_std::Uint128)]
+    MaxSwapEstimate {
+        account_id: String,
+        kind: AccountKind,
+        action: ActionKind,
+        from_denom: String,
+        to_denom: String,
+        swap_kind: SwapKind,
+    },
+    /// Walks accounts with debt in the credit manager and returns those that are liquidatable.
+    /// At most `limit` accounts with debt are examined per call, whether liquidatable or not.
+    /// A page also ends early once the queries it makes would exceed a fixed bound (300, counting
+    /// a positions query plus two per coin position and five per vault position of each account).
+    /// Continue from `next_start_after` until it is None.
+    #[returns(LiquidatableAccountsResponse)]
+    LiquidatableAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     #[returns(ConfigResponse)]
     Config {},
 }
 
+#[cw_serde]
+pub struct LiquidatableAccount {
+    pub account_id: String,
+    pub kind: AccountKind,
+    pub health: HealthValuesResponse,
+}
+
+#[cw_serde]
+pub struct FailedAccount {
+    pub account_id: String,
+    /// Why the health of the account could not be computed, e.g. a missing price
+    pub error: String,
+}
+
+/// `limit` counts the accounts with debt examined. The gas of an account grows with its number of
+/// positions, so a page of large accounts may end before `limit` is reached.
+#[cw_serde]
+pub struct LiquidatableAccountsResponse {
+    pub accounts: Vec<LiquidatableAccount>,
+    /// Examined accounts whose health could not be computed. They do not stop the page.
+    pub failed: Vec<FailedAccount>,
+    /// Last account examined, None once all accounts with debt have been examined.
+    /// Set whenever the page ended early, even if fewer than `limit` accounts were examined.
+    pub next_start_after: Option<String>,
+}
+
 #[cw_serde]
 pub struct ConfigResponse {
     pub credit_manager: Option<String>,
@@ -36,6 +36,15 @@ impl AccountNft {
         querier.query_wasm_smart(self.address().to_string(), &QueryMsg::NextId {})
     }
 
+    pub fn query_transfer_nonce(&self, querier: &QuerierWrapper, token_id: &str) -> StdResult<u64> {
+        querier.query_wasm_smart(
+            self.address().to_string(),
+            &QueryMsg::TransferNonce {
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+
     pub fn query_tokens(
         &self,
         querier: &QuerierWrapper,
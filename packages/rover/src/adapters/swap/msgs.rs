@@ -27,6 +27,13 @@ pub enum ExecuteMsg<Route> {
         denom_out: String,
         slippage: Decimal,
     },
+    /// Perform a swapper with an exact-out amount. The sender funds `max_amount_in` of `denom_in`
+    /// and any input not spent on the swap is refunded.
+    SwapExactOut {
+        denom_in: String,
+        coin_out: Coin,
+        max_amount_in: Uint128,
+    },
     /// Send swapper results back to swapper. Also refunds extra if sent more than needed. Internal use only.
     TransferResult {
         recipient: Addr,
@@ -60,6 +67,13 @@ pub enum QueryMsg {
         coin_in: Coin,
         denom_out: String,
     },
+    /// Return the current spot amount of In needed to receive exactly `coin_out`
+    /// Warning: Do not use this as an oracle price feed. Use Mars-Oracle for pricing.
+    #[returns(EstimateExactOutSwapResponse)]
+    EstimateExactOutSwap {
+        denom_in: String,
+        coin_out: Coin,
+    },
 }
 
 #[cw_serde]
@@ -75,3 +89,8 @@ pub type RoutesResponse<Route> = Vec<RouteResponse<Route>>;
 pub struct EstimateExactInSwapResponse {
     pub amount: Uint128,
 }
+
+#[cw_serde]
+pub struct EstimateExactOutSwapResponse {
+    pub amount: Uint128,
+}
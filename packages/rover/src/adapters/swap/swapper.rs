@@ -1,7 +1,12 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_binary, Addr, Api, Coin, CosmosMsg, Decimal, Empty, StdResult, WasmMsg};
+use cosmwasm_std::{
+    to_binary, Addr, Api, Coin, CosmosMsg, Decimal, Empty, QuerierWrapper, StdResult, Uint128,
+    WasmMsg,
+};
 
-use crate::adapters::swap::ExecuteMsg;
+use crate::adapters::swap::{
+    EstimateExactInSwapResponse, EstimateExactOutSwapResponse, ExecuteMsg, QueryMsg,
+};
 
 #[cw_serde]
 pub struct SwapperBase<T>(T);
@@ -49,4 +54,58 @@ impl Swapper {
             funds: vec![coin_in.clone()],
         }))
     }
+
+    /// Generate message for performing a swapper with an exact amount out.
+    /// Unspent input is refunded to the sender by the swapper.
+    pub fn swap_exact_out_msg(
+        &self,
+        denom_in: &str,
+        coin_out: &Coin,
+        max_amount_in: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.address().to_string(),
+            msg: to_binary(&ExecuteMsg::<Empty>::SwapExactOut {
+                denom_in: denom_in.to_string(),
+                coin_out: coin_out.clone(),
+                max_amount_in,
+            })?,
+            funds: vec![Coin {
+                denom: denom_in.to_string(),
+                amount: max_amount_in,
+            }],
+        }))
+    }
+
+    pub fn estimate_exact_in_swap(
+        &self,
+        querier: &QuerierWrapper,
+        coin_in: &Coin,
+        denom_out: &str,
+    ) -> StdResult<Uint128> {
+        let res: EstimateExactInSwapResponse = querier.query_wasm_smart(
+            self.address(),
+            &QueryMsg::EstimateExactInSwap {
+                coin_in: coin_in.clone(),
+                denom_out: denom_out.to_string(),
+            },
+        )?;
+        Ok(res.amount)
+    }
+
+    pub fn estimate_exact_out_swap(
+        &self,
+        querier: &QuerierWrapper,
+        denom_in: &str,
+        coin_out: &Coin,
+    ) -> StdResult<Uint128> {
+        let res: EstimateExactOutSwapResponse = querier.query_wasm_smart(
+            self.address(),
+            &QueryMsg::EstimateExactOutSwap {
+                denom_in: denom_in.to_string(),
+                coin_out: coin_out.clone(),
+            },
+        )?;
+        Ok(res.amount)
+    }
 }
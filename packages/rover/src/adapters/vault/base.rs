@@ -210,6 +210,19 @@ impl Vault {
         Ok(res.amount.amount)
     }
 
+    pub fn query_preview_deposit(
+        &self,
+        querier: &QuerierWrapper,
+        amount: Uint128,
+    ) -> StdResult<Uint128> {
+        querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: self.address.to_string(),
+            msg: to_binary(&QueryMsg::PreviewDeposit {
+                amount,
+            })?,
+        }))
+    }
+
     pub fn query_preview_redeem(
         &self,
         querier: &QuerierWrapper,
@@ -6,9 +6,13 @@ use cw2::VersionError;
 use cw_utils::PaymentError;
 use mars_liquidation::error::LiquidationError;
 use mars_owner::OwnerError;
+use mars_rover_health_types::HealthError;
 use thiserror::Error;
 
-use crate::coins::Coins;
+use crate::{
+    coins::Coins,
+    msg::{execute::PausableAction, instantiate::Role},
+};
 
 pub type ContractResult<T> = Result<T, ContractError>;
 
@@ -85,6 +89,9 @@ pub enum ContractError {
         new_hf: String,
     },
 
+    #[error("{0}")]
+    Health(#[from] HealthError),
+
     #[error("{reason:?}")]
     HLS {
         reason: String,
@@ -157,6 +164,9 @@ pub enum ContractError {
     #[error("Cannot request liquidation on own credit account")]
     SelfLiquidation,
 
+    #[error("{0} action can not be simulated")]
+    SimulationNotSupported(String),
+
     #[error("{0}")]
     Std(#[from] StdError),
 
@@ -172,6 +182,36 @@ pub enum ContractError {
     #[error("{0}")]
     Version(#[from] VersionError),
 
+    #[error("Permission granted to {delegate} on account {account_id} has expired")]
+    PermissionExpired {
+        delegate: String,
+        account_id: String,
+    },
+
+    #[error("Flash loan of {0} has not been repaid")]
+    FlashLoanNotRepaid(String),
+
+    #[error("No flash loans to repay")]
+    NoFlashLoan,
+
+    #[error("Flash loan of {requested} exceeds the available liquidity of {available}")]
+    FlashLoanExceedsLiquidity {
+        requested: String,
+        available: String,
+    },
+
+    #[error("Target LTV must be greater than zero and less than one, got {0}")]
+    InvalidTargetLtv(Decimal),
+
+    #[error("{0} action can not be part of a trigger order")]
+    TriggerActionNotAllowed(String),
+
+    #[error("Trigger order {0} condition has not been met")]
+    TriggerConditionNotMet(u64),
+
+    #[error("Trigger order {0} is no longer valid, the credit account has been transferred")]
+    TriggerOrderInvalidated(u64),
+
     #[error("{0}")]
     Liquidation(#[from] LiquidationError),
 
@@ -180,4 +220,78 @@ pub enum ContractError {
         slippage: Decimal,
         max_slippage: Decimal,
     },
+
+    #[error("Can not swap {0} into itself")]
+    SwapSameDenom(String),
+
+    #[error("Swap returned {received} {denom}, less than the minimum of {minimum}")]
+    SwapOutputTooLow {
+        denom: String,
+        received: Uint128,
+        minimum: Uint128,
+    },
+
+    #[error("Invalid liquidation basket: {reason}")]
+    InvalidLiquidationBasket {
+        reason: String,
+    },
+
+    #[error(
+        "{account_id} has no bad debt, its collateral must be worth zero with debt outstanding"
+    )]
+    NoBadDebt {
+        account_id: String,
+    },
+
+    #[error(
+        "Rewards-collector account holds none of the debt denoms of {account_id} to write off"
+    )]
+    NoInsuranceBalance {
+        account_id: String,
+    },
+
+    #[error("{account_id} is already of kind {kind}")]
+    AccountKindUnchanged {
+        account_id: String,
+        kind: String,
+    },
+
+    #[error("Can not merge {account_id} into itself")]
+    MergeIntoSelf {
+        account_id: String,
+    },
+
+    #[error("Invalid transfer to account: {reason}")]
+    InvalidTransfer {
+        reason: String,
+    },
+
+    #[error("{action:?} is paused, target: {target:?}")]
+    ActionPaused {
+        action: PausableAction,
+        target: Option<String>,
+    },
+
+    #[error("{user} is missing the {role:?} role required to update {field}")]
+    MissingRole {
+        user: String,
+        role: Role,
+        field: String,
+    },
+
+    #[error("{field} can only be updated through a config proposal")]
+    ConfigUpdateTimelocked {
+        field: String,
+    },
+
+    #[error("Config proposal {proposal_id} can not be executed before {executable_at}")]
+    ConfigProposalNotReady {
+        proposal_id: u64,
+        executable_at: u64,
+    },
+
+    #[error("{account_id} is updated more than once in the batch")]
+    DuplicateAccountUpdate {
+        account_id: String,
+    },
 }
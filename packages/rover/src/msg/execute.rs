@@ -1,14 +1,18 @@
 use std::collections::BTreeSet;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{
+    to_binary, Addr, Api, BankMsg, Binary, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg,
+};
+use cw_utils::Expiration;
 use mars_account_nft_types::nft_config::NftConfigUpdates;
 use mars_owner::OwnerUpdate;
 use mars_rover_health_types::{AccountKind, HealthState};
 
 use crate::{
     adapters::vault::{Vault, VaultPositionType, VaultUnchecked},
-    msg::instantiate::ConfigUpdates,
+    msg::instantiate::{ConfigUpdates, Role},
+    reentrancy_guard::GuardScope,
 };
 
 #[cw_serde]
@@ -23,19 +27,99 @@ pub enum ExecuteMsg {
         account_id: String,
         actions: Vec<Action>,
     },
+    /// Update positions on several credit accounts at once. Funds sent are allocated to the
+    /// `Deposit` actions of each account. If any of the updates fails, the whole batch is reverted.
+    UpdateCreditAccounts(Vec<AccountUpdate>),
+    /// Converts an existing credit account to another kind. The account must follow the rules of
+    /// the new kind and be healthy under its LTVs. Only callable by the account owner.
+    ChangeAccountKind {
+        account_id: String,
+        kind: AccountKind,
+    },
+    /// Moves all positions of `from_account_id` into `into_account_id`, both owned by the sender.
+    /// The merged account must follow the rules of its kind and its health must not weaken.
+    /// If `burn_from_account` is set, the emptied account's NFT is burned, which requires the
+    /// credit manager to be approved for it.
+    MergeAccounts {
+        from_account_id: String,
+        into_account_id: String,
+        burn_from_account: bool,
+    },
     /// Repay debt on behalf of an account, funded from wallet. Must send exactly one coin in message funds.
     /// Allows repaying debts of assets that have been de-listed from credit manager.
     RepayFromWallet {
         account_id: String,
     },
+    /// Liquidate a credit account without owning one. Must send exactly one coin in message funds,
+    /// which is used to repay the liquidatee's debt. Any amount not repaid is refunded.
+    /// The liquidated coins (vault underlying included) are sent to the sender.
+    LiquidateFromWallet {
+        liquidatee_account_id: String,
+        request: LiquidateRequest<VaultUnchecked>,
+    },
+    /// Records the current block as the one the account became liquidatable at, if not yet
+    /// recorded. Clears the record if the account is no longer liquidatable. Callable by anyone.
+    /// Starts the liquidation bonus auction without having to liquidate the account.
+    UpdateLiquidatableSince {
+        account_id: String,
+    },
+    /// Repays the debt of an account whose collateral is worth zero from the coin balances of the
+    /// rewards-collector account. Callable by anyone.
+    WriteOffBadDebt {
+        account_id: String,
+    },
+    /// Stores a bundle of actions to be executed on the account once `condition` is met.
+    /// Orders are executed by keepers, who are paid the configured keeper fee from the account.
+    /// Orders become invalid once the account is transferred to a new owner.
+    CreateTriggerOrder {
+        account_id: String,
+        actions: Vec<Action>,
+        condition: TriggerCondition,
+    },
+    /// Removes a stored trigger order
+    CancelTriggerOrder {
+        account_id: String,
+        trigger_order_id: u64,
+    },
+    /// Executes a stored trigger order if its condition is met. Callable by anyone.
+    /// The keeper fee is sent to the sender.
+    ExecuteTriggerOrder {
+        account_id: String,
+        trigger_order_id: u64,
+    },
+    /// Allows `delegate` to update the credit account using the permitted actions.
+    /// Overwrites any previous grant to the same delegate.
+    /// Grants lapse once the account is transferred to a new owner.
+    GrantPermission {
+        account_id: String,
+        delegate: String,
+        permissions: Vec<ActionPermission>,
+        expires: Option<Expiration>,
+    },
+    /// Removes all permissions granted to `delegate`
+    RevokePermission {
+        account_id: String,
+        delegate: String,
+    },
 
     //--------------------------------------------------------------------------------------------------
     // Privileged messages
     //--------------------------------------------------------------------------------------------------
-    /// Update contract config constants
+    /// Update contract config constants. While the config update delay is non-zero, contract
+    /// addresses and the delay itself must be updated via `ProposeConfigUpdate` instead.
     UpdateConfig {
         updates: ConfigUpdates,
     },
+    /// Stores a config update to be applied once the config update delay has passed
+    ProposeConfigUpdate(ConfigUpdates),
+    /// Applies a proposed config update whose delay has passed
+    ExecuteConfigUpdate {
+        proposal_id: u64,
+    },
+    /// Discards a proposed config update. Callable by the owner or the emergency owner.
+    CancelConfigUpdate {
+        proposal_id: u64,
+    },
     /// Manages owner role state
     UpdateOwner(OwnerUpdate),
     /// Update nft contract config
@@ -43,10 +127,31 @@ pub enum ExecuteMsg {
         config: Option<NftConfigUpdates>,
         ownership: Option<cw721_base::Action>,
     },
+    /// Pauses an action, optionally only for one denom or vault. Only callable by the emergency owner
+    /// or a `Pauser`.
+    PauseAction(ActionPause),
+    /// Lifts a pause set via `PauseAction`. Only callable by the owner.
+    UnpauseAction(ActionPause),
+    /// Grants an administrative role to an address. Only callable by the owner.
+    GrantRole {
+        address: String,
+        role: Role,
+    },
+    /// Revokes an administrative role from an address. Only callable by the owner.
+    RevokeRole {
+        address: String,
+        role: Role,
+    },
     /// Internal actions only callable by the contract itself
     Callback(CallbackMsg),
 }
 
+#[cw_serde]
+pub struct AccountUpdate {
+    pub account_id: String,
+    pub actions: Vec<Action>,
+}
+
 #[cw_serde]
 pub enum ActionAmount {
     Exact(Uint128),
@@ -68,6 +173,70 @@ pub struct ActionCoin {
     pub amount: ActionAmount,
 }
 
+/// Where coins leaving a credit account are sent
+#[cw_serde]
+pub enum RecipientBase<T> {
+    /// Bank transfer to the address
+    Wallet(T),
+    /// Executes `msg` on the contract with the coins attached, similar to cw721 `SendNft`
+    Contract {
+        contract: T,
+        msg: Binary,
+    },
+}
+
+pub type RecipientUnchecked = RecipientBase<String>;
+pub type Recipient = RecipientBase<Addr>;
+
+impl RecipientUnchecked {
+    pub fn check(&self, api: &dyn Api) -> StdResult<Recipient> {
+        Ok(match self {
+            RecipientBase::Wallet(addr) => RecipientBase::Wallet(api.addr_validate(addr)?),
+            RecipientBase::Contract {
+                contract,
+                msg,
+            } => RecipientBase::Contract {
+                contract: api.addr_validate(contract)?,
+                msg: msg.clone(),
+            },
+        })
+    }
+
+    /// Executing a contract hands over control, which could reenter the Credit Manager
+    pub fn is_contract(&self) -> bool {
+        matches!(self, RecipientBase::Contract { .. })
+    }
+}
+
+impl Recipient {
+    pub fn address(&self) -> &Addr {
+        match self {
+            RecipientBase::Wallet(addr) => addr,
+            RecipientBase::Contract {
+                contract,
+                ..
+            } => contract,
+        }
+    }
+
+    pub fn send_msg(&self, coins: Vec<Coin>) -> CosmosMsg {
+        match self {
+            RecipientBase::Wallet(addr) => CosmosMsg::Bank(BankMsg::Send {
+                to_address: addr.to_string(),
+                amount: coins,
+            }),
+            RecipientBase::Contract {
+                contract,
+                msg,
+            } => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                msg: msg.clone(),
+                funds: coins,
+            }),
+        }
+    }
+}
+
 impl From<&Coin> for ActionCoin {
     fn from(value: &Coin) -> Self {
         Self {
@@ -83,6 +252,36 @@ pub enum ChangeExpected {
     Decrease,
 }
 
+#[cw_serde]
+pub enum Comparison {
+    GreaterThan,
+    LessThan,
+}
+
+impl Comparison {
+    pub fn is_met(&self, value: Decimal, threshold: Decimal) -> bool {
+        match self {
+            Comparison::GreaterThan => value > threshold,
+            Comparison::LessThan => value < threshold,
+        }
+    }
+}
+
+/// The condition under which a trigger order can be executed
+#[cw_serde]
+pub enum TriggerCondition {
+    /// The oracle price of `denom` crosses `price` in the direction of `comparison`
+    OraclePrice {
+        denom: String,
+        comparison: Comparison,
+        price: Decimal,
+    },
+    /// The account's max LTV health factor falls below `threshold`
+    HealthFactor {
+        threshold: Decimal,
+    },
+}
+
 #[cw_serde]
 pub enum LiquidateRequest<T> {
     /// Pay back debt of a liquidatable rover account for a bonus. Requires specifying 1) the debt
@@ -109,6 +308,90 @@ pub enum LiquidateRequest<T> {
         request_vault: T,
         position_type: VaultPositionType,
     },
+    /// Liquidate several deposit, lend and vault positions at once. The debt coin is allocated to
+    /// the requests in the order given, each sized as a single liquidation of the account as it is
+    /// after the previous ones. Stops once the debt coin is used up or the account is no longer
+    /// liquidatable. Baskets can not be nested.
+    Basket(Vec<LiquidateRequest<T>>),
+}
+
+impl LiquidateRequest<VaultUnchecked> {
+    pub fn check(self, api: &dyn Api) -> StdResult<LiquidateRequest<Vault>> {
+        Ok(match self {
+            LiquidateRequest::Deposit(denom) => LiquidateRequest::Deposit(denom),
+            LiquidateRequest::Lend(denom) => LiquidateRequest::Lend(denom),
+            LiquidateRequest::Vault {
+                request_vault,
+                position_type,
+            } => LiquidateRequest::Vault {
+                request_vault: request_vault.check(api)?,
+                position_type,
+            },
+            LiquidateRequest::Basket(requests) => LiquidateRequest::Basket(
+                requests.into_iter().map(|r| r.check(api)).collect::<StdResult<_>>()?,
+            ),
+        })
+    }
+}
+
+/// Actions a delegate can be permitted to perform on a credit account.
+/// Actions that move funds out of the account (`Withdraw`, `WithdrawToRecipient`, `RefundAllCoinBalances`,
+/// `ClaimRewards` to a recipient, `Repay` for another account,
+/// `TransferToAccount`) can not be delegated.
+#[cw_serde]
+pub enum ActionPermission {
+    Deposit,
+    Borrow,
+    Lend,
+    Reclaim,
+    ClaimRewards,
+    Repay,
+    EnterVault,
+    ExitVault,
+    RequestVaultUnlock,
+    ExitVaultUnlocked,
+    Liquidate,
+    SwapExactIn,
+    SwapExactOut,
+    ProvideLiquidity,
+    WithdrawLiquidity,
+    FlashLoan,
+    Leverage,
+    RepayFromCollateral,
+    ClosePosition,
+}
+
+/// Actions that can be paused. `Deposit`, `Repay` and `Liquidate` can not be paused,
+/// so that accounts can always be made healthier.
+#[cw_serde]
+pub enum PausableAction {
+    Withdraw,
+    Borrow,
+    Lend,
+    Reclaim,
+    ClaimRewards,
+    EnterVault,
+    ExitVault,
+    RequestVaultUnlock,
+    ExitVaultUnlocked,
+    SwapExactIn,
+    SwapExactOut,
+    ProvideLiquidity,
+    WithdrawLiquidity,
+    RefundAllCoinBalances,
+    FlashBorrow,
+    FlashRepay,
+    Leverage,
+    RepayFromCollateral,
+    ClosePosition,
+    TransferToAccount,
+}
+
+#[cw_serde]
+pub struct ActionPause {
+    pub action: PausableAction,
+    /// Denom for coin actions, vault address for vault actions. `None` pauses the action entirely.
+    pub target: Option<String>,
 }
 
 /// The list of actions that users can perform on their positions
@@ -118,6 +401,11 @@ pub enum Action {
     Deposit(Coin),
     /// Withdraw coin of specified denom and amount
     Withdraw(ActionCoin),
+    /// Withdraw coin of specified denom and amount to `recipient` instead of the account owner
+    WithdrawToRecipient {
+        coin: ActionCoin,
+        recipient: RecipientUnchecked,
+    },
     /// Borrow coin of specified amount from Red Bank
     Borrow(Coin),
     /// Lend coin to the Red Bank
@@ -126,7 +414,12 @@ pub enum Action {
     Reclaim(ActionCoin),
     /// For assets lent to the Red Bank, some can accumulate incentive rewards.
     /// This message claims all of them adds them to account balance.
-    ClaimRewards {},
+    /// Rewards withdrawn instead (see `CallbackMsg::ClaimRewards`) are sent to `recipient`,
+    /// the account owner if `None`.
+    ClaimRewards {
+        #[serde(default)]
+        recipient: Option<RecipientUnchecked>,
+    },
     /// Repay coin of specified amount back to Red Bank. If `amount: AccountBalance` is passed,
     /// the repaid amount will be the minimum between account balance for denom and total owed.
     /// The sender will repay on behalf of the recipient account. If 'recipient_account_id: None',
@@ -172,6 +465,15 @@ pub enum Action {
         denom_out: String,
         slippage: Decimal,
     },
+    /// Perform a swapper with an exact-out amount. At most `max_amount_in` of `denom_in` is spent
+    /// and any unspent input is returned to the account.
+    /// If `max_amount_in: AccountBalance`, the accounts entire balance of `denom_in` may be used.
+    /// The swap is rejected if it returns less than `coin_out`, or if `denom_in` is the denom out.
+    SwapExactOut {
+        denom_in: String,
+        coin_out: Coin,
+        max_amount_in: ActionAmount,
+    },
     /// Add Vec<Coin> to liquidity pool in exchange for LP tokens.
     /// Slippage allowance (%) is used to calculate the minimum amount of LP tokens to receive.
     ProvideLiquidity {
@@ -186,8 +488,315 @@ pub enum Action {
         lp_token: ActionCoin,
         slippage: Decimal,
     },
-    /// Refunds all coin balances back to user wallet
-    RefundAllCoinBalances {},
+    /// Refunds all coin balances back to user wallet, or to `recipient` if set
+    RefundAllCoinBalances {
+        #[serde(default)]
+        recipient: Option<RecipientUnchecked>,
+    },
+    /// Same-tx advance with fee: credits the account with coin of specified amount, taken from
+    /// the coins held by the credit manager rather than borrowed from Red Bank, so it does not
+    /// depend on the account's collateral. It must be returned within the same bundle via
+    /// `FlashRepay`, else the bundle is rejected. Only the final state of the account needs to
+    /// be healthy.
+    FlashBorrow(Coin),
+    /// Return all flash borrowed coins from the account's balance, plus the flash loan fee which
+    /// is sent to the rewards-collector account
+    FlashRepay {},
+    /// Borrow `debt_denom` and swap it into `collateral_denom` so the account's LTV
+    /// (debt value / collateral value) reaches `target_ltv`. The borrow amount is capped
+    /// so the account stays below its max LTV. Slippage allowance (%) applies to the swap
+    /// and is accounted for when sizing the borrow.
+    Leverage {
+        collateral_denom: String,
+        debt_denom: String,
+        target_ltv: Decimal,
+        slippage: Decimal,
+    },
+    /// Swap collateral into `debt_denom` and repay the debt with the swap output. Any surplus
+    /// stays in the account as a deposit. Requires slippage allowance %; the swap must return at
+    /// least the oracle value of the collateral less the slippage allowance. Allowed for unhealthy
+    /// accounts, so no health check is run if this is the only kind of action.
+    /// If `collateral.amount: AccountBalance`, the accounts entire balance of `collateral.denom` will be used.
+    RepayFromCollateral {
+        collateral: ActionCoin,
+        debt_denom: String,
+        slippage: Decimal,
+    },
+    /// Unwind the account into `target_denom`: reclaim all lends, exit unlocked vault positions,
+    /// withdraw the liquidity of LP tokens, swap collateral to repay all debts and swap the
+    /// remainder into `target_denom`.
+    /// Locked and unlocking vault positions are left in place. Requires slippage allowance %.
+    ClosePosition {
+        target_denom: String,
+        slippage: Decimal,
+    },
+    /// Move coins, debt and whole vault positions into another credit account with the same owner.
+    /// If `amount: AccountBalance` is passed, the entire coin balance or debt of the denom is moved.
+    /// Both accounts must end up healthy (or not weakened) and follow the rules of their kind.
+    TransferToAccount {
+        recipient_account_id: String,
+        coins: Vec<ActionCoin>,
+        debt: Vec<ActionCoin>,
+        vault_positions: Vec<VaultUnchecked>,
+    },
+}
+
+impl Action {
+    /// The permission a delegate needs to perform the action. `None` if it can not be delegated.
+    pub fn permission(&self) -> Option<ActionPermission> {
+        let permission = match self {
+            Action::Deposit(..) => ActionPermission::Deposit,
+            Action::Withdraw(..)
+            | Action::WithdrawToRecipient {
+                ..
+            } => return None,
+            Action::Borrow(..) => ActionPermission::Borrow,
+            Action::Lend(..) => ActionPermission::Lend,
+            Action::Reclaim(..) => ActionPermission::Reclaim,
+            Action::ClaimRewards {
+                recipient: None,
+            } => ActionPermission::ClaimRewards,
+            Action::ClaimRewards {
+                recipient: Some(_),
+            } => return None,
+            Action::Repay {
+                recipient_account_id,
+                ..
+            } => {
+                if recipient_account_id.is_some() {
+                    return None;
+                }
+                ActionPermission::Repay
+            }
+            Action::EnterVault {
+                ..
+            } => ActionPermission::EnterVault,
+            Action::ExitVault {
+                ..
+            } => ActionPermission::ExitVault,
+            Action::RequestVaultUnlock {
+                ..
+            } => ActionPermission::RequestVaultUnlock,
+            Action::ExitVaultUnlocked {
+                ..
+            } => ActionPermission::ExitVaultUnlocked,
+            Action::Liquidate {
+                ..
+            } => ActionPermission::Liquidate,
+            Action::SwapExactIn {
+                ..
+            } => ActionPermission::SwapExactIn,
+            Action::SwapExactOut {
+                ..
+            } => ActionPermission::SwapExactOut,
+            Action::ProvideLiquidity {
+                ..
+            } => ActionPermission::ProvideLiquidity,
+            Action::WithdrawLiquidity {
+                ..
+            } => ActionPermission::WithdrawLiquidity,
+            Action::RefundAllCoinBalances {
+                ..
+            } => return None,
+            Action::FlashBorrow(..) | Action::FlashRepay {} => ActionPermission::FlashLoan,
+            Action::Leverage {
+                ..
+            } => ActionPermission::Leverage,
+            Action::RepayFromCollateral {
+                ..
+            } => ActionPermission::RepayFromCollateral,
+            Action::ClosePosition {
+                ..
+            } => ActionPermission::ClosePosition,
+            Action::TransferToAccount {
+                ..
+            } => return None,
+        };
+        Some(permission)
+    }
+
+    /// Whether the action calls a contract that could reenter, relies on bank balance diffs, or
+    /// acts upon another account. The reentrancy guard must then be locked globally rather than
+    /// for the account alone.
+    pub fn requires_global_guard(&self) -> bool {
+        match self {
+            Action::Deposit(..)
+            | Action::Borrow(..)
+            | Action::Lend(..)
+            | Action::Reclaim(..)
+            | Action::Withdraw(..)
+            | Action::FlashRepay {} => false,
+            Action::WithdrawToRecipient {
+                recipient,
+                ..
+            } => recipient.is_contract(),
+            Action::RefundAllCoinBalances {
+                recipient,
+            } => recipient.as_ref().map_or(false, RecipientUnchecked::is_contract),
+            Action::Repay {
+                recipient_account_id,
+                ..
+            } => recipient_account_id.is_some(),
+            // the advance is funded by the coins the credit manager holds for all accounts
+            Action::FlashBorrow(..)
+            | Action::ClaimRewards {
+                ..
+            }
+            | Action::EnterVault {
+                ..
+            }
+            | Action::ExitVault {
+                ..
+            }
+            | Action::RequestVaultUnlock {
+                ..
+            }
+            | Action::ExitVaultUnlocked {
+                ..
+            }
+            | Action::Liquidate {
+                ..
+            }
+            | Action::SwapExactIn {
+                ..
+            }
+            | Action::SwapExactOut {
+                ..
+            }
+            | Action::ProvideLiquidity {
+                ..
+            }
+            | Action::WithdrawLiquidity {
+                ..
+            }
+            | Action::Leverage {
+                ..
+            }
+            | Action::RepayFromCollateral {
+                ..
+            }
+            | Action::ClosePosition {
+                ..
+            }
+            | Action::TransferToAccount {
+                ..
+            } => true,
+        }
+    }
+
+    /// Every pausable action the action runs, along with the denoms and vault addresses each acts
+    /// upon. Composite actions include the steps they run internally. Steps whose targets depend
+    /// on the account's positions (the reclaims, vault exits and swaps of `ClosePosition`) are
+    /// checked once those are known. Empty if the action can not be paused.
+    pub fn pause_targets(&self) -> Vec<(PausableAction, Vec<String>)> {
+        match self {
+            Action::Deposit(..)
+            | Action::Repay {
+                ..
+            }
+            | Action::Liquidate {
+                ..
+            } => vec![],
+            Action::Withdraw(coin)
+            | Action::WithdrawToRecipient {
+                coin,
+                ..
+            } => vec![(PausableAction::Withdraw, vec![coin.denom.clone()])],
+            Action::Borrow(coin) => vec![(PausableAction::Borrow, vec![coin.denom.clone()])],
+            Action::Lend(coin) => vec![(PausableAction::Lend, vec![coin.denom.clone()])],
+            Action::Reclaim(coin) => vec![(PausableAction::Reclaim, vec![coin.denom.clone()])],
+            Action::ClaimRewards {
+                ..
+            } => vec![(PausableAction::ClaimRewards, vec![])],
+            Action::EnterVault {
+                vault,
+                ..
+            } => vec![(PausableAction::EnterVault, vec![vault.address.clone()])],
+            Action::ExitVault {
+                vault,
+                ..
+            } => vec![(PausableAction::ExitVault, vec![vault.address.clone()])],
+            Action::RequestVaultUnlock {
+                vault,
+                ..
+            } => vec![(PausableAction::RequestVaultUnlock, vec![vault.address.clone()])],
+            Action::ExitVaultUnlocked {
+                vault,
+                ..
+            } => vec![(PausableAction::ExitVaultUnlocked, vec![vault.address.clone()])],
+            Action::SwapExactIn {
+                coin_in,
+                denom_out,
+                ..
+            } => {
+                vec![(PausableAction::SwapExactIn, vec![coin_in.denom.clone(), denom_out.clone()])]
+            }
+            Action::SwapExactOut {
+                denom_in,
+                coin_out,
+                ..
+            } => {
+                vec![(PausableAction::SwapExactOut, vec![denom_in.clone(), coin_out.denom.clone()])]
+            }
+            Action::ProvideLiquidity {
+                coins_in,
+                lp_token_out,
+                ..
+            } => vec![(
+                PausableAction::ProvideLiquidity,
+                coins_in.iter().map(|c| c.denom.clone()).chain([lp_token_out.clone()]).collect(),
+            )],
+            Action::WithdrawLiquidity {
+                lp_token,
+                ..
+            } => vec![(PausableAction::WithdrawLiquidity, vec![lp_token.denom.clone()])],
+            Action::RefundAllCoinBalances {
+                ..
+            } => vec![(PausableAction::RefundAllCoinBalances, vec![])],
+            Action::FlashBorrow(coin) => {
+                vec![(PausableAction::FlashBorrow, vec![coin.denom.clone()])]
+            }
+            Action::FlashRepay {} => vec![(PausableAction::FlashRepay, vec![])],
+            Action::Leverage {
+                collateral_denom,
+                debt_denom,
+                ..
+            } => vec![
+                (PausableAction::Leverage, vec![collateral_denom.clone(), debt_denom.clone()]),
+                (PausableAction::Borrow, vec![debt_denom.clone()]),
+                (PausableAction::SwapExactIn, vec![debt_denom.clone(), collateral_denom.clone()]),
+            ],
+            Action::RepayFromCollateral {
+                collateral,
+                debt_denom,
+                ..
+            } => vec![
+                (
+                    PausableAction::RepayFromCollateral,
+                    vec![collateral.denom.clone(), debt_denom.clone()],
+                ),
+                (PausableAction::SwapExactIn, vec![collateral.denom.clone(), debt_denom.clone()]),
+            ],
+            Action::ClosePosition {
+                target_denom,
+                ..
+            } => vec![(PausableAction::ClosePosition, vec![target_denom.clone()])],
+            Action::TransferToAccount {
+                coins,
+                debt,
+                vault_positions,
+                ..
+            } => vec![(
+                PausableAction::TransferToAccount,
+                coins
+                    .iter()
+                    .chain(debt.iter())
+                    .map(|c| c.denom.clone())
+                    .chain(vault_positions.iter().map(|v| v.address.clone()))
+                    .collect(),
+            )],
+        }
+    }
 }
 
 /// Internal actions made by the contract with pre-validated inputs
@@ -198,7 +807,7 @@ pub enum CallbackMsg {
     Withdraw {
         account_id: String,
         coin: ActionCoin,
-        recipient: Addr,
+        recipient: Recipient,
     },
     /// Borrow specified amount of coin from Red Bank;
     /// Increase the token's coin amount and debt shares;
@@ -237,7 +846,7 @@ pub enum CallbackMsg {
     /// For simplicity we withdraw all claimed rewards.
     ClaimRewards {
         account_id: String,
-        recipient: Addr,
+        recipient: Recipient,
     },
     /// Assert MaxLTV is either:
     /// - Healthy, if prior to actions MaxLTV health factor >= 1 or None
@@ -300,6 +909,20 @@ pub enum CallbackMsg {
         denom_out: String,
         slippage: Decimal,
     },
+    /// Perform a swapper with an exact-out amount. At most `max_amount_in` of `denom_in` is spent
+    /// and any unspent input is returned to the account.
+    SwapExactOut {
+        account_id: String,
+        denom_in: String,
+        coin_out: Coin,
+        max_amount_in: ActionAmount,
+    },
+    /// Asserts the credit manager's balance of the coin grew by at least `minimum` since
+    /// `previous_balance` was taken
+    AssertMinReceived {
+        previous_balance: Coin,
+        minimum: Uint128,
+    },
     /// Used to update the coin balance of account after an async action
     UpdateCoinBalance {
         /// Account that needs coin balance adjustment
@@ -319,6 +942,15 @@ pub enum CallbackMsg {
         /// Protocol fee percentage transfered to rewards-collector account
         protocol_fee: Decimal,
     },
+    /// Used to send the coins withdrawn from a vault to a wallet liquidator
+    SendCoinAfterVaultLiquidation {
+        /// Liquidator receiving the coins
+        recipient: Addr,
+        /// Total balance for coin in Rover prior to withdraw
+        previous_balance: Coin,
+        /// Protocol fee percentage transfered to rewards-collector account
+        protocol_fee: Decimal,
+    },
     /// Add Vec<Coin> to liquidity pool in exchange for LP tokens.
     ProvideLiquidity {
         account_id: String,
@@ -333,23 +965,102 @@ pub enum CallbackMsg {
         lp_token: ActionCoin,
         slippage: Decimal,
     },
-    /// Refunds all coin balances back to user wallet
+    /// Refunds all coin balances back to user wallet, or to `recipient` if set
     RefundAllCoinBalances {
         account_id: String,
+        recipient: Option<Recipient>,
     },
     /// Ensures that HLS accounts abide by specific rules
     AssertHlsRules {
         account_id: String,
     },
+    /// Advance specified amount of coin from the credit manager's balance and record it as a
+    /// flash loan
+    FlashBorrow {
+        account_id: String,
+        coin: Coin,
+    },
+    /// Take back all flash loans of the account and transfer the flash loan fee
+    /// to the rewards-collector account
+    FlashRepay {
+        account_id: String,
+    },
+    /// Assert all flash loans of the account have been repaid
+    AssertFlashLoansRepaid {
+        account_id: String,
+    },
+    /// Size the borrow of `debt_denom` to reach `target_ltv` and dispatch the borrow
+    /// and the swap into `collateral_denom`
+    Leverage {
+        account_id: String,
+        collateral_denom: String,
+        debt_denom: String,
+        target_ltv: Decimal,
+        slippage: Decimal,
+    },
+    /// Swap collateral into `debt_denom` and dispatch the repayment of the swap output
+    RepayFromCollateral {
+        account_id: String,
+        collateral: ActionCoin,
+        debt_denom: String,
+        slippage: Decimal,
+    },
+    /// Repay debt of `denom` with the amount the coin balance increased by since `previous_balance`.
+    /// Errors if that amount is less than `min_amount_out`.
+    RepaySwapOutput {
+        account_id: String,
+        denom: String,
+        previous_balance: Uint128,
+        min_amount_out: Uint128,
+    },
+    /// Reclaim all lends and exit unlocked vault positions, then dispatch the repayment of debts
+    /// and the swap of the remainder into `target_denom`
+    ClosePosition {
+        account_id: String,
+        target_denom: String,
+        slippage: Decimal,
+    },
+    /// Withdraw the underlying coins of all LP token balances of the account
+    WithdrawLiquidityBalances {
+        account_id: String,
+        slippage: Decimal,
+    },
+    /// Swap coins that are not a debt denom for the part of each debt the account can not cover
+    /// from its balance, then repay all debts
+    RepayDebtsFromCollateral {
+        account_id: String,
+        slippage: Decimal,
+    },
+    /// Swap all coin balances of the account into `target_denom`
+    SwapBalancesInto {
+        account_id: String,
+        target_denom: String,
+        slippage: Decimal,
+    },
+    /// Move coins, debt shares and vault positions from `account_id` to `recipient_account_id`
+    TransferToAccount {
+        account_id: String,
+        recipient_account_id: String,
+        coins: Vec<ActionCoin>,
+        debt: Vec<ActionCoin>,
+        vault_positions: Vec<Vault>,
+    },
+    /// Records the account as liquidatable after a liquidation, or clears the record if the
+    /// liquidation restored its health
+    UpdateLiquidatableSince {
+        account_id: String,
+    },
     /// At the end of the execution of dispatched actions, this callback removes the guard
     /// and allows subsequent dispatches.
-    RemoveReentrancyGuard {},
+    RemoveReentrancyGuard {
+        scope: GuardScope,
+    },
     /// Send reward amounts of coin from credit manager to recipient by querying balance, claiming rewards,
     /// and comparing previous balance to new balance after reward claim - send the diff to the recipient.
     SendRewardsToAddr {
         account_id: String,
         previous_balances: Vec<Coin>,
-        recipient: Addr,
+        recipient: Recipient,
     },
 }
 
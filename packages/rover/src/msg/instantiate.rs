@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::{Coin, Decimal, Uint128};
 
 use crate::adapters::{
     account_nft::AccountNftUnchecked, health::HealthContractUnchecked,
@@ -48,4 +48,101 @@ pub struct ConfigUpdates {
     pub health_contract: Option<HealthContractUnchecked>,
     /// The Mars Protocol rewards-collector contract. We collect protocol fee for its account.
     pub rewards_collector: Option<String>,
+    /// Fee paid from the account to the keeper executing a trigger order
+    pub keeper_fee: Option<Coin>,
+    /// Fee rate charged on flash loans, paid to the rewards-collector account
+    pub flash_loan_fee: Option<Decimal>,
+    /// Enables, updates or disables the Dutch-auction mode for the liquidation bonus
+    pub liquidation_auction: Option<LiquidationAuctionUpdate>,
+    /// Collateral value, in the oracle's base denom, at or below which an account's debt can be
+    /// written off as bad debt
+    pub bad_debt_dust_threshold: Option<Uint128>,
+    /// Seconds a config proposal must wait before it can be executed. While non-zero, contract
+    /// addresses and the delay itself can only be updated through a config proposal.
+    pub config_update_delay: Option<u64>,
+}
+
+impl ConfigUpdates {
+    /// The role permitted to update each of the set fields, besides the owner.
+    /// `None` if the field can only be updated by the owner.
+    pub fn required_roles(&self) -> Vec<(&'static str, Option<Role>)> {
+        let fields = [
+            ("account_nft", self.account_nft.is_some(), None),
+            ("oracle", self.oracle.is_some(), None),
+            ("red_bank", self.red_bank.is_some(), None),
+            ("incentives", self.incentives.is_some(), None),
+            (
+                "max_unlocking_positions",
+                self.max_unlocking_positions.is_some(),
+                Some(Role::RiskManager),
+            ),
+            ("max_slippage", self.max_slippage.is_some(), Some(Role::RiskManager)),
+            ("swapper", self.swapper.is_some(), None),
+            ("zapper", self.zapper.is_some(), None),
+            ("health_contract", self.health_contract.is_some(), None),
+            ("rewards_collector", self.rewards_collector.is_some(), None),
+            ("keeper_fee", self.keeper_fee.is_some(), Some(Role::ConfigAdmin)),
+            ("flash_loan_fee", self.flash_loan_fee.is_some(), Some(Role::ConfigAdmin)),
+            ("liquidation_auction", self.liquidation_auction.is_some(), Some(Role::RiskManager)),
+            (
+                "bad_debt_dust_threshold",
+                self.bad_debt_dust_threshold.is_some(),
+                Some(Role::RiskManager),
+            ),
+            ("config_update_delay", self.config_update_delay.is_some(), None),
+        ];
+        fields
+            .into_iter()
+            .filter(|(_, set, _)| *set)
+            .map(|(field, _, role)| (field, role))
+            .collect()
+    }
+
+    /// The first of the set fields that must go through a config proposal while the
+    /// config update delay is non-zero
+    pub fn timelocked_field(&self) -> Option<&'static str> {
+        [
+            ("account_nft", self.account_nft.is_some()),
+            ("oracle", self.oracle.is_some()),
+            ("red_bank", self.red_bank.is_some()),
+            ("incentives", self.incentives.is_some()),
+            ("swapper", self.swapper.is_some()),
+            ("zapper", self.zapper.is_some()),
+            ("health_contract", self.health_contract.is_some()),
+            ("rewards_collector", self.rewards_collector.is_some()),
+            ("config_update_delay", self.config_update_delay.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(field, set)| set.then_some(field))
+    }
+}
+
+/// Administrative roles the owner can grant to addresses, so that the owner key does not have to
+/// be shared to tune individual parameters
+#[cw_serde]
+pub enum Role {
+    /// Updates risk parameters: `max_unlocking_positions`, `max_slippage`, `liquidation_auction`
+    /// and `bad_debt_dust_threshold`
+    RiskManager,
+    /// Pauses actions alongside the emergency owner. Only the owner can unpause.
+    Pauser,
+    /// Updates fees: `keeper_fee` and `flash_loan_fee`
+    ConfigAdmin,
+}
+
+/// Rather than paying the full liquidation bonus as soon as an account becomes liquidatable,
+/// the bonus is capped at the asset's `min_lb` and the cap rises linearly to its `max_lb`.
+#[cw_serde]
+pub struct LiquidationAuction {
+    /// Number of blocks, counted from the block the account was first recorded as liquidatable,
+    /// for the bonus cap to reach `max_lb`. Zero pays the full bonus straight away.
+    pub blocks_to_max_bonus: u64,
+}
+
+#[cw_serde]
+pub enum LiquidationAuctionUpdate {
+    /// Enables the auction, or updates it if already enabled
+    Set(LiquidationAuction),
+    /// Disables the auction, the full liquidation bonus is paid straight away
+    Disable,
 }
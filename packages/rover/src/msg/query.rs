@@ -1,12 +1,18 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Coin, Decimal, Uint128};
+use cw_utils::Expiration;
 use mars_owner::OwnerResponse;
+use mars_rover_health_types::HealthValuesResponse;
 
 use crate::{
     adapters::{
         rewards_collector::RewardsCollector,
         vault::{Vault, VaultPosition, VaultUnchecked},
     },
+    msg::{
+        execute::{Action, ActionPermission, LiquidateRequest, TriggerCondition},
+        instantiate::{ConfigUpdates, LiquidationAuction, Role},
+    },
     traits::Coins,
 };
 
@@ -23,6 +29,12 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Enumerate accounts with outstanding debt; start_after accepts account_id
+    #[returns(Vec<Account>)]
+    AccountsWithDebt {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Rover contract-level config
     #[returns(ConfigResponse)]
     Config {},
@@ -82,6 +94,82 @@ pub enum QueryMsg {
     VaultPositionValue {
         vault_position: VaultPosition,
     },
+    /// Estimates the outcome of an `UpdateCreditAccount` sent by `sender` without executing it.
+    /// Like the execution, it fails if `sender` is neither the owner nor a permitted delegate, or
+    /// if any of the actions is paused.
+    /// Swaps, zaps and vault actions are estimated via their respective contracts.
+    /// `Liquidate`, `FlashBorrow`, `FlashRepay` and `ClosePosition` can not be simulated.
+    #[returns(SimulateActionsResponse)]
+    SimulateActions {
+        sender: String,
+        account_id: String,
+        actions: Vec<Action>,
+    },
+    /// Previews the outcome of liquidating `debt_coin` of the liquidatee's debt for the requested
+    /// position, using the same calculations as `Action::Liquidate`.
+    /// Vault positions are previewed in the underlying coins the liquidator receives.
+    /// Baskets are previewed with `BasketLiquidationPreview`.
+    #[returns(LiquidationPreviewResponse)]
+    LiquidationPreview {
+        liquidatee_account_id: String,
+        debt_coin: Coin,
+        request: LiquidateRequest<VaultUnchecked>,
+    },
+    /// Previews the outcome of liquidating `debt_coin` of the liquidatee's debt across the
+    /// requested positions, as `LiquidateRequest::Basket` would
+    #[returns(BasketLiquidationPreviewResponse)]
+    BasketLiquidationPreview {
+        liquidatee_account_id: String,
+        debt_coin: Coin,
+        requests: Vec<LiquidateRequest<VaultUnchecked>>,
+    },
+    /// Block height at which the account was first recorded as liquidatable, if it has not
+    /// recovered since. Used to price the liquidation bonus in auction mode.
+    #[returns(Option<u64>)]
+    LiquidatableSince {
+        account_id: String,
+    },
+    /// Enumerate addresses granted administrative roles; start_after accepts address
+    #[returns(Vec<RolesResponseItem>)]
+    Roles {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Enumerate config updates proposed and not yet executed or cancelled; start_after accepts proposal id
+    #[returns(Vec<ConfigProposal>)]
+    ConfigProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Actions paused by the emergency owner
+    #[returns(Vec<crate::msg::execute::ActionPause>)]
+    PausedActions {},
+    /// Enumerate debt written off against the rewards-collector account; start_after accepts (account_id, denom)
+    #[returns(Vec<BadDebtResponseItem>)]
+    BadDebt {
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    /// Enumerate trigger orders of an account; start_after accepts trigger order id
+    #[returns(Vec<TriggerOrder>)]
+    TriggerOrders {
+        account_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Enumerate trigger orders of all accounts; start_after accepts (account_id, trigger_order_id)
+    #[returns(Vec<TriggerOrder>)]
+    AllTriggerOrders {
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
+    /// Enumerate delegates of an account and their permissions; start_after accepts delegate address
+    #[returns(Vec<Delegate>)]
+    Delegates {
+        account_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -97,6 +185,14 @@ pub struct CoinBalanceResponseItem {
     pub amount: Uint128,
 }
 
+#[cw_serde]
+pub struct BadDebtResponseItem {
+    pub account_id: String,
+    pub denom: String,
+    /// Total amount written off for the account and denom
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 pub struct SharesResponseItem {
     pub account_id: String,
@@ -171,6 +267,52 @@ pub struct ConfigResponse {
     pub zapper: String,
     pub health_contract: String,
     pub rewards_collector: Option<RewardsCollector>,
+    pub keeper_fee: Option<Coin>,
+    pub flash_loan_fee: Decimal,
+    pub liquidation_auction: Option<LiquidationAuction>,
+    /// Collateral value at or below which an account's debt can be written off as bad debt
+    pub bad_debt_dust_threshold: Uint128,
+    /// Seconds a config proposal must wait before it can be executed
+    pub config_update_delay: u64,
+}
+
+#[cw_serde]
+pub struct TriggerOrder {
+    pub account_id: String,
+    pub trigger_order_id: u64,
+    /// Owner of the account at the time the order was created
+    pub owner: String,
+    /// Transfer nonce of the account NFT at the time the order was created. The order is no
+    /// longer valid once the account has been transferred, even if back to `owner`.
+    pub transfer_nonce: u64,
+    pub actions: Vec<Action>,
+    pub condition: TriggerCondition,
+}
+
+#[cw_serde]
+pub struct ConfigProposal {
+    pub proposal_id: u64,
+    pub updates: ConfigUpdates,
+    /// Block time, in seconds, from which the proposal can be executed
+    pub executable_at: u64,
+}
+
+#[cw_serde]
+pub struct RolesResponseItem {
+    pub address: String,
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct Delegate {
+    pub delegate: String,
+    /// Owner of the account at the time permissions were granted
+    pub granter: String,
+    /// Transfer nonce of the account NFT at the time permissions were granted. The grant is no
+    /// longer valid once the account has been transferred, even if back to `granter`.
+    pub transfer_nonce: u64,
+    pub permissions: Vec<ActionPermission>,
+    pub expires: Option<Expiration>,
 }
 
 #[cw_serde]
@@ -178,3 +320,52 @@ pub struct Account {
     pub id: String,
     pub kind: mars_rover_health_types::AccountKind,
 }
+
+#[cw_serde]
+pub enum SimulationRule {
+    HlsRules,
+    MaxLtv,
+    DepositCaps,
+}
+
+#[cw_serde]
+pub struct SimulationRejection {
+    pub rule: SimulationRule,
+    pub reason: String,
+}
+
+#[cw_serde]
+pub struct SimulateActionsResponse {
+    /// Estimated positions of the account after the actions are applied
+    pub positions: Positions,
+    pub health_before: HealthValuesResponse,
+    pub health_after: HealthValuesResponse,
+    /// The first end-of-bundle assertion that would reject the actions, if any
+    pub rejected_by: Option<SimulationRejection>,
+}
+
+#[cw_serde]
+pub struct LiquidationPreviewResponse {
+    /// Debt repaid by the liquidator, adjusted down if the requested amount can not be repaid
+    pub debt_repaid: Coin,
+    /// Coin the liquidator receives
+    pub liquidator_request: Coin,
+    /// Coin taken from the liquidatee
+    pub liquidatee_request: Coin,
+    /// Difference between the liquidatee and liquidator request, sent to the rewards-collector
+    pub protocol_fee: Coin,
+    /// Whether the liquidator receives more value than the debt repaid. Liquidations that are
+    /// not profitable are rejected.
+    pub profitable: bool,
+}
+
+#[cw_serde]
+pub struct BasketLiquidationPreviewResponse {
+    /// Debt repaid by the liquidator for all positions
+    pub debt_repaid: Coin,
+    /// Preview of each position the debt is allocated to, in the order requested. Positions
+    /// left once the debt has been allocated are omitted.
+    pub positions: Vec<LiquidationPreviewResponse>,
+    /// Whether every position is profitable. Otherwise, the basket liquidation is rejected.
+    pub profitable: bool,
+}
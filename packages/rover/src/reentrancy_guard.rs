@@ -1,8 +1,8 @@
 use std::fmt::Debug;
 
 use cosmwasm_schema::{cw_serde, schemars::JsonSchema};
-use cosmwasm_std::{Response, StdResult, Storage};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Order, Response, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
 
 use crate::error::{ContractError, ContractResult};
 
@@ -12,53 +12,93 @@ pub enum GuardState {
     Locked,
 }
 
+/// What a lock applies to
+#[cw_serde]
+pub enum GuardScope {
+    /// Only the given account. Other accounts can be acted upon while it is held.
+    Account(String),
+    /// Every account. Required whenever an untrusted contract is called, or more than one
+    /// account is acted upon, e.g. by liquidations.
+    Global,
+}
+
 /// Contracts we call from Credit Manager should not be attempting to execute actions.
 /// This prevents reentrancy attacks where a contract we call (that turned evil) deposits
 /// into their own credit account and trick our state updates like update_coin_balances.rs which
 /// rely on pre-post querying of bank balances of Rover.
 /// NOTE: https://twitter.com/larry0x/status/1595919149381079041
-pub struct ReentrancyGuard<'a>(Item<'a, GuardState>);
+///
+/// Dispatches only touching a single account through trusted contracts lock that account alone.
+/// Those relying on bank balance diffs lock globally, as a deposit into any other account would
+/// be attributed to them.
+pub struct ReentrancyGuard<'a> {
+    global: Item<'a, GuardState>,
+    accounts: Map<'a, String, GuardState>,
+}
 
 impl<'a> ReentrancyGuard<'a> {
-    pub const fn new(namespace: &'a str) -> Self {
-        Self(Item::new(namespace))
+    pub const fn new(global_namespace: &'a str, accounts_namespace: &'a str) -> Self {
+        Self {
+            global: Item::new(global_namespace),
+            accounts: Map::new(accounts_namespace),
+        }
     }
 
-    /// Ensures the guard is unlocked and sets lock
-    pub fn try_lock(&self, storage: &mut dyn Storage) -> ContractResult<()> {
-        self.assert_unlocked(storage)?;
-        self.transition_state(storage, GuardState::Locked)?;
+    /// Ensures neither the scope nor anything overlapping it is locked and sets lock
+    pub fn try_lock(&self, storage: &mut dyn Storage, scope: &GuardScope) -> ContractResult<()> {
+        self.assert_unlocked(storage, scope)?;
+        self.transition_state(storage, scope, GuardState::Locked)?;
         Ok(())
     }
 
     /// Sets guard to unlocked and returns response to be used for callback
-    pub fn try_unlock<C>(&self, storage: &mut dyn Storage) -> ContractResult<Response<C>>
+    pub fn try_unlock<C>(
+        &self,
+        storage: &mut dyn Storage,
+        scope: &GuardScope,
+    ) -> ContractResult<Response<C>>
     where
         C: Clone + Debug + PartialEq + JsonSchema,
     {
-        self.transition_state(storage, GuardState::Unlocked)?;
+        self.transition_state(storage, scope, GuardState::Unlocked)?;
         Ok(Response::new().add_attribute("action", "remove_reentrancy_guard"))
     }
 
-    fn assert_unlocked(&self, storage: &dyn Storage) -> ContractResult<()> {
-        match self.state(storage)? {
-            GuardState::Locked => {
-                Err(ContractError::ReentrancyGuard("Reentrancy guard is active".to_string()))
+    fn assert_unlocked(&self, storage: &dyn Storage, scope: &GuardScope) -> ContractResult<()> {
+        let overlapping_lock = match scope {
+            GuardScope::Global => {
+                self.state(storage, scope)? == GuardState::Locked
+                    || self.accounts.keys(storage, None, None, Order::Ascending).next().is_some()
+            }
+            GuardScope::Account(_) => {
+                self.state(storage, &GuardScope::Global)? == GuardState::Locked
+                    || self.state(storage, scope)? == GuardState::Locked
             }
-            GuardState::Unlocked => Ok(()),
+        };
+
+        if overlapping_lock {
+            return Err(ContractError::ReentrancyGuard("Reentrancy guard is active".to_string()));
         }
+        Ok(())
     }
 
-    fn state(&self, storage: &dyn Storage) -> StdResult<GuardState> {
-        Ok(self.0.may_load(storage)?.unwrap_or(GuardState::Unlocked))
+    fn state(&self, storage: &dyn Storage, scope: &GuardScope) -> StdResult<GuardState> {
+        let state = match scope {
+            GuardScope::Global => self.global.may_load(storage)?,
+            GuardScope::Account(account_id) => {
+                self.accounts.may_load(storage, account_id.clone())?
+            }
+        };
+        Ok(state.unwrap_or(GuardState::Unlocked))
     }
 
     fn transition_state(
         &self,
         storage: &mut dyn Storage,
+        scope: &GuardScope,
         new_state: GuardState,
     ) -> ContractResult<()> {
-        let current_state = self.state(storage)?;
+        let current_state = self.state(storage, scope)?;
 
         let new_state = match (current_state, new_state) {
             (GuardState::Locked, GuardState::Unlocked) => Ok(GuardState::Unlocked),
@@ -68,6 +108,16 @@ impl<'a> ReentrancyGuard<'a> {
             )),
         }?;
 
-        Ok(self.0.save(storage, &new_state)?)
+        match (scope, new_state) {
+            (GuardScope::Global, new_state) => self.global.save(storage, &new_state)?,
+            // Unlocked accounts are removed, so checking for any locked account is a single read
+            (GuardScope::Account(account_id), GuardState::Locked) => {
+                self.accounts.save(storage, account_id.clone(), &GuardState::Locked)?
+            }
+            (GuardScope::Account(account_id), GuardState::Unlocked) => {
+                self.accounts.remove(storage, account_id.clone())
+            }
+        }
+        Ok(())
     }
 }